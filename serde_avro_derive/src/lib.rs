@@ -2,6 +2,21 @@
 //!
 //! See the [`#[derive(BuildSchema)]`](derive@BuildSchema) documentation for
 //! more information
+//!
+//! # Features
+//!
+//! The `chrono`, `jiff`, `uuid` and `bigdecimal` features provide
+//! [`BuildSchema`] implementations for the types of the corresponding crates,
+//! using the matching avro logical types.
+//!
+//! There is no such feature for the `time` crate: the default serde
+//! representations of its types are not the ones that `serde_avro_fast` maps to
+//! the avro temporal logical types (RFC3339 timestamps, `YYYY-MM-DD` dates and
+//! `HH:MM:SS[.fraction]` times of day), so deriving a schema for them would
+//! produce a schema that they can't be serialized to. Fields of these types can
+//! still use such a schema by specifying a schema with
+//! `#[avro_schema(with = ...)]` along with the corresponding `#[serde(with =
+//! ...)]` modules of the `time` crate.
 
 pub use serde_avro_fast;
 
@@ -17,8 +32,13 @@ use serde_avro_fast::schema::*;
 /// [`#[derive(BuildSchema)]`](derive@BuildSchema)
 pub trait BuildSchema {
 	/// Build a [`Schema`] for this type
+	///
+	/// This fails if the `default` of a field doesn't match the schema of the
+	/// field (see [`SchemaMut::check_defaults`]).
 	fn schema() -> Result<Schema, SchemaError> {
-		Self::schema_mut().try_into()
+		let schema_mut = Self::schema_mut();
+		schema_mut.check_defaults()?;
+		schema_mut.try_into()
 	}
 	/// Build a [`SchemaMut`] for this type
 	fn schema_mut() -> SchemaMut {
@@ -115,6 +135,49 @@ impl SchemaBuilder {
 		}
 	}
 
+	/// If the schema built by `build_schema` has already been built and
+	/// inserted in the [`nodes`](SchemaBuilder::nodes), return the
+	/// [`SchemaKey`] for it.
+	///
+	/// Otherwise, call `build_schema`, which should insert the relevant
+	/// [`SchemaNode`]s in [`nodes`](SchemaBuilder::nodes) (or find them, e.g.
+	/// with [`find_or_build`](SchemaBuilder::find_or_build)) and return the
+	/// [`SchemaKey`] of the schema.
+	///
+	/// This is what `#[avro_schema(with = ...)]` uses.
+	///
+	/// Schemas are identified by the type of `build_schema`, so it has to be
+	/// zero-sized (a function item or a closure that doesn't capture anything),
+	/// which is checked at compile time: function pointers and closures that
+	/// capture values may build different schemas with the same type.
+	/// Contrary to [`find_or_build`](SchemaBuilder::find_or_build), the schema
+	/// is only registered once it is built, so `build_schema` should not end up
+	/// calling itself via `find_or_build_with`: recursive schemas should go
+	/// through a type that implements [`BuildSchema`].
+	pub fn find_or_build_with<F>(&mut self, build_schema: F) -> SchemaKey
+	where
+		F: FnOnce(&mut Self) -> SchemaKey + 'static,
+	{
+		struct AssertZeroSized<F>(std::marker::PhantomData<F>);
+		impl<F> AssertZeroSized<F> {
+			const ASSERT: () = assert!(
+				std::mem::size_of::<F>() == 0,
+				"`find_or_build_with` requires a function item or a closure that doesn't \
+					capture anything, as schemas are identified by its type"
+			);
+		}
+		#[allow(clippy::let_unit_value)] // Forces the evaluation of the assertion
+		let () = AssertZeroSized::<F>::ASSERT;
+
+		let type_id = TypeId::of::<F>();
+		if let Some(&schema_key) = self.already_built_types.get(&type_id) {
+			return schema_key;
+		}
+		let schema_key = build_schema(self);
+		self.already_built_types.insert(type_id, schema_key);
+		schema_key
+	}
+
 	/// Insert a new [`SchemaNode`] corresponding to the schema for the type `T`
 	/// into [`nodes`](SchemaBuilder::nodes), regardless of whether it has
 	/// already been built.
@@ -202,6 +265,59 @@ impl_forward! {
 	i8, i32;
 	i16, i32;
 	usize, i64;
+	isize, i64;
+	char, String;
+	std::num::NonZeroU8, i32;
+	std::num::NonZeroU16, i32;
+	std::num::NonZeroU32, i64;
+	std::num::NonZeroU64, i64;
+	std::num::NonZeroUsize, i64;
+	std::num::NonZeroU128, u128;
+	std::num::NonZeroI8, i32;
+	std::num::NonZeroI16, i32;
+	std::num::NonZeroI32, i32;
+	std::num::NonZeroI64, i64;
+	std::num::NonZeroIsize, i64;
+	std::num::NonZeroI128, i128;
+	// These serialize as strings because the serializer is human-readable
+	std::net::IpAddr, String;
+	std::net::Ipv4Addr, String;
+	std::net::Ipv6Addr, String;
+	std::net::SocketAddr, String;
+	std::net::SocketAddrV4, String;
+	std::net::SocketAddrV6, String;
+}
+
+/// `i128` and `u128` are represented as decimals with scale 0 stored in a
+/// `fixed(17)`
+///
+/// Their largest values have 39 digits, which is more than a `fixed(16)`
+/// allows (38), and `u128::MAX` needs an extra byte for the sign anyway.
+macro_rules! impl_128 {
+	($($ty:ty;)+) => {
+		$(
+			impl BuildSchema for $ty {
+				fn append_schema(builder: &mut SchemaBuilder) {
+					builder.nodes.push(SchemaNode::with_logical_type(
+						Fixed::new(
+							Name::from_fully_qualified_name(concat!(
+								"serde_avro_derive.",
+								stringify!($ty)
+							)),
+							17,
+						)
+						.into(),
+						LogicalType::Decimal(Decimal::new(0, 39)),
+					));
+				}
+				type TypeLookup = Self;
+			}
+		)*
+	};
+}
+impl_128! {
+	i128;
+	u128;
 }
 
 macro_rules! impl_ptr {
@@ -223,6 +339,12 @@ impl_ptr! {
 	std::cell::RefCell,
 	std::cell::Cell,
 }
+impl<T: BuildSchema + ToOwned + ?Sized> BuildSchema for std::borrow::Cow<'_, T> {
+	fn append_schema(builder: &mut SchemaBuilder) {
+		<T as BuildSchema>::append_schema(builder)
+	}
+	type TypeLookup = T::TypeLookup;
+}
 impl<T: BuildSchema + ?Sized> BuildSchema for &'_ T {
 	fn append_schema(builder: &mut SchemaBuilder) {
 		<T as BuildSchema>::append_schema(builder)
@@ -253,6 +375,89 @@ impl<T: BuildSchema> BuildSchema for [T] {
 	type TypeLookup = <Vec<T> as BuildSchema>::TypeLookup;
 }
 
+macro_rules! impl_seq {
+	($($($ty_path:ident)::+,)+) => {
+		$(
+			impl<T: BuildSchema> BuildSchema for $($ty_path)::+<T> {
+				fn append_schema(builder: &mut SchemaBuilder) {
+					<Vec<T> as BuildSchema>::append_schema(builder)
+				}
+				type TypeLookup = <Vec<T> as BuildSchema>::TypeLookup;
+			}
+		)*
+	};
+}
+impl_seq! {
+	std::collections::VecDeque,
+	std::collections::HashSet,
+	std::collections::BTreeSet,
+}
+
+/// Tuples are represented as records whose fields are named after the
+/// position of the element (`_0`, `_1`...), like tuple structs
+macro_rules! impl_tuple {
+	($($len:literal => ($($ty:ident $idx:tt),+);)+) => {
+		$(
+			impl<$($ty: BuildSchema),+> BuildSchema for ($($ty,)+) {
+				fn append_schema(builder: &mut SchemaBuilder) {
+					let reserved_schema_key = builder.reserve();
+					let mut name = concat!("Tuple", $len).to_owned();
+					hash_type_id(&mut name, TypeId::of::<Self::TypeLookup>());
+					let new_node = Record::new(
+						Name::from_fully_qualified_name(name),
+						vec![$(
+							RecordField::new(concat!("_", $idx), builder.find_or_build::<$ty>()),
+						)+],
+					)
+					.into();
+					builder.nodes[reserved_schema_key] = new_node;
+				}
+				type TypeLookup = ($($ty::TypeLookup,)+);
+			}
+		)*
+	};
+}
+impl_tuple! {
+	1 => (T0 0);
+	2 => (T0 0, T1 1);
+	3 => (T0 0, T1 1, T2 2);
+	4 => (T0 0, T1 1, T2 2, T3 3);
+	5 => (T0 0, T1 1, T2 2, T3 3, T4 4);
+	6 => (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+	7 => (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+	8 => (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+	9 => (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8);
+	10 => (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9);
+	11 => (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10);
+	12 => (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11);
+}
+
+impl<T: ?Sized> BuildSchema for std::marker::PhantomData<T> {
+	fn append_schema(builder: &mut SchemaBuilder) {
+		<() as BuildSchema>::append_schema(builder)
+	}
+	type TypeLookup = <() as BuildSchema>::TypeLookup;
+}
+
+/// `std::time::Duration` is represented as a `duration` with 0 months
+///
+/// Serializing a `Duration` that has sub-millisecond precision is an error,
+/// unless [`SerializerConfig::allow_sub_unit_precision_truncation`](serde_avro_fast::ser::SerializerConfig::allow_sub_unit_precision_truncation)
+/// is enabled.
+impl BuildSchema for std::time::Duration {
+	fn append_schema(builder: &mut SchemaBuilder) {
+		builder.nodes.push(SchemaNode::with_logical_type(
+			Fixed::new(
+				Name::from_fully_qualified_name("serde_avro_derive.duration"),
+				12,
+			)
+			.into(),
+			LogicalType::Duration,
+		));
+	}
+	type TypeLookup = Self;
+}
+
 impl<T: BuildSchema> BuildSchema for Option<T> {
 	fn append_schema(builder: &mut SchemaBuilder) {
 		let reserved_schema_key = builder.reserve();
@@ -297,6 +502,90 @@ impl<S: std::ops::Deref<Target = str>, V: BuildSchema> BuildSchema
 	type TypeLookup = <HashMap<String, V> as BuildSchema>::TypeLookup;
 }
 
+macro_rules! impl_logical {
+	($($(#[$attr:meta])* $ty:ty, $variant:ident, $logical_type:ident;)+) => {
+		$(
+			$(#[$attr])*
+			impl BuildSchema for $ty {
+				fn append_schema(builder: &mut SchemaBuilder) {
+					builder.nodes.push(SchemaNode::with_logical_type(
+						RegularType::$variant,
+						LogicalType::$logical_type,
+					));
+				}
+				type TypeLookup = Self;
+			}
+		)*
+	};
+}
+impl_logical!(
+	/// Serializing a `SystemTime` that has sub-microsecond precision is an error,
+	/// unless [`SerializerConfig::allow_sub_unit_precision_truncation`](serde_avro_fast::ser::SerializerConfig::allow_sub_unit_precision_truncation)
+	/// is enabled.
+	std::time::SystemTime, Long, TimestampMicros;
+	/// Serializing a `DateTime` that has sub-microsecond precision (such as
+	/// `Utc::now()`) is an error, unless
+	/// [`SerializerConfig::allow_sub_unit_precision_truncation`](serde_avro_fast::ser::SerializerConfig::allow_sub_unit_precision_truncation)
+	/// is enabled.
+	#[cfg(feature = "chrono")]
+	chrono::DateTime<chrono::Utc>, Long, TimestampMicros;
+	#[cfg(feature = "chrono")]
+	chrono::NaiveDate, Int, Date;
+	/// Serializing a `NaiveTime` that has sub-microsecond precision is an error,
+	/// unless [`SerializerConfig::allow_sub_unit_precision_truncation`](serde_avro_fast::ser::SerializerConfig::allow_sub_unit_precision_truncation)
+	/// is enabled.
+	#[cfg(feature = "chrono")]
+	chrono::NaiveTime, Long, TimeMicros;
+	/// Serializing a `Timestamp` that has sub-microsecond precision (such as
+	/// `Timestamp::now()`) is an error, unless
+	/// [`SerializerConfig::allow_sub_unit_precision_truncation`](serde_avro_fast::ser::SerializerConfig::allow_sub_unit_precision_truncation)
+	/// is enabled.
+	#[cfg(feature = "jiff")]
+	jiff::Timestamp, Long, TimestampMicros;
+	#[cfg(feature = "jiff")]
+	jiff::civil::Date, Int, Date;
+	/// Serializing a `Time` that has sub-microsecond precision is an error,
+	/// unless [`SerializerConfig::allow_sub_unit_precision_truncation`](serde_avro_fast::ser::SerializerConfig::allow_sub_unit_precision_truncation)
+	/// is enabled.
+	#[cfg(feature = "jiff")]
+	jiff::civil::Time, Long, TimeMicros;
+	#[cfg(feature = "uuid")]
+	uuid::Uuid, String, Uuid;
+	#[cfg(feature = "bigdecimal")]
+	bigdecimal::BigDecimal, Bytes, BigDecimal;
+);
+
+#[doc(hidden)]
+/// Used by the [`BuildSchema!`] derive macro to compute the `TypeLookup` of
+/// the fields of a type
+///
+/// This is implemented for every [`BuildSchema`] type, and for
+/// [`WithSchemaFn`], which only stands for a field in that computation.
+pub trait FieldTypeLookup {
+	/// The `TypeLookup` for this field
+	type TypeLookup: std::any::Any;
+}
+impl<T: BuildSchema + ?Sized> FieldTypeLookup for T {
+	type TypeLookup = T::TypeLookup;
+}
+
+#[doc(hidden)]
+/// Used by the [`BuildSchema!`] derive macro as the type of fields whose
+/// schema is built by a `with` function, so that the name of generic types
+/// depends on the type arguments of that function
+///
+/// This purposely does not implement [`BuildSchema`], as the schema of such
+/// fields is built by that function:
+/// ```compile_fail
+/// use serde_avro_derive::{BuildSchema, WithSchemaFn};
+///
+/// WithSchemaFn::<(i32,)>::schema();
+/// ```
+pub struct WithSchemaFn<Args: ?Sized>(std::marker::PhantomData<Args>);
+impl<Args: ?Sized + 'static> FieldTypeLookup for WithSchemaFn<Args> {
+	type TypeLookup = Self;
+}
+
 #[doc(hidden)]
 /// Used by the [`BuildSchema!`] derive macro to generate a unique name for a
 /// struct when it's generic
@@ -311,6 +600,58 @@ pub fn hash_type_id(struct_name: &mut String, type_id: TypeId) {
 	write!(struct_name, "_{:016x?}", hasher.finish()).unwrap();
 }
 
+#[doc(hidden)]
+/// Used by the [`BuildSchema!`] derive macro to splice the fields of a record
+/// into another one, for `#[serde(flatten)]` and internally tagged enums
+pub fn flattened_fields(builder: &SchemaBuilder, record: SchemaKey) -> Vec<RecordField> {
+	match &builder.nodes[record.idx()].type_ {
+		RegularType::Record(record) => record.fields.clone(),
+		_ => panic!(
+			"Flattened types should be represented as records in order to derive `BuildSchema` \
+				(the schema of a flattened field or of the newtype variant of an internally \
+				tagged enum is not a record, or is recursive)"
+		),
+	}
+}
+
+#[doc(hidden)]
+/// Used by the [`BuildSchema!`] derive macro to make the schema of fields that
+/// are not always serialized nullable, as the serializer writes `null` for them
+///
+/// Unions can't directly contain other unions, so if the schema is already a
+/// union, `null` is made its first variant instead of wrapping it.
+pub fn nullable(builder: &mut SchemaBuilder, schema_key: SchemaKey) -> SchemaKey {
+	let null = builder.find_or_build::<()>();
+	let is_null = |key: &SchemaKey| {
+		let node = &builder.nodes[key.idx()];
+		matches!(node.type_, RegularType::Null) && node.logical_type.is_none()
+	};
+	let variants = match &builder.nodes[schema_key.idx()].type_ {
+		RegularType::Union(union) => {
+			if union.variants.first().is_some_and(is_null) {
+				return schema_key;
+			}
+			std::iter::once(null)
+				.chain(union.variants.iter().copied().filter(|key| !is_null(key)))
+				.collect()
+		}
+		_ => vec![null, schema_key],
+	};
+	let new_node_key = SchemaKey::from_idx(builder.nodes.len());
+	builder.nodes.push(Union::new(variants).into());
+	new_node_key
+}
+
+#[doc(hidden)]
+/// Used by the [`BuildSchema!`] derive macro to set the `default` of record
+/// fields
+///
+/// The JSON is validated when the macro is expanded, and that it matches the
+/// schema of the field is checked by [`BuildSchema::schema`].
+pub fn json_default(json: &str) -> serde_json::Value {
+	serde_json::from_str(json).expect("Invalid JSON in `default` attribute")
+}
+
 #[doc(hidden)]
 pub enum LazyNamespace {
 	Pending(fn() -> String),
@@ -315,6 +315,596 @@ fn foo_enum() {
 	);
 }
 
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+enum Event {
+	Created { id: i64, name: String },
+	Deleted { id: i64 },
+	Moved(i64, i64),
+}
+
+#[test]
+fn struct_and_tuple_variants() {
+	test::<Event>(
+		r#"[
+  {
+    "type": "record",
+    "name": "derive_schema.Event.Created",
+    "fields": [
+      {
+        "name": "id",
+        "type": "long"
+      },
+      {
+        "name": "name",
+        "type": "string"
+      }
+    ]
+  },
+  {
+    "type": "record",
+    "name": "derive_schema.Event.Deleted",
+    "fields": [
+      {
+        "name": "id",
+        "type": "long"
+      }
+    ]
+  },
+  {
+    "type": "record",
+    "name": "derive_schema.Event.Moved",
+    "fields": [
+      {
+        "name": "_0",
+        "type": "long"
+      },
+      {
+        "name": "_1",
+        "type": "long"
+      }
+    ]
+  }
+]"#,
+	);
+
+	// Make sure serialization & deserialization both work in this scenario
+	let schema = &Event::schema().unwrap();
+	for event in [
+		Event::Created {
+			id: 1,
+			name: "foo".to_owned(),
+		},
+		Event::Deleted { id: 2 },
+		Event::Moved(3, 4),
+	] {
+		let serialized = serde_avro_fast::to_datum_vec(
+			&event,
+			&mut serde_avro_fast::ser::SerializerConfig::new(schema),
+		)
+		.unwrap();
+		let deserialized: Event = serde_avro_fast::from_datum_slice(&serialized, schema).unwrap();
+		assert_eq!(deserialized, event);
+	}
+}
+
+#[derive(BuildSchema)]
+#[allow(unused)]
+enum GenericEvent<T> {
+	Created { value: T },
+	Deleted,
+}
+
+#[test]
+fn generic_struct_variants() {
+	test::<GenericEvent<i32>>(
+		r#"[
+  {
+    "type": "record",
+    "name": "derive_schema.GenericEvent.Created_TYPEIDHASH",
+    "fields": [
+      {
+        "name": "value",
+        "type": "int"
+      }
+    ]
+  },
+  "null"
+]"#,
+	);
+}
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct Point(f64, f64);
+
+/// Fields of different variants would have the same name (`A_b_c`) if they
+/// were prefixed with the name of their variant
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+#[allow(non_camel_case_types)]
+enum CollidingVariantFields<T> {
+	A_b { c: T },
+	A { b_c: T },
+}
+
+#[test]
+fn colliding_variant_fields() {
+	let schema_json = serde_json::to_string(&CollidingVariantFields::<i32>::schema_mut()).unwrap();
+	assert!(schema_json.contains(r#"{"name":"c","type":"int"}"#));
+	assert!(schema_json.contains(r#"{"name":"b_c","type":"int"}"#));
+}
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct Rgb(
+	#[avro_schema(name = red)] i32,
+	#[avro_schema(name = green)] i32,
+	#[avro_schema(name = blue)] i32,
+);
+
+#[test]
+fn tuple_structs() {
+	test::<Point>(
+		r#"{
+  "type": "record",
+  "name": "derive_schema.Point",
+  "fields": [
+    {
+      "name": "_0",
+      "type": "double"
+    },
+    {
+      "name": "_1",
+      "type": "double"
+    }
+  ]
+}"#,
+	);
+	test::<Rgb>(
+		r#"{
+  "type": "record",
+  "name": "derive_schema.Rgb",
+  "fields": [
+    {
+      "name": "red",
+      "type": "int"
+    },
+    {
+      "name": "green",
+      "type": "int"
+    },
+    {
+      "name": "blue",
+      "type": "int"
+    }
+  ]
+}"#,
+	);
+
+	// Make sure serialization & deserialization both work in this scenario,
+	// including when the record is in a union
+	let schema = &<Option<Point>>::schema().unwrap();
+	for point in [Some(Point(1.5, -2.)), None] {
+		let serialized = serde_avro_fast::to_datum_vec(
+			&point,
+			&mut serde_avro_fast::ser::SerializerConfig::new(schema),
+		)
+		.unwrap();
+		let deserialized: Option<Point> =
+			serde_avro_fast::from_datum_slice(&serialized, schema).unwrap();
+		assert_eq!(deserialized, point);
+	}
+	let schema = &Rgb::schema().unwrap();
+	let rgb = Rgb(1, 2, 3);
+	let serialized = serde_avro_fast::to_datum_vec(
+		&rgb,
+		&mut serde_avro_fast::ser::SerializerConfig::new(schema),
+	)
+	.unwrap();
+	assert_eq!(serialized, [2, 4, 6]);
+	let deserialized: Rgb = serde_avro_fast::from_datum_slice(&serialized, schema).unwrap();
+	assert_eq!(deserialized, rgb);
+}
+
+fn round_trip<T>(schema: &serde_avro_fast::Schema, value: &T) -> Vec<u8>
+where
+	T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+{
+	let serialized = serde_avro_fast::to_datum_vec(
+		value,
+		&mut serde_avro_fast::ser::SerializerConfig::new(schema),
+	)
+	.unwrap();
+	let deserialized: T = serde_avro_fast::from_datum_slice(&serialized, schema).unwrap();
+	assert_eq!(&deserialized, value);
+	serialized
+}
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+#[serde(rename = "Renamed", rename_all = "camelCase")]
+struct SerdeRenames {
+	first_field: i32,
+	#[serde(rename = "second")]
+	second_field: String,
+	#[serde(skip)]
+	skipped: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	maybe: Option<i32>,
+	#[serde(skip_serializing_if = "Vec::is_empty", default)]
+	list: Vec<i32>,
+}
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Level {
+	LowPriority,
+	#[serde(rename = "HI")]
+	High,
+	#[serde(skip)]
+	#[allow(unused)]
+	Internal,
+}
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case", rename_all_fields = "camelCase")]
+enum RenamedEvent {
+	UserCreated {
+		user_id: i64,
+	},
+	#[serde(rename = "gone", rename_all = "UPPERCASE")]
+	UserDeleted {
+		user_id: i64,
+	},
+}
+
+#[test]
+fn serde_attributes() {
+	test::<SerdeRenames>(
+		r#"{
+  "type": "record",
+  "name": "derive_schema.Renamed",
+  "fields": [
+    {
+      "name": "firstField",
+      "type": "int"
+    },
+    {
+      "name": "second",
+      "type": "string"
+    },
+    {
+      "name": "maybe",
+      "type": [
+        "null",
+        "int"
+      ],
+      "default": null
+    },
+    {
+      "name": "list",
+      "type": [
+        "null",
+        {
+          "type": "array",
+          "items": "int"
+        }
+      ],
+      "default": null
+    }
+  ]
+}"#,
+	);
+	test::<Level>(
+		r#"{
+  "type": "enum",
+  "name": "derive_schema.Level",
+  "symbols": [
+    "LOW_PRIORITY",
+    "HI"
+  ]
+}"#,
+	);
+	test::<RenamedEvent>(
+		r#"[
+  {
+    "type": "record",
+    "name": "derive_schema.RenamedEvent.user_created",
+    "fields": [
+      {
+        "name": "userId",
+        "type": "long"
+      }
+    ]
+  },
+  {
+    "type": "record",
+    "name": "derive_schema.RenamedEvent.gone",
+    "fields": [
+      {
+        "name": "USER_ID",
+        "type": "long"
+      }
+    ]
+  }
+]"#,
+	);
+
+	// Make sure serialization & deserialization both work in this scenario
+	let schema = &SerdeRenames::schema().unwrap();
+	round_trip(
+		schema,
+		&SerdeRenames {
+			first_field: 1,
+			second_field: "a".to_owned(),
+			skipped: false,
+			maybe: Some(2),
+			list: vec![3],
+		},
+	);
+	// Fields that are skipped by serde are serialized as null, and deserialized
+	// as absent
+	assert_eq!(
+		round_trip(
+			schema,
+			&SerdeRenames {
+				first_field: 1,
+				second_field: "a".to_owned(),
+				skipped: false,
+				maybe: None,
+				list: Vec::new(),
+			},
+		),
+		[2, 2, b'a', 0, 0]
+	);
+	let schema = &Level::schema().unwrap();
+	assert_eq!(round_trip(schema, &Level::High), [2]);
+	let schema = &RenamedEvent::schema().unwrap();
+	round_trip(schema, &RenamedEvent::UserCreated { user_id: 1 });
+	assert_eq!(
+		round_trip(schema, &RenamedEvent::UserDeleted { user_id: 1 }),
+		[2, 2]
+	);
+}
+
+// Skipped fields still count in the positions of the other fields
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct SkippedTupleField(#[serde(skip)] bool, i32, String);
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+enum SkippedTupleVariantField {
+	Variant(#[serde(skip)] bool, i32, String),
+}
+
+#[test]
+fn skipped_tuple_fields() {
+	test::<SkippedTupleField>(
+		r#"{
+  "type": "record",
+  "name": "derive_schema.SkippedTupleField",
+  "fields": [
+    {
+      "name": "_1",
+      "type": "int"
+    },
+    {
+      "name": "_2",
+      "type": "string"
+    }
+  ]
+}"#,
+	);
+	test::<SkippedTupleVariantField>(
+		r#"[
+  {
+    "type": "record",
+    "name": "derive_schema.SkippedTupleVariantField.Variant",
+    "fields": [
+      {
+        "name": "_1",
+        "type": "int"
+      },
+      {
+        "name": "_2",
+        "type": "string"
+      }
+    ]
+  }
+]"#,
+	);
+
+	let schema = &SkippedTupleField::schema().unwrap();
+	round_trip(schema, &SkippedTupleField(false, 1, "a".to_owned()));
+	let schema = &SkippedTupleVariantField::schema().unwrap();
+	round_trip(
+		schema,
+		&SkippedTupleVariantField::Variant(false, 1, "a".to_owned()),
+	);
+}
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct SkippedUnion {
+	#[serde(skip_serializing_if = "LongOrString::is_zero", default)]
+	value: LongOrString,
+}
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+enum LongOrString {
+	Long(i64),
+	String(String),
+}
+
+impl LongOrString {
+	fn is_zero(&self) -> bool {
+		*self == Self::Long(0)
+	}
+}
+
+impl Default for LongOrString {
+	fn default() -> Self {
+		Self::Long(0)
+	}
+}
+
+#[test]
+fn skip_serializing_if_union() {
+	// `null` is added to the union rather than wrapping it in another union
+	test::<SkippedUnion>(
+		r#"{
+  "type": "record",
+  "name": "derive_schema.SkippedUnion",
+  "fields": [
+    {
+      "name": "value",
+      "type": [
+        "null",
+        "long",
+        "string"
+      ],
+      "default": null
+    }
+  ]
+}"#,
+	);
+
+	let schema = &SkippedUnion::schema().unwrap();
+	assert_eq!(
+		round_trip(
+			schema,
+			&SkippedUnion {
+				value: LongOrString::String("a".to_owned()),
+			},
+		),
+		[4, 2, b'a']
+	);
+	assert_eq!(
+		round_trip(
+			schema,
+			&SkippedUnion {
+				value: LongOrString::Long(0),
+			},
+		),
+		[0]
+	);
+}
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct WithFlatten {
+	id: i64,
+	#[serde(flatten)]
+	metadata: Metadata,
+}
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct Metadata {
+	author: String,
+	version: i32,
+}
+
+#[test]
+fn serde_flatten() {
+	test::<WithFlatten>(
+		r#"{
+  "type": "record",
+  "name": "derive_schema.WithFlatten",
+  "fields": [
+    {
+      "name": "id",
+      "type": "long"
+    },
+    {
+      "name": "author",
+      "type": "string"
+    },
+    {
+      "name": "version",
+      "type": "int"
+    }
+  ]
+}"#,
+	);
+
+	let schema = &WithFlatten::schema().unwrap();
+	round_trip(
+		schema,
+		&WithFlatten {
+			id: 1,
+			metadata: Metadata {
+				author: "me".to_owned(),
+				version: 2,
+			},
+		},
+	);
+}
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+#[serde(tag = "type")]
+enum Shape {
+	Circle { radius: f64 },
+	Square(Metadata),
+	Empty,
+}
+
+#[test]
+fn serde_internally_tagged() {
+	test::<Shape>(
+		r#"[
+  {
+    "type": "record",
+    "name": "derive_schema.Shape.Circle",
+    "fields": [
+      {
+        "name": "type",
+        "type": "string"
+      },
+      {
+        "name": "radius",
+        "type": "double"
+      }
+    ]
+  },
+  {
+    "type": "record",
+    "name": "derive_schema.Shape.Square",
+    "fields": [
+      {
+        "name": "type",
+        "type": "string"
+      },
+      {
+        "name": "author",
+        "type": "string"
+      },
+      {
+        "name": "version",
+        "type": "int"
+      }
+    ]
+  },
+  {
+    "type": "record",
+    "name": "derive_schema.Shape.Empty",
+    "fields": [
+      {
+        "name": "type",
+        "type": "string"
+      }
+    ]
+  }
+]"#,
+	);
+
+	let schema = &Shape::schema().unwrap();
+	round_trip(schema, &Shape::Circle { radius: 1.5 });
+	round_trip(
+		schema,
+		&Shape::Square(Metadata {
+			author: "me".to_owned(),
+			version: 2,
+		}),
+	);
+	assert_eq!(
+		round_trip(schema, &Shape::Empty),
+		[4, 10, b'E', b'm', b'p', b't', b'y']
+	);
+}
+
 #[derive(BuildSchema)]
 #[allow(unused)]
 struct NewType(Box<[u8; 3]>);
@@ -508,3 +1098,588 @@ fn raw_identifiers() {
 	// or may be the only way to use a keyword as an identifier.
 	assert_eq!(x.inner, 5);
 }
+
+/// A documented record
+///
+/// With several paragraphs
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct Documented {
+	/// The amount
+	#[avro_schema(default = "3")]
+	amount: i32,
+	#[avro_schema(default = r#"{"a": [1, 2]}"#)]
+	counts: std::collections::HashMap<String, Vec<i32>>,
+	optional: Option<String>,
+	/// Null unless set
+	#[serde(skip_serializing_if = "String::is_empty", default)]
+	label: String,
+	kind: DocumentedKind,
+}
+
+/// A documented enum
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+enum DocumentedKind {
+	A,
+	B,
+}
+
+#[test]
+fn docs_and_defaults() {
+	test::<Documented>(
+		r#"{
+  "type": "record",
+  "name": "derive_schema.Documented",
+  "doc": "A documented record\n\nWith several paragraphs",
+  "fields": [
+    {
+      "name": "amount",
+      "doc": "The amount",
+      "type": "int",
+      "default": 3
+    },
+    {
+      "name": "counts",
+      "type": {
+        "type": "map",
+        "values": {
+          "type": "array",
+          "items": "int"
+        }
+      },
+      "default": {
+        "a": [
+          1,
+          2
+        ]
+      }
+    },
+    {
+      "name": "optional",
+      "type": [
+        "null",
+        "string"
+      ],
+      "default": null
+    },
+    {
+      "name": "label",
+      "doc": "Null unless set",
+      "type": [
+        "null",
+        "string"
+      ],
+      "default": null
+    },
+    {
+      "name": "kind",
+      "type": {
+        "type": "enum",
+        "name": "DocumentedKind",
+        "doc": "A documented enum",
+        "symbols": [
+          "A",
+          "B"
+        ]
+      }
+    }
+  ]
+}"#,
+	);
+
+	let schema = &Documented::schema().unwrap();
+	let value = Documented {
+		amount: 1,
+		counts: std::collections::HashMap::new(),
+		optional: None,
+		label: "x".to_owned(),
+		kind: DocumentedKind::B,
+	};
+	assert_eq!(round_trip(schema, &value), [2, 0, 0, 2, 2, b'x', 2]);
+	let value = Documented {
+		label: String::new(),
+		..value
+	};
+	assert_eq!(round_trip(schema, &value), [2, 0, 0, 0, 2]);
+}
+
+#[derive(BuildSchema)]
+#[allow(unused)]
+struct InvalidDefault {
+	#[avro_schema(default = r#""not a number""#)]
+	amount: i32,
+}
+
+#[test]
+fn invalid_default() {
+	assert_eq!(
+		InvalidDefault::schema().unwrap_err().to_string(),
+		"The default of field `amount` of record `derive_schema.InvalidDefault` does not \
+			match the schema of the field: \"not a number\""
+	);
+}
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct StdTypes<'a> {
+	deque: std::collections::VecDeque<i32>,
+	hash_set: std::collections::HashSet<i32>,
+	btree_set: std::collections::BTreeSet<i32>,
+	btree_map: std::collections::BTreeMap<String, i32>,
+	tuple: (i32, String),
+	same_tuple: (i32, String),
+	signed: i128,
+	unsigned: u128,
+	character: char,
+	cow: std::borrow::Cow<'a, str>,
+	boxed: Box<str>,
+	non_zero: std::num::NonZeroU32,
+	duration: std::time::Duration,
+	system_time: std::time::SystemTime,
+	phantom: std::marker::PhantomData<&'a ()>,
+	ip: std::net::IpAddr,
+}
+
+#[test]
+fn std_types() {
+	let schema = StdTypes::schema_mut();
+	let schema_json = clean_schema(&serde_json::to_string_pretty(&schema).unwrap());
+	let tuple_name = regex::Regex::new(r#""name": "(Tuple2_[0-9a-f]{16})""#)
+		.unwrap()
+		.captures(&schema_json)
+		.unwrap()[1]
+		.to_owned();
+	assert_eq!(
+		schema_json.replace(&tuple_name, "Tuple2_hash"),
+		r#"{
+  "type": "record",
+  "name": "derive_schema.StdTypes",
+  "fields": [
+    {
+      "name": "deque",
+      "type": {
+        "type": "array",
+        "items": "int"
+      }
+    },
+    {
+      "name": "hash_set",
+      "type": {
+        "type": "array",
+        "items": "int"
+      }
+    },
+    {
+      "name": "btree_set",
+      "type": {
+        "type": "array",
+        "items": "int"
+      }
+    },
+    {
+      "name": "btree_map",
+      "type": {
+        "type": "map",
+        "values": "int"
+      }
+    },
+    {
+      "name": "tuple",
+      "type": {
+        "type": "record",
+        "namespace": "",
+        "name": "Tuple2_hash",
+        "fields": [
+          {
+            "name": "_0",
+            "type": "int"
+          },
+          {
+            "name": "_1",
+            "type": "string"
+          }
+        ]
+      }
+    },
+    {
+      "name": "same_tuple",
+      "type": ".Tuple2_hash"
+    },
+    {
+      "name": "signed",
+      "type": {
+        "logicalType": "decimal",
+        "type": "fixed",
+        "scale": 0,
+        "precision": 39,
+        "name": "serde_avro_derive.i128",
+        "size": 17
+      }
+    },
+    {
+      "name": "unsigned",
+      "type": {
+        "logicalType": "decimal",
+        "type": "fixed",
+        "scale": 0,
+        "precision": 39,
+        "name": "serde_avro_derive.u128",
+        "size": 17
+      }
+    },
+    {
+      "name": "character",
+      "type": "string"
+    },
+    {
+      "name": "cow",
+      "type": "string"
+    },
+    {
+      "name": "boxed",
+      "type": "string"
+    },
+    {
+      "name": "non_zero",
+      "type": "long"
+    },
+    {
+      "name": "duration",
+      "type": {
+        "logicalType": "duration",
+        "type": "fixed",
+        "name": "serde_avro_derive.duration",
+        "size": 12
+      }
+    },
+    {
+      "name": "system_time",
+      "type": {
+        "logicalType": "timestamp-micros",
+        "type": "long"
+      }
+    },
+    {
+      "name": "phantom",
+      "type": "null"
+    },
+    {
+      "name": "ip",
+      "type": "string"
+    }
+  ]
+}"#
+	);
+
+	let schema = &schema.try_into().unwrap();
+	round_trip(
+		schema,
+		&StdTypes {
+			deque: [1, 2].into(),
+			hash_set: [3].into(),
+			btree_set: [4, 5].into(),
+			btree_map: [("a".to_owned(), 6)].into(),
+			tuple: (7, "b".to_owned()),
+			same_tuple: (8, "c".to_owned()),
+			signed: -(10_i128.pow(37)),
+			unsigned: 10_u128.pow(37),
+			character: 'é',
+			cow: "d".into(),
+			boxed: "e".into(),
+			non_zero: std::num::NonZeroU32::new(9).unwrap(),
+			duration: std::time::Duration::from_millis(86_400_010),
+			system_time: std::time::SystemTime::UNIX_EPOCH
+				+ std::time::Duration::from_micros(1_700_000_000_000_001),
+			phantom: std::marker::PhantomData,
+			ip: std::net::Ipv4Addr::LOCALHOST.into(),
+		},
+	);
+}
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct NetTypes {
+	ip: std::net::IpAddr,
+	socket: std::net::SocketAddr,
+	socket_v6: std::net::SocketAddrV6,
+}
+
+#[test]
+fn net_types_are_strings() {
+	// serde only serializes these as strings when the serializer is
+	// human-readable, which the `string` schema relies on
+	let schema = &NetTypes::schema().unwrap();
+	let serialized = round_trip(
+		schema,
+		&NetTypes {
+			ip: std::net::Ipv6Addr::LOCALHOST.into(),
+			socket: "127.0.0.1:8080".parse().unwrap(),
+			socket_v6: "[::1]:443".parse().unwrap(),
+		},
+	);
+	let expected = [
+		&[6][..],
+		b"::1",
+		&[28],
+		b"127.0.0.1:8080",
+		&[18],
+		b"[::1]:443",
+	]
+	.concat();
+	assert_eq!(serialized, expected);
+}
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct Integers128 {
+	signed: i128,
+	unsigned: u128,
+}
+
+#[test]
+fn integers_128_bounds() {
+	let schema = &Integers128::schema().unwrap();
+	for (signed, unsigned) in [
+		(i128::MIN, u128::MIN),
+		(i128::MAX, u128::MAX),
+		(-1, i128::MAX as u128 + 1),
+	] {
+		round_trip(schema, &Integers128 { signed, unsigned });
+	}
+	// `u128::MAX` needs the 17th byte for the sign
+	assert_eq!(
+		serde_avro_fast::to_datum_vec(
+			&Integers128 {
+				signed: i128::MIN,
+				unsigned: u128::MAX,
+			},
+			&mut serde_avro_fast::ser::SerializerConfig::new(schema),
+		)
+		.unwrap(),
+		[
+			[0xFF].as_slice(),
+			&i128::MIN.to_be_bytes(),
+			&[0],
+			&u128::MAX.to_be_bytes()
+		]
+		.concat()
+	);
+}
+
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct CustomSchemas {
+	#[serde(with = "entries")]
+	#[avro_schema(with = entries::schema)]
+	first: std::collections::BTreeMap<i32, String>,
+	#[serde(with = "entries")]
+	#[avro_schema(with = entries::schema)]
+	second: std::collections::BTreeMap<i32, String>,
+}
+
+/// Serialize maps with integer keys as arrays of entries
+mod entries {
+	use {
+		serde::{Deserialize, Deserializer, Serialize, Serializer},
+		serde_avro_derive::{
+			serde_avro_fast::schema::{Array, Name, Record, RecordField, SchemaKey},
+			SchemaBuilder,
+		},
+		std::collections::BTreeMap,
+	};
+
+	#[derive(Serialize, Deserialize)]
+	struct Entry<K, V> {
+		key: K,
+		value: V,
+	}
+
+	pub(super) fn serialize<S: Serializer>(
+		map: &BTreeMap<i32, String>,
+		serializer: S,
+	) -> Result<S::Ok, S::Error> {
+		serializer.collect_seq(map.iter().map(|(key, value)| Entry { key, value }))
+	}
+
+	pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<BTreeMap<i32, String>, D::Error> {
+		Ok(Vec::<Entry<i32, String>>::deserialize(deserializer)?
+			.into_iter()
+			.map(|entry| (entry.key, entry.value))
+			.collect())
+	}
+
+	pub(super) fn schema(builder: &mut SchemaBuilder) -> SchemaKey {
+		let entry = Record::new(
+			Name::from_fully_qualified_name("derive_schema.Entry"),
+			vec![
+				RecordField::new("key", builder.find_or_build::<i32>()),
+				RecordField::new("value", builder.find_or_build::<String>()),
+			],
+		);
+		let entry_key = SchemaKey::from_idx(builder.nodes.len());
+		builder.nodes.push(entry.into());
+		let array_key = SchemaKey::from_idx(builder.nodes.len());
+		builder.nodes.push(Array::new(entry_key).into());
+		array_key
+	}
+}
+
+#[test]
+fn custom_schema_function() {
+	test::<CustomSchemas>(
+		r#"{
+  "type": "record",
+  "name": "derive_schema.CustomSchemas",
+  "fields": [
+    {
+      "name": "first",
+      "type": {
+        "type": "array",
+        "items": {
+          "type": "record",
+          "name": "Entry",
+          "fields": [
+            {
+              "name": "key",
+              "type": "int"
+            },
+            {
+              "name": "value",
+              "type": "string"
+            }
+          ]
+        }
+      }
+    },
+    {
+      "name": "second",
+      "type": {
+        "type": "array",
+        "items": "Entry"
+      }
+    }
+  ]
+}"#,
+	);
+
+	let schema = &CustomSchemas::schema().unwrap();
+	assert_eq!(
+		round_trip(
+			schema,
+			&CustomSchemas {
+				first: [(1, "a".to_owned())].into(),
+				second: std::collections::BTreeMap::new(),
+			}
+		),
+		[2, 2, 2, b'a', 0, 0]
+	);
+}
+
+#[derive(BuildSchema)]
+#[allow(unused)]
+struct GenericWith<T: BuildSchema> {
+	// e.g. serialized as an array via `#[serde(with = ...)]`
+	#[avro_schema(with = array_of::<T>)]
+	values: std::collections::BTreeSet<T>,
+}
+
+fn array_of<T: BuildSchema>(
+	builder: &mut serde_avro_derive::SchemaBuilder,
+) -> serde_avro_fast::schema::SchemaKey {
+	let reserved_schema_key = builder.reserve();
+	let new_node = serde_avro_fast::schema::Array::new(builder.find_or_build::<T>()).into();
+	builder.nodes[reserved_schema_key] = new_node;
+	serde_avro_fast::schema::SchemaKey::from_idx(reserved_schema_key)
+}
+
+#[derive(BuildSchema)]
+#[allow(unused)]
+struct GenericWithInstantiations {
+	ints: GenericWith<i32>,
+	strings: GenericWith<String>,
+}
+
+#[test]
+fn generic_custom_schema_function() {
+	// The type arguments of the function are part of the `TypeLookup`, so the
+	// two instantiations are different records
+	let schema_json = serde_json::to_string(&GenericWithInstantiations::schema_mut()).unwrap();
+	let record_names: std::collections::HashSet<&str> =
+		regex::Regex::new(r#""name":"(GenericWith_\w{16})""#)
+			.unwrap()
+			.captures_iter(&schema_json)
+			.map(|captures| captures.get(1).unwrap().as_str())
+			.collect();
+	assert_eq!(record_names.len(), 2);
+	assert!(schema_json.contains(r#"{"type":"array","items":"int"}"#));
+	assert!(schema_json.contains(r#"{"type":"array","items":"string"}"#));
+}
+
+#[cfg(feature = "chrono")]
+#[derive(BuildSchema, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct ChronoTypes {
+	timestamp: chrono::DateTime<chrono::Utc>,
+	date: chrono::NaiveDate,
+	time: chrono::NaiveTime,
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_types() {
+	test::<ChronoTypes>(
+		r#"{
+  "type": "record",
+  "name": "derive_schema.ChronoTypes",
+  "fields": [
+    {
+      "name": "timestamp",
+      "type": {
+        "logicalType": "timestamp-micros",
+        "type": "long"
+      }
+    },
+    {
+      "name": "date",
+      "type": {
+        "logicalType": "date",
+        "type": "int"
+      }
+    },
+    {
+      "name": "time",
+      "type": {
+        "logicalType": "time-micros",
+        "type": "long"
+      }
+    }
+  ]
+}"#,
+	);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_now() {
+	use chrono::SubsecRound;
+
+	let schema = ChronoTypes::schema().unwrap();
+	let now = chrono::Utc::now();
+	let value = ChronoTypes {
+		timestamp: now,
+		date: now.date_naive(),
+		time: now.time(),
+	};
+
+	// The clock has nanosecond precision, which timestamp-micros and time-micros
+	// can only hold if truncation is allowed
+	let mut config = serde_avro_fast::ser::SerializerConfig::new(&schema);
+	config.allow_sub_unit_precision_truncation();
+	let serialized = serde_avro_fast::to_datum_vec(&value, &mut config).unwrap();
+	let deserialized: ChronoTypes =
+		serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	assert_eq!(
+		deserialized,
+		ChronoTypes {
+			timestamp: now.trunc_subsecs(6),
+			date: now.date_naive(),
+			time: now.time().trunc_subsecs(6),
+		}
+	);
+}
@@ -14,11 +14,18 @@ pub(super) struct FieldTypeAndInstantiationsBuilder<'t, 'm> {
 	pub(super) has_direct_lookup: bool,
 }
 
+#[derive(Clone, Copy)]
 pub(super) enum FieldKind<'a> {
 	StructField {
 		struct_name: &'a syn::Ident,
 		field_name: &'a syn::Ident,
 	},
+	/// Field of the record that a struct or tuple variant is represented as
+	VariantField {
+		enum_name: &'a syn::Ident,
+		variant_name: &'a syn::Ident,
+		field_name: &'a syn::Ident,
+	},
 	NewtypeStruct {
 		struct_name: &'a syn::Ident,
 	},
@@ -34,6 +41,102 @@ impl<'t> FieldTypeAndInstantiationsBuilder<'t, '_> {
 		field: &'t SchemaDeriveField,
 		field_kind: FieldKind<'_>,
 	) -> (Cow<'t, syn::Type>, TokenStream) {
+		if let Some(name) = &field.name {
+			let error = match field_kind {
+				FieldKind::StructField { .. } | FieldKind::VariantField { .. } => {
+					field.ident.is_some().then_some(
+						"`name` attribute is only supported on fields of tuple structs and \
+							tuple variants: for named fields, the record field is named after \
+							the Rust field",
+					)
+				}
+				FieldKind::NewtypeStruct { .. } | FieldKind::NewtypeVariant { .. } => Some(
+					"`name` attribute is not supported on the field of a newtype, as it is not \
+						represented as a record",
+				),
+			};
+			if let Some(error) = error {
+				self.errors
+					.extend(Error::new_spanned(name, error).to_compile_error());
+			}
+		}
+
+		if let (Some(default), FieldKind::NewtypeStruct { .. } | FieldKind::NewtypeVariant { .. }) =
+			(&field.default, &field_kind)
+		{
+			self.errors.extend(
+				Error::new_spanned(
+					default,
+					"`default` attribute is not supported on the field of a newtype, as it is \
+						not represented as a record",
+				)
+				.to_compile_error(),
+			);
+		}
+
+		if let Some(with) = &field.with {
+			let error = if let FieldKind::NewtypeStruct { .. } = field_kind {
+				Some(
+					"`with` attribute is not supported on the field of a newtype struct, as \
+						the newtype is not represented as a node of its own: \
+						`BuildSchema` should be implemented manually for the newtype instead",
+				)
+			} else if field.has_same_type_as.is_some() || field.logical_type.is_some() {
+				Some(
+					"`with` attribute can't be combined with `has_same_type_as` or \
+						`logical_type`, as the function builds the whole schema of the field",
+				)
+			} else {
+				None
+			};
+			if let Some(error) = error {
+				self.errors
+					.extend(Error::new_spanned(with, error).to_compile_error());
+			}
+			// The schema doesn't depend on the type of the field, so it doesn't need
+			// to implement `BuildSchema`, but it may depend on the type arguments of
+			// the function (e.g. `with = schema::<T>`), which hence have to be part
+			// of the `TypeLookup`
+			let mut type_arguments = Vec::new();
+			for argument in with
+				.segments
+				.iter()
+				.flat_map(|segment| match &segment.arguments {
+					syn::PathArguments::AngleBracketed(arguments) => {
+						arguments.args.iter().collect()
+					}
+					_ => Vec::new(),
+				}) {
+				match argument {
+					syn::GenericArgument::Type(ty) => {
+						// `find_or_build_with` requires the function to be `'static`
+						if self.is_relevant_generic(ty) {
+							self.generics
+								.make_where_clause()
+								.predicates
+								.push(parse_quote!(#ty: 'static));
+						}
+						type_arguments.push(ty);
+					}
+					syn::GenericArgument::Lifetime(_) => {}
+					_ => self.errors.extend(
+						Error::new_spanned(
+							argument,
+							"Only type and lifetime arguments are supported in the path of the \
+								`with` function",
+						)
+						.to_compile_error(),
+					),
+				}
+			}
+			return (
+				Cow::Owned(parse_quote!(
+					serde_avro_derive::WithSchemaFn<(#(#type_arguments,)*)>
+				)),
+				quote! { builder.find_or_build_with(#with) },
+			);
+		}
+
 		// Choose type
 		let mut ty = field.has_same_type_as.as_ref().unwrap_or(&field.ty);
 		loop {
@@ -94,63 +197,12 @@ impl<'t> FieldTypeAndInstantiationsBuilder<'t, '_> {
 			}
 		}
 
-		let mut new_name_for_owned_subnode = || match self.namespace {
-			None => 'new_name: {
-				let pattern = match field_kind {
-					FieldKind::NewtypeStruct { struct_name } => {
-						format!(r#"{{}}.{struct_name}"#)
-					}
-					FieldKind::StructField {
-						struct_name: _,
-						field_name,
-					} => {
-						let pattern = format!(r#"{{}}.{}"#, field_name.unraw());
-						break 'new_name quote! { format!(#pattern, type_name) };
-					}
-					FieldKind::NewtypeVariant {
-						enum_name,
-						variant_name,
-					} => format!(r#"{{}}.{}.{}"#, enum_name.unraw(), variant_name.unraw()),
-				};
-				self.expand_namespace_var = true;
-				quote! {
-					format!(#pattern, namespace.get())
-				}
-			}
-			Some(namespace) => {
-				let namespace_prefix = if namespace.is_empty() {
-					"".to_owned()
-				} else {
-					format!("{}.", namespace)
-				};
-				let type_name = match field_kind {
-					FieldKind::NewtypeStruct { struct_name } => {
-						format!("{}{}", namespace_prefix, struct_name.unraw())
-					}
-					FieldKind::StructField {
-						struct_name: type_name,
-						field_name: field_or_variant_name,
-					}
-					| FieldKind::NewtypeVariant {
-						enum_name: type_name,
-						variant_name: field_or_variant_name,
-					} => format!(
-						"{}{}.{}",
-						namespace_prefix,
-						type_name.unraw(),
-						field_or_variant_name.unraw(),
-					),
-				};
-				quote! { #type_name.to_owned() }
-			}
-		};
-
 		// If it's a logical type, wrap it
 		let field_instantiation = match logical_type_litstr.as_deref() {
 			None => {
 				let mut override_field_instantiation = None;
 				let override_fixed_name = match field_kind {
-					FieldKind::StructField { .. } => false,
+					FieldKind::StructField { .. } | FieldKind::VariantField { .. } => false,
 					FieldKind::NewtypeStruct { .. } | FieldKind::NewtypeVariant { .. } => true,
 				};
 				if override_fixed_name {
@@ -190,7 +242,7 @@ impl<'t> FieldTypeAndInstantiationsBuilder<'t, '_> {
 										0
 									}
 								};
-								let fixed_name = new_name_for_owned_subnode();
+								let fixed_name = self.new_name_for_owned_subnode(field_kind);
 								override_field_instantiation = Some(quote! {
 									{
 										let schema_key = schema::SchemaKey::from_idx(builder.nodes.len());
@@ -274,36 +326,24 @@ impl<'t> FieldTypeAndInstantiationsBuilder<'t, '_> {
 						(schema::Decimal::new(#scale, #precision))
 					});
 				} else {
+					let is_path = |ty: &syn::Type, ident: &str| matches!(ty, syn::Type::Path(p) if p.path.is_ident(ident));
 					match logical_type_str_pascal.as_str() {
-						"TimestampMillis" | "TimestampMicros" | "TimeMicros" => {
-							if !matches!(&*ty, syn::Type::Path(p) if p.path.is_ident("i64")) {
-								ty = Cow::Owned(
-									parse_quote_spanned!(logical_type_litstr.span() => i64),
-								);
-							}
+						"TimestampMillis" | "TimestampMicros" | "TimeMicros"
+							if !is_path(&ty, "i64") =>
+						{
+							ty =
+								Cow::Owned(parse_quote_spanned!(logical_type_litstr.span() => i64));
 						}
-						"TimeMillis" => {
-							if !matches!(&*ty, syn::Type::Path(p) if p.path.is_ident("i32")) {
-								ty = Cow::Owned(
-									parse_quote_spanned!(logical_type_litstr.span() => i32),
-								);
-							}
+						"TimeMillis" | "Date" if !is_path(&ty, "i32") => {
+							ty =
+								Cow::Owned(parse_quote_spanned!(logical_type_litstr.span() => i32));
 						}
-						"Uuid" => {
-							if !matches!(&*ty, syn::Type::Path(p) if p.path.is_ident("String")) {
-								// It is specified that
-								// "A uuid logical type annotates an Avro string"
-								ty = Cow::Owned(
-									parse_quote_spanned!(logical_type_litstr.span() => String),
-								);
-							}
-						}
-						"Date" => {
-							if !matches!(&*ty, syn::Type::Path(p) if p.path.is_ident("i32")) {
-								ty = Cow::Owned(
-									parse_quote_spanned!(logical_type_litstr.span() => i32),
-								);
-							}
+						"Uuid" if !is_path(&ty, "String") => {
+							// It is specified that
+							// "A uuid logical type annotates an Avro string"
+							ty = Cow::Owned(
+								parse_quote_spanned!(logical_type_litstr.span() => String),
+							);
 						}
 						_ => {}
 					}
@@ -329,7 +369,7 @@ impl<'t> FieldTypeAndInstantiationsBuilder<'t, '_> {
 						error(f);
 					}
 				}
-				let name_override = new_name_for_owned_subnode();
+				let name_override = self.new_name_for_owned_subnode(field_kind);
 				quote! {
 					builder.build_logical_type(
 						#logical_type,
@@ -341,26 +381,105 @@ impl<'t> FieldTypeAndInstantiationsBuilder<'t, '_> {
 		};
 
 		// Add relevant where clause if not already present
-		if !self.generics.params.is_empty() {
-			let mut is_relevant_generic = IsRelevantGeneric {
-				generics: &*self.generics,
-				result: false,
-			};
-			is_relevant_generic.visit_type(&*ty);
-			if is_relevant_generic.result
-				&& self
-					.added_where_clause_predicate_for_types
-					.insert(ty.clone())
-			{
-				self.generics
-					.make_where_clause()
-					.predicates
-					.push(parse_quote!(#ty: serde_avro_derive::BuildSchema));
-			}
+		if self.is_relevant_generic(&ty)
+			&& self
+				.added_where_clause_predicate_for_types
+				.insert(ty.clone())
+		{
+			self.generics
+				.make_where_clause()
+				.predicates
+				.push(parse_quote!(#ty: serde_avro_derive::BuildSchema));
 		}
 
 		(ty, field_instantiation)
 	}
+
+	/// Whether the type depends on the generics of the type we derive on
+	fn is_relevant_generic(&self, ty: &syn::Type) -> bool {
+		if self.generics.params.is_empty() {
+			return false;
+		}
+		let mut is_relevant_generic = IsRelevantGeneric {
+			generics: &*self.generics,
+			result: false,
+		};
+		is_relevant_generic.visit_type(ty);
+		is_relevant_generic.result
+	}
+
+	/// Expression that evaluates to the fully qualified name of a named node
+	/// that is owned by this field (e.g. `Fixed` for `[u8; N]`, or logical
+	/// type), or by this variant for the record a struct variant is
+	/// represented as
+	pub(super) fn new_name_for_owned_subnode(&mut self, field_kind: FieldKind<'_>) -> TokenStream {
+		match self.namespace {
+			None => 'new_name: {
+				let pattern = match field_kind {
+					FieldKind::NewtypeStruct { struct_name } => {
+						format!(r#"{{}}.{struct_name}"#)
+					}
+					FieldKind::StructField {
+						struct_name: _,
+						field_name,
+					}
+					| FieldKind::VariantField {
+						enum_name: _,
+						variant_name: _,
+						field_name,
+					} => {
+						let pattern = format!(r#"{{}}.{}"#, field_name.unraw());
+						break 'new_name quote! { format!(#pattern, type_name) };
+					}
+					FieldKind::NewtypeVariant {
+						enum_name,
+						variant_name,
+					} => format!(r#"{{}}.{}.{}"#, enum_name.unraw(), variant_name.unraw()),
+				};
+				self.expand_namespace_var = true;
+				quote! {
+					format!(#pattern, namespace.get())
+				}
+			}
+			Some(namespace) => {
+				let namespace_prefix = if namespace.is_empty() {
+					"".to_owned()
+				} else {
+					format!("{}.", namespace)
+				};
+				let type_name = match field_kind {
+					FieldKind::NewtypeStruct { struct_name } => {
+						format!("{}{}", namespace_prefix, struct_name.unraw())
+					}
+					FieldKind::StructField {
+						struct_name: type_name,
+						field_name: field_or_variant_name,
+					}
+					| FieldKind::NewtypeVariant {
+						enum_name: type_name,
+						variant_name: field_or_variant_name,
+					} => format!(
+						"{}{}.{}",
+						namespace_prefix,
+						type_name.unraw(),
+						field_or_variant_name.unraw(),
+					),
+					FieldKind::VariantField {
+						enum_name,
+						variant_name,
+						field_name,
+					} => format!(
+						"{}{}.{}.{}",
+						namespace_prefix,
+						enum_name.unraw(),
+						variant_name.unraw(),
+						field_name.unraw(),
+					),
+				};
+				quote! { #type_name.to_owned() }
+			}
+		}
+	}
 }
 
 struct IsRelevantGeneric<'a> {
@@ -1,7 +1,8 @@
 mod field_types_and_instantiations;
+mod serde_attributes;
 mod type_lookup;
 
-use field_types_and_instantiations::*;
+use {field_types_and_instantiations::*, serde_attributes::*};
 
 use {
 	darling::ast::Style,
@@ -21,61 +22,93 @@ use {
 #[derive(darling::FromDeriveInput)]
 #[darling(
 	attributes(avro_schema),
-	supports(struct_named, struct_newtype, enum_unit, enum_newtype)
+	forward_attrs(serde, doc),
+	supports(
+		struct_named,
+		struct_newtype,
+		struct_tuple,
+		enum_unit,
+		enum_newtype,
+		enum_tuple,
+		enum_named
+	)
 )]
 pub(crate) struct SchemaDeriveInput {
 	ident: proc_macro2::Ident,
 	data: darling::ast::Data<SchemaDeriveVariant, SchemaDeriveField>,
 	generics: syn::Generics,
+	#[darling(with = ForwardedAttributes::from_forwarded_attrs)]
+	attrs: ForwardedAttributes,
 
 	namespace: Option<String>,
 	name: Option<proc_macro2::Ident>,
 }
 
 #[derive(darling::FromField, Debug)]
-#[darling(attributes(avro_schema))]
+#[darling(attributes(avro_schema), forward_attrs(serde, doc))]
 pub(crate) struct SchemaDeriveField {
 	ident: Option<proc_macro2::Ident>,
 	ty: syn::Type,
+	#[darling(with = ForwardedAttributes::from_forwarded_attrs)]
+	attrs: ForwardedAttributes,
 
 	skip: darling::util::Flag,
+	/// Name of the record field, for fields that are not named in Rust
+	name: Option<proc_macro2::Ident>,
 
 	logical_type: Option<syn::LitStr>,
 	scale: Option<WithMetaPath<syn::LitInt>>,
 	precision: Option<WithMetaPath<syn::LitInt>>,
 
 	has_same_type_as: Option<syn::Type>,
+	/// Function that builds the schema of the field, for fields that are
+	/// serialized in a way that no type describes (e.g. `#[serde(with = ...)]`)
+	with: Option<syn::Path>,
+
+	/// JSON of the default value of the record field
+	default: Option<syn::LitStr>,
 }
 
 #[derive(darling::FromVariant, Debug)]
-#[darling(attributes(avro_schema))]
+#[darling(attributes(avro_schema), forward_attrs(serde, doc))]
 pub(crate) struct SchemaDeriveVariant {
 	ident: proc_macro2::Ident,
 	fields: darling::ast::Fields<SchemaDeriveField>,
+	#[darling(with = ForwardedAttributes::from_forwarded_attrs)]
+	attrs: ForwardedAttributes,
 
 	skip: darling::util::Flag,
 }
 
 pub(crate) fn schema_impl(input: SchemaDeriveInput) -> Result<TokenStream, Error> {
+	let mut errors = TokenStream::default();
+	if let Some(span) = input.attrs.serde.unsupported_enum_representation {
+		errors.extend(unsupported_enum_representation(span).to_compile_error());
+	}
+
 	let struct_or_enum = {
 		let mut d = input.data;
-		match &mut d {
-			darling::ast::Data::Struct(fields) => {
-				fields.fields.retain(|f| !f.skip.is_present());
-			}
-			darling::ast::Data::Enum(enum_variants) => {
-				enum_variants.retain(|f| !f.skip.is_present());
-			}
+		// Skipped fields are filtered out later, as they still count in the positions
+		// of the fields of tuple structs
+		if let darling::ast::Data::Enum(enum_variants) = &mut d {
+			enum_variants.retain(|v| !v.skip.is_present() && !v.attrs.serde.skip_serializing);
 		}
 		d
 	};
 
 	let type_ident = &input.ident;
-	let name_ident = input.name.as_ref().unwrap_or(type_ident);
+	let name_ident: Cow<'_, syn::Ident> = match &input.name {
+		Some(name) => Cow::Borrowed(name),
+		None => input
+			.attrs
+			.serde
+			.serialized_ident(type_ident, |_| None, &mut errors),
+	};
+	let name_ident = &*name_ident;
 	let compute_namespace_expr = quote! { module_path!().replace("::", ".") };
 	let type_name_var = match &input.namespace {
 		None => {
-			let type_name_str = format!(".{}", name_ident);
+			let type_name_str = format!(".{}", name_ident.unraw());
 			quote! {
 				let mut type_name = #compute_namespace_expr;
 				type_name.push_str(#type_name_str);
@@ -83,9 +116,9 @@ pub(crate) fn schema_impl(input: SchemaDeriveInput) -> Result<TokenStream, Error
 		}
 		Some(namespace) => {
 			let type_name = if namespace.is_empty() {
-				name_ident.to_string()
+				name_ident.unraw().to_string()
 			} else {
-				format!("{}.{}", namespace, name_ident)
+				format!("{}.{}", namespace, name_ident.unraw())
 			};
 			quote! {
 				let mut type_name = #type_name.to_owned();
@@ -97,8 +130,17 @@ pub(crate) fn schema_impl(input: SchemaDeriveInput) -> Result<TokenStream, Error
 			let mut namespace = serde_avro_derive::LazyNamespace::new(|| #compute_namespace_expr);
 		}
 	};
+	let add_type_id_to_fqn = |has_non_lifetime_generics: bool| {
+		has_non_lifetime_generics.then(|| {
+			quote! {
+				serde_avro_derive::hash_type_id(
+					&mut type_name,
+					std::any::TypeId::of::<<Self as serde_avro_derive::BuildSchema>::TypeLookup>(),
+				);
+			}
+		})
+	};
 
-	let mut errors = TokenStream::default();
 	let mut generics = input.generics;
 
 	let mut field_types_and_instantiations = FieldTypeAndInstantiationsBuilder {
@@ -115,10 +157,12 @@ pub(crate) fn schema_impl(input: SchemaDeriveInput) -> Result<TokenStream, Error
 	let append_schema_body: TokenStream = match struct_or_enum {
 		darling::ast::Data::Struct(fields) => {
 			// We support two scenarios: newtype structs (forward to inner type) and named
-			// structs (avro record)
-			if fields.len() == 1 && fields.fields[0].ident.is_none() {
+			// or tuple structs (avro record)
+			let non_skipped_fields: Vec<&SchemaDeriveField> =
+				fields.iter().filter(|field| !field.is_skipped()).collect();
+			if non_skipped_fields.len() == 1 && non_skipped_fields[0].ident.is_none() {
 				// newtype struct
-				let field = &fields.fields[0];
+				let field = non_skipped_fields[0];
 				let (field_type, field_instantiation) = field_types_and_instantiations
 					.field_type_and_instantiation(
 						field,
@@ -153,95 +197,215 @@ pub(crate) fn schema_impl(input: SchemaDeriveInput) -> Result<TokenStream, Error
 					}
 				}
 			} else {
-				// named struct
-				let field_idents = fields
-					.iter()
-					.map(|f| f.ident.as_ref())
-					.collect::<Option<Vec<_>>>()
-					.ok_or_else(|| {
-						Error::new(Span::call_site(), "Unnamed fields are not supported")
-					})?;
-
-				let (field_types, field_instantiations): (
-					Vec<Cow<'_, syn::Type>>,
-					Vec<TokenStream>,
-				) = fields
-					.iter()
-					.zip(&field_idents)
-					.map(|(field, field_name)| {
-						field_types_and_instantiations.field_type_and_instantiation(
-							field,
-							FieldKind::StructField {
-								struct_name: name_ident,
-								field_name,
-							},
-						)
-					})
-					.unzip();
+				// named or tuple struct (avro record)
+				let push_tag_field = input
+					.attrs
+					.serde
+					.tag
+					.as_ref()
+					.map(|tag| push_tag_field(tag, field_types_and_instantiations.errors));
 
-				let field_names = field_idents.iter().map(|ident| ident.unraw().to_string());
+				let mut field_types = Vec::new();
+				let mut type_lookup_field_names = Vec::new();
+				let push_fields = record_fields(
+					&mut field_types_and_instantiations,
+					fields.iter(),
+					input.attrs.serde.rename_all,
+					RecordOwner::Struct {
+						struct_name: name_ident,
+					},
+					&mut field_types,
+					Some(&mut type_lookup_field_names),
+				);
 
+				let type_lookup_field_names: Vec<&syn::Ident> = type_lookup_field_names
+					.iter()
+					.map(|ident| &**ident)
+					.collect();
 				let has_non_lifetime_generics;
 				(type_lookup, type_lookup_decl, has_non_lifetime_generics) =
 					type_lookup::build_type_lookup(
 						type_ident,
 						&generics,
-						Some(field_idents.as_slice()),
+						Some(type_lookup_field_names.as_slice()),
 						&field_types,
 					);
 
-				let add_type_id_to_fqn = has_non_lifetime_generics.then(|| {
-					quote! {
-						serde_avro_derive::hash_type_id(
-							&mut type_name,
-							std::any::TypeId::of::<<Self as serde_avro_derive::BuildSchema>::TypeLookup>(),
-						);
-					}
-				});
+				let add_type_id_to_fqn = add_type_id_to_fqn(has_non_lifetime_generics);
+				let set_doc = input.attrs.set_doc("new_node");
 
 				quote! {
 					let reserved_schema_key = builder.reserve();
 					#type_name_var
 					#add_type_id_to_fqn
-					let fields = vec![#(
-						schema::RecordField::new(
-							#field_names,
-							#field_instantiations,
-						),
-					)*];
-					let new_node = schema::Record::new(
+					let mut fields = Vec::new();
+					#push_tag_field
+					#push_fields
+					let mut new_node = schema::Record::new(
 						schema::Name::from_fully_qualified_name(type_name),
 						fields,
 					);
+					#set_doc
 					builder.nodes[reserved_schema_key] = new_node.into();
 				}
 			}
 		}
 		darling::ast::Data::Enum(variants) => {
-			if variants.iter().all(|v| v.fields.is_empty()) {
+			let variant_idents: Vec<Cow<'_, syn::Ident>> = variants
+				.iter()
+				.map(|v| {
+					if let Some(span) = v.attrs.serde.unsupported_enum_representation {
+						field_types_and_instantiations
+							.errors
+							.extend(unsupported_enum_representation(span).to_compile_error());
+					}
+					v.attrs.serde.serialized_ident(
+						&v.ident,
+						|name| {
+							input
+								.attrs
+								.serde
+								.rename_all
+								.map(|rule| rule.apply_to_variant(name))
+						},
+						field_types_and_instantiations.errors,
+					)
+				})
+				.collect();
+			let variant_fields_rename_all = |v: &SchemaDeriveVariant| {
+				v.attrs
+					.serde
+					.rename_all
+					.or(input.attrs.serde.rename_all_fields)
+			};
+
+			if let Some(tag) = &input.attrs.serde.tag {
+				// Internally tagged enum: every variant is represented as a record whose
+				// first field is the tag, so that the serializer can pick the union
+				// variant from it.
+				let push_tag_field = push_tag_field(tag, field_types_and_instantiations.errors);
+				let mut field_types = Vec::new();
+				let has_non_lifetime_generics =
+					type_lookup::has_non_lifetime_generics(field_types_and_instantiations.generics);
+
+				let variant_records: Vec<TokenStream> = variants
+					.iter()
+					.zip(&variant_idents)
+					.map(|(v, variant_ident)| {
+						let mut style = v.fields.style;
+						if v.fields.is_empty() {
+							style = Style::Unit;
+						}
+						let push_fields = match style {
+							Style::Unit => TokenStream::new(),
+							Style::Tuple if v.fields.len() == 1 => {
+								// The fields of the inner record are serialized right
+								// after the tag
+								let (field_type, field_instantiation) =
+									field_types_and_instantiations.field_type_and_instantiation(
+										&v.fields.fields[0],
+										FieldKind::NewtypeVariant {
+											enum_name: name_ident,
+											variant_name: variant_ident,
+										},
+									);
+								field_types.push(field_type);
+								quote! {
+									let flattened = #field_instantiation;
+									fields.extend(serde_avro_derive::flattened_fields(builder, flattened));
+								}
+							}
+							Style::Tuple => {
+								field_types_and_instantiations.errors.extend(
+									Error::new_spanned(
+										&v.ident,
+										"Tuple variants are not supported in internally \
+											tagged enums",
+									)
+									.to_compile_error(),
+								);
+								TokenStream::new()
+							}
+							Style::Struct => record_fields(
+								&mut field_types_and_instantiations,
+								v.fields.iter(),
+								variant_fields_rename_all(v),
+								RecordOwner::Variant {
+									enum_name: name_ident,
+									variant_name: variant_ident,
+								},
+								&mut field_types,
+								None,
+							),
+						};
+						let record_name_pattern = format!("{{}}.{}", variant_ident.unraw());
+						let set_doc = v.attrs.set_doc("new_node");
+						quote! {
+							{
+								let reserved_schema_key = builder.reserve();
+								let type_name = format!(#record_name_pattern, enum_name);
+								let mut fields = Vec::new();
+								#push_tag_field
+								#push_fields
+								let mut new_node = schema::Record::new(
+									schema::Name::from_fully_qualified_name(type_name),
+									fields,
+								);
+								#set_doc
+								builder.nodes[reserved_schema_key] = new_node.into();
+								schema::SchemaKey::from_idx(reserved_schema_key)
+							}
+						}
+					})
+					.collect();
+
+				let namespace_var = field_types_and_instantiations
+					.expand_namespace_var
+					.then(lazy_compute_namespace);
+
+				// The type lookup has unnamed fields, because the fields of different
+				// variants may have the same name
+				(type_lookup, type_lookup_decl, _) =
+					type_lookup::build_type_lookup(type_ident, &generics, None, &field_types);
+				let add_type_id_to_fqn = add_type_id_to_fqn(has_non_lifetime_generics);
+
+				quote! {
+					let reserved_schema_key = builder.reserve();
+					#namespace_var
+					#type_name_var
+					#add_type_id_to_fqn
+					let enum_name = type_name;
+					let new_node = schema::Union::new(vec![
+						#(#variant_records,)*
+					]);
+					builder.nodes[reserved_schema_key] = new_node.into();
+				}
+			} else if variants.iter().all(|v| v.fields.is_empty()) {
 				// Only unit variants
 				type_lookup = parse_quote!(Self);
 				type_lookup_decl = None;
-				let variants = variants.iter().map(|v| v.ident.to_string());
+				let variants = variant_idents.iter().map(|ident| ident.unraw().to_string());
+				let set_doc = input.attrs.set_doc("new_node");
 				quote! {
 					#type_name_var
-					builder.nodes.push(
-						schema::Enum::new(
-							schema::Name::from_fully_qualified_name(type_name),
-							vec![#(#variants.to_owned(),)*],
-						)
-						.into()
+					let mut new_node = schema::Enum::new(
+						schema::Name::from_fully_qualified_name(type_name),
+						vec![#(#variants.to_owned(),)*],
 					);
+					#set_doc
+					builder.nodes.push(new_node.into());
 				}
 			} else {
 				let mut field_types = Vec::new();
-				let mut type_lookup_field_names = Vec::new();
+				let has_non_lifetime_generics =
+					type_lookup::has_non_lifetime_generics(field_types_and_instantiations.generics);
 
 				let mut has_unit = 0;
 
 				let variant_instantiations: Vec<TokenStream> = variants
 					.iter()
-					.map(|v| {
+					.zip(&variant_idents)
+					.map(|(v, variant_ident)| {
 						let mut style = v.fields.style;
 						if v.fields.is_empty() {
 							style = Style::Unit;
@@ -269,21 +433,8 @@ pub(crate) fn schema_impl(input: SchemaDeriveInput) -> Result<TokenStream, Error
 								}
 								quote! { builder.find_or_build::<()>() }
 							}
-							Style::Tuple => {
-								let mut fields = v.fields.iter();
-								let field = fields
-									.next()
-									.expect("Style should be Unit if there are no fields");
-								if fields.next().is_some() {
-									field_types_and_instantiations.errors.extend(
-										Error::new_spanned(
-											&v.ident,
-											"Tuple variants may only contain one \
-												field for Avro Union schema generation",
-										)
-										.to_compile_error(),
-									);
-								}
+							Style::Tuple if v.fields.len() == 1 => {
+								let field = &v.fields.fields[0];
 								if field.skip.is_present() {
 									field_types_and_instantiations.errors.extend(
 										Error::new(
@@ -298,19 +449,52 @@ pub(crate) fn schema_impl(input: SchemaDeriveInput) -> Result<TokenStream, Error
 									field_types_and_instantiations.field_type_and_instantiation(
 										field,
 										FieldKind::NewtypeVariant {
-											enum_name: type_ident,
-											variant_name: &v.ident,
+											enum_name: name_ident,
+											variant_name: variant_ident,
 										},
 									);
 								field_types.push(field_type);
-								type_lookup_field_names.push(&v.ident);
 								field_instantiation
 							}
-							Style::Struct => {
-								panic!(
-									"Struct variant style at this point should have been prevented \
-										by the lack of 'enum_struct' in 'supports'"
-								)
+							Style::Tuple | Style::Struct => {
+								// Struct and tuple variants are represented as records, whose
+								// name is that of the variant so that the serializer can
+								// look them up in the union.
+								let push_fields = record_fields(
+									&mut field_types_and_instantiations,
+									v.fields.iter(),
+									variant_fields_rename_all(v),
+									RecordOwner::Variant {
+										enum_name: name_ident,
+										variant_name: variant_ident,
+									},
+									&mut field_types,
+									None,
+								);
+								let record_name = field_types_and_instantiations
+									.new_name_for_owned_subnode(FieldKind::NewtypeVariant {
+										enum_name: name_ident,
+										variant_name: variant_ident,
+									});
+								let add_type_id_to_fqn =
+									add_type_id_to_fqn(has_non_lifetime_generics);
+								let set_doc = v.attrs.set_doc("new_node");
+								quote! {
+									{
+										let reserved_schema_key = builder.reserve();
+										let mut type_name = #record_name;
+										#add_type_id_to_fqn
+										let mut fields = Vec::new();
+										#push_fields
+										let mut new_node = schema::Record::new(
+											schema::Name::from_fully_qualified_name(type_name),
+											fields,
+										);
+										#set_doc
+										builder.nodes[reserved_schema_key] = new_node.into();
+										schema::SchemaKey::from_idx(reserved_schema_key)
+									}
+								}
 							}
 						}
 					})
@@ -320,12 +504,10 @@ pub(crate) fn schema_impl(input: SchemaDeriveInput) -> Result<TokenStream, Error
 					.expand_namespace_var
 					.then(lazy_compute_namespace);
 
-				(type_lookup, type_lookup_decl, _) = type_lookup::build_type_lookup(
-					type_ident,
-					&generics,
-					Some(&type_lookup_field_names),
-					&field_types,
-				);
+				// The type lookup has unnamed fields, because the fields of different
+				// variants may have the same name
+				(type_lookup, type_lookup_decl, _) =
+					type_lookup::build_type_lookup(type_ident, &generics, None, &field_types);
 
 				quote! {
 					let reserved_schema_key = builder.reserve();
@@ -360,6 +542,260 @@ pub(crate) fn schema_impl(input: SchemaDeriveInput) -> Result<TokenStream, Error
 	})
 }
 
+impl SchemaDeriveField {
+	/// Whether the field is not part of the schema, because it is never
+	/// serialized
+	fn is_skipped(&self) -> bool {
+		self.skip.is_present() || self.attrs.serde.skip_serializing
+	}
+}
+
+/// The struct or variant that a record is generated for
+#[derive(Clone, Copy)]
+enum RecordOwner<'a> {
+	Struct {
+		struct_name: &'a syn::Ident,
+	},
+	Variant {
+		enum_name: &'a syn::Ident,
+		variant_name: &'a syn::Ident,
+	},
+}
+
+impl<'a> RecordOwner<'a> {
+	fn field_kind(self, field_name: &'a syn::Ident) -> FieldKind<'a> {
+		match self {
+			Self::Struct { struct_name } => FieldKind::StructField {
+				struct_name,
+				field_name,
+			},
+			Self::Variant {
+				enum_name,
+				variant_name,
+			} => FieldKind::VariantField {
+				enum_name,
+				variant_name,
+				field_name,
+			},
+		}
+	}
+}
+
+/// Statements that push the record fields for the given Rust fields into a
+/// `fields` variable
+///
+/// Skipped fields are ignored, but still count in the positions of unnamed
+/// fields, as they do for serde.
+///
+/// Fields of flattened types are spliced in place of the flattened field.
+///
+/// The names of the fields of the type lookup are pushed to
+/// `type_lookup_field_names`, unless it has unnamed fields.
+fn record_fields<'t>(
+	field_types_and_instantiations: &mut FieldTypeAndInstantiationsBuilder<'t, '_>,
+	fields: impl Iterator<Item = &'t SchemaDeriveField>,
+	rename_all: Option<RenameRule>,
+	record_owner: RecordOwner<'_>,
+	field_types: &mut Vec<Cow<'t, syn::Type>>,
+	mut type_lookup_field_names: Option<&mut Vec<Cow<'t, syn::Ident>>>,
+) -> TokenStream {
+	let mut push_fields = TokenStream::new();
+	for (position, field) in fields.enumerate().filter(|(_, field)| !field.is_skipped()) {
+		let rust_field_ident = record_field_ident(field, position);
+		let push_field = if let Some(flatten_span) = field.attrs.serde.flatten {
+			if field.with.is_none() && is_map(&field.ty) {
+				field_types_and_instantiations.errors.extend(
+					Error::new(
+						flatten_span,
+						"Flattened maps are not supported by `BuildSchema`: \
+							the fields of the record would depend on the keys of the map",
+					)
+					.to_compile_error(),
+				);
+			}
+			let (field_type, field_instantiation) = field_types_and_instantiations
+				.field_type_and_instantiation(field, record_owner.field_kind(&rust_field_ident));
+			field_types.push(field_type);
+			if let Some(default) = &field.default {
+				field_types_and_instantiations.errors.extend(
+					Error::new_spanned(
+						default,
+						"`default` attribute is not supported on flattened fields: \
+							it should be set on the fields of the flattened type instead",
+					)
+					.to_compile_error(),
+				);
+			}
+			quote! {
+				let flattened = #field_instantiation;
+				fields.extend(serde_avro_derive::flattened_fields(builder, flattened));
+			}
+		} else {
+			let field_name = match &field.ident {
+				Some(ident) => field.attrs.serde.serialized_ident(
+					ident,
+					|name| rename_all.map(|rule| rule.apply_to_field(name)),
+					field_types_and_instantiations.errors,
+				),
+				None => rust_field_ident.clone(),
+			};
+			let (field_type, mut field_instantiation) = field_types_and_instantiations
+				.field_type_and_instantiation(field, record_owner.field_kind(&field_name));
+			field_types.push(field_type);
+			// If the schema is built by a function, it may not be a union even if the
+			// field is an `Option`
+			let is_option = field.with.is_none()
+				&& is_option(field.has_same_type_as.as_ref().unwrap_or(&field.ty));
+			let is_nullable = is_option || field.attrs.serde.skip_serializing_if.is_some();
+			if is_nullable && !is_option {
+				// The field is not always serialized, in which case the serializer
+				// will write `null`, so it has to be nullable
+				field_instantiation = quote! {
+					{
+						let schema_key = #field_instantiation;
+						serde_avro_derive::nullable(builder, schema_key)
+					}
+				};
+			}
+			let field_name = field_name.unraw().to_string();
+			let set_doc = field.attrs.set_doc("new_field");
+			let default = match &field.default {
+				Some(default) => {
+					if let Err(e) = serde_json::from_str::<serde_json::Value>(&default.value()) {
+						field_types_and_instantiations.errors.extend(
+							Error::new_spanned(
+								default,
+								format_args!("`default` attribute should be valid JSON: {e}"),
+							)
+							.to_compile_error(),
+						);
+					}
+					Some(default.value())
+				}
+				// The serializer writes `null` for fields that are not serialized, and
+				// `Option` is represented as a union whose first variant is `null`, so
+				// `null` is a valid default
+				None if is_nullable => Some("null".to_owned()),
+				None => None,
+			};
+			let set_default = default.map(|default| {
+				quote! {
+					new_field.set_default(Some(serde_avro_derive::json_default(#default)));
+				}
+			});
+			quote! {
+				let mut new_field = schema::RecordField::new(#field_name, #field_instantiation);
+				#set_doc
+				#set_default
+				fields.push(new_field);
+			}
+		};
+		push_fields.extend(quote! { { #push_field } });
+		if let Some(type_lookup_field_names) = &mut type_lookup_field_names {
+			type_lookup_field_names.push(rust_field_ident);
+		}
+	}
+	push_fields
+}
+
+/// Statement that pushes the tag field of an internally tagged enum (or
+/// struct) into a `fields` variable
+fn push_tag_field(tag: &syn::LitStr, errors: &mut TokenStream) -> TokenStream {
+	if let Err(e) = renamed_ident(&tag.value(), tag.span()) {
+		errors.extend(e.to_compile_error());
+	}
+	quote! {
+		fields.push(schema::RecordField::new(#tag, builder.find_or_build::<String>()));
+	}
+}
+
+fn unsupported_enum_representation(span: Span) -> Error {
+	Error::new(
+		span,
+		"Untagged and adjacently tagged enums are not supported by `BuildSchema`: \
+			only externally tagged (the default) and internally tagged \
+			(`#[serde(tag = \"...\")]`) enums are supported",
+	)
+}
+
+fn is_option(ty: &syn::Type) -> bool {
+	matches!(ty, syn::Type::Path(path) if path.path.segments.last().is_some_and(|s| s.ident == "Option"))
+}
+
+fn is_map(ty: &syn::Type) -> bool {
+	matches!(ty, syn::Type::Path(path) if path.path.segments.last().is_some_and(|s| s.ident == "HashMap" || s.ident == "BTreeMap"))
+}
+
+/// Identifier of the field in the Rust type: that of the Rust field if it is
+/// named, otherwise the `name` attribute, or the position of the field in the
+/// tuple (`_0`, `_1`...)
+///
+/// For fields that are not named in Rust, this is also the name of the field
+/// in the record.
+fn record_field_ident(field: &SchemaDeriveField, position: usize) -> Cow<'_, syn::Ident> {
+	match (&field.ident, &field.name) {
+		(Some(ident), _) | (None, Some(ident)) => Cow::Borrowed(ident),
+		(None, None) => Cow::Owned(format_ident!("_{position}")),
+	}
+}
+
+/// The attributes of a container, variant or field that are not
+/// `avro_schema` attributes but still affect the schema
+#[derive(Debug)]
+pub(crate) struct ForwardedAttributes {
+	serde: SerdeAttributes,
+	/// Doc comments, which are turned into the `doc` of records, fields and
+	/// enums
+	doc: Option<String>,
+}
+
+impl ForwardedAttributes {
+	/// To be used as `#[darling(with = ...)]` on the field that receives the
+	/// forwarded attributes
+	fn from_forwarded_attrs(attrs: Vec<syn::Attribute>) -> darling::Result<Self> {
+		let (doc_attrs, serde_attrs): (Vec<_>, Vec<_>) = attrs
+			.into_iter()
+			.partition(|attr| attr.path().is_ident("doc"));
+		let doc_lines: Vec<String> = doc_attrs
+			.iter()
+			.filter_map(|attr| match &attr.meta {
+				syn::Meta::NameValue(syn::MetaNameValue {
+					value:
+						syn::Expr::Lit(syn::ExprLit {
+							lit: syn::Lit::Str(line),
+							..
+						}),
+					..
+				}) => Some(line.value()),
+				_ => None,
+			})
+			.collect();
+		// `/// foo` is `#[doc = " foo"]`
+		let doc = doc_lines
+			.iter()
+			.map(|line| line.strip_prefix(' ').unwrap_or(line))
+			.collect::<Vec<_>>()
+			.join("\n")
+			.trim()
+			.to_owned();
+		Ok(Self {
+			serde: SerdeAttributes::from_forwarded_attrs(serde_attrs)?,
+			doc: (!doc.is_empty()).then_some(doc),
+		})
+	}
+
+	/// Statement that sets the `doc` of the `new_node` or `new_field` variable,
+	/// if there is any
+	fn set_doc(&self, var: &str) -> Option<TokenStream> {
+		let var = syn::Ident::new(var, Span::call_site());
+		self.doc.as_ref().map(|doc| {
+			quote! {
+				#var.set_doc(Some(#doc.to_owned()));
+			}
+		})
+	}
+}
+
 #[derive(Debug)]
 struct WithMetaPath<T> {
 	path: syn::Path,
@@ -0,0 +1,246 @@
+//! Reading of the `#[serde(...)]` attributes that affect what `serde_derive`
+//! serializes, so that the schema matches it
+
+use super::*;
+
+use syn::spanned::Spanned as _;
+
+/// The subset of the `#[serde(...)]` attributes of a container, variant or
+/// field that is relevant to schema generation
+///
+/// Attributes that are not relevant are ignored (`serde_derive` will report
+/// them if they are invalid).
+#[derive(Default, Debug)]
+pub(crate) struct SerdeAttributes {
+	/// `rename = "..."` or `rename(serialize = "...")`
+	pub(super) rename: Option<syn::LitStr>,
+	/// `rename_all = "..."` or `rename_all(serialize = "...")`
+	pub(super) rename_all: Option<RenameRule>,
+	/// `rename_all_fields = "..."` (enums only)
+	pub(super) rename_all_fields: Option<RenameRule>,
+	/// `skip` or `skip_serializing`
+	pub(super) skip_serializing: bool,
+	pub(super) skip_serializing_if: Option<Span>,
+	pub(super) flatten: Option<Span>,
+	/// `tag = "..."` (internally tagged enums)
+	pub(super) tag: Option<syn::LitStr>,
+	/// `untagged` or `content = "..."`, which are not supported
+	pub(super) unsupported_enum_representation: Option<Span>,
+}
+
+impl SerdeAttributes {
+	/// To be used as `#[darling(with = ...)]` on the field that receives the
+	/// forwarded `serde` attributes
+	pub(super) fn from_forwarded_attrs(attrs: Vec<syn::Attribute>) -> darling::Result<Self> {
+		let mut serde_attributes = Self::default();
+		let mut errors = darling::Error::accumulator();
+		for attr in &attrs {
+			errors.handle(
+				attr.parse_nested_meta(|meta| serde_attributes.parse_meta(meta))
+					.map_err(darling::Error::from),
+			);
+		}
+		errors.finish_with(serde_attributes)
+	}
+
+	/// Identifier under which `serde_derive` serializes this field or variant
+	///
+	/// `rename_all` should apply the relevant rename rule (if any) to the
+	/// unrenamed name. If the resulting name is not a valid Avro name, an error
+	/// is emitted and the Rust identifier is used instead.
+	pub(super) fn serialized_ident<'a>(
+		&self,
+		ident: &'a syn::Ident,
+		rename_all: impl FnOnce(&str) -> Option<String>,
+		errors: &mut TokenStream,
+	) -> Cow<'a, syn::Ident> {
+		let renamed = match &self.rename {
+			Some(rename) => Some((rename.value(), rename.span())),
+			None => rename_all(&ident.unraw().to_string()).map(|name| (name, ident.span())),
+		};
+		match renamed {
+			None => Cow::Borrowed(ident),
+			Some((name, span)) => match renamed_ident(&name, span) {
+				Ok(renamed) => Cow::Owned(renamed),
+				Err(e) => {
+					errors.extend(e.to_compile_error());
+					Cow::Borrowed(ident)
+				}
+			},
+		}
+	}
+
+	fn parse_meta(&mut self, meta: syn::meta::ParseNestedMeta<'_>) -> syn::Result<()> {
+		let path = &meta.path;
+		if path.is_ident("rename") {
+			self.rename = serialize_value(&meta)?;
+		} else if path.is_ident("rename_all") {
+			self.rename_all = serialize_value(&meta)?
+				.map(|rule| RenameRule::from_lit_str(&rule))
+				.transpose()?;
+		} else if path.is_ident("rename_all_fields") {
+			self.rename_all_fields = serialize_value(&meta)?
+				.map(|rule| RenameRule::from_lit_str(&rule))
+				.transpose()?;
+		} else if path.is_ident("skip") || path.is_ident("skip_serializing") {
+			self.skip_serializing = true;
+		} else if path.is_ident("skip_serializing_if") {
+			self.skip_serializing_if = Some(path.span());
+			skip_value(&meta)?;
+		} else if path.is_ident("flatten") {
+			self.flatten = Some(path.span());
+		} else if path.is_ident("tag") {
+			self.tag = Some(meta.value()?.parse()?);
+		} else if path.is_ident("untagged") || path.is_ident("content") {
+			self.unsupported_enum_representation = Some(path.span());
+			skip_value(&meta)?;
+		} else {
+			skip_value(&meta)?;
+		}
+		Ok(())
+	}
+}
+
+/// Parse either `name = "value"` or `name(serialize = "value", ...)`, in
+/// which case only the `serialize` value is relevant, as the schema describes
+/// what is serialized
+fn serialize_value(meta: &syn::meta::ParseNestedMeta<'_>) -> syn::Result<Option<syn::LitStr>> {
+	if meta.input.peek(syn::Token![=]) {
+		Ok(Some(meta.value()?.parse()?))
+	} else {
+		let mut value = None;
+		meta.parse_nested_meta(|nested| {
+			if nested.path.is_ident("serialize") {
+				value = Some(nested.value()?.parse()?);
+			} else {
+				skip_value(&nested)?;
+			}
+			Ok(())
+		})?;
+		Ok(value)
+	}
+}
+
+/// Consume the value of an attribute we don't need to interpret, if any
+fn skip_value(meta: &syn::meta::ParseNestedMeta<'_>) -> syn::Result<()> {
+	if meta.input.peek(syn::Token![=]) {
+		meta.value()?.parse::<syn::Expr>()?;
+	} else if meta.input.peek(syn::token::Paren) {
+		let content;
+		syn::parenthesized!(content in meta.input);
+		content.parse::<TokenStream>()?;
+	}
+	Ok(())
+}
+
+/// Same as `serde_derive`'s `rename_all` rules
+#[derive(Clone, Copy, Debug)]
+pub(super) enum RenameRule {
+	Lower,
+	Upper,
+	Pascal,
+	Camel,
+	Snake,
+	ScreamingSnake,
+	Kebab,
+	ScreamingKebab,
+}
+
+impl RenameRule {
+	fn from_lit_str(rule: &syn::LitStr) -> syn::Result<Self> {
+		Ok(match rule.value().as_str() {
+			"lowercase" => Self::Lower,
+			"UPPERCASE" => Self::Upper,
+			"PascalCase" => Self::Pascal,
+			"camelCase" => Self::Camel,
+			"snake_case" => Self::Snake,
+			"SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+			"kebab-case" => Self::Kebab,
+			"SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+			_ => return Err(Error::new_spanned(rule, "Unknown rename rule")),
+		})
+	}
+
+	/// Apply to a variant name, which is assumed to be in `PascalCase`
+	pub(super) fn apply_to_variant(self, variant: &str) -> String {
+		match self {
+			Self::Pascal => variant.to_owned(),
+			Self::Lower => variant.to_ascii_lowercase(),
+			Self::Upper => variant.to_ascii_uppercase(),
+			Self::Camel => lowercase_first_char(variant),
+			Self::Snake => {
+				let mut snake = String::new();
+				for (i, ch) in variant.char_indices() {
+					if i > 0 && ch.is_uppercase() {
+						snake.push('_');
+					}
+					snake.push(ch.to_ascii_lowercase());
+				}
+				snake
+			}
+			Self::ScreamingSnake => Self::Snake.apply_to_variant(variant).to_ascii_uppercase(),
+			Self::Kebab => Self::Snake.apply_to_variant(variant).replace('_', "-"),
+			Self::ScreamingKebab => Self::ScreamingSnake
+				.apply_to_variant(variant)
+				.replace('_', "-"),
+		}
+	}
+
+	/// Apply to a field name, which is assumed to be in `snake_case`
+	pub(super) fn apply_to_field(self, field: &str) -> String {
+		match self {
+			Self::Lower | Self::Snake => field.to_owned(),
+			Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+			Self::Pascal => {
+				let mut pascal = String::new();
+				let mut capitalize = true;
+				for ch in field.chars() {
+					if ch == '_' {
+						capitalize = true;
+					} else if capitalize {
+						pascal.push(ch.to_ascii_uppercase());
+						capitalize = false;
+					} else {
+						pascal.push(ch);
+					}
+				}
+				pascal
+			}
+			Self::Camel => lowercase_first_char(&Self::Pascal.apply_to_field(field)),
+			Self::Kebab => field.replace('_', "-"),
+			Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+		}
+	}
+}
+
+fn lowercase_first_char(s: &str) -> String {
+	let mut chars = s.chars();
+	match chars.next() {
+		None => String::new(),
+		Some(first) => first.to_lowercase().chain(chars).collect(),
+	}
+}
+
+/// Build the identifier that the schema will use for a name that was
+/// renamed via serde attributes
+///
+/// Avro names must match `[A-Za-z_][A-Za-z0-9_]*`, which are all valid Rust
+/// identifiers, so this errors if the name is not one.
+pub(super) fn renamed_ident(name: &str, span: Span) -> Result<syn::Ident, Error> {
+	let mut chars = name.chars();
+	let is_valid_avro_name = chars
+		.next()
+		.is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+		&& chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+	if is_valid_avro_name {
+		Ok(syn::Ident::new(name, span))
+	} else {
+		Err(Error::new(
+			span,
+			format_args!(
+				"`{name}` is not a valid Avro name: \
+					Avro names must match `[A-Za-z_][A-Za-z0-9_]*`"
+			),
+		))
+	}
+}
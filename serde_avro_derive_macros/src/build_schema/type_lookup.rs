@@ -7,10 +7,7 @@ pub(super) fn build_type_lookup(
 	field_idents: Option<&[&syn::Ident]>,
 	field_types: &[Cow<'_, syn::Type>],
 ) -> (syn::Type, Option<syn::ItemStruct>, bool) {
-	let has_non_lifetime_generics = generics
-		.params
-		.iter()
-		.any(|gp| !matches!(gp, syn::GenericParam::Lifetime(_)));
+	let has_non_lifetime_generics = has_non_lifetime_generics(generics);
 	let (type_lookup, type_lookup_decl): (syn::Type, _) = match has_non_lifetime_generics {
 		false => {
 			let type_lookup = if generics.params.is_empty() {
@@ -101,7 +98,7 @@ pub(super) fn build_type_lookup(
 						args: field_types
 							.iter()
 							.map(|ty| -> syn::GenericArgument {
-								parse_quote!(<#ty as serde_avro_derive::BuildSchema>::TypeLookup)
+								parse_quote!(<#ty as serde_avro_derive::FieldTypeLookup>::TypeLookup)
 							})
 							.collect(),
 						colon2_token: Default::default(),
@@ -116,6 +113,16 @@ pub(super) fn build_type_lookup(
 	(type_lookup, type_lookup_decl, has_non_lifetime_generics)
 }
 
+/// If that is the case, named types need to be suffixed with a hash of the
+/// `TypeId` of their `TypeLookup`, otherwise different instantiations of the
+/// generic type would generate different schemas with the same name
+pub(super) fn has_non_lifetime_generics(generics: &syn::Generics) -> bool {
+	generics
+		.params
+		.iter()
+		.any(|gp| !matches!(gp, syn::GenericParam::Lifetime(_)))
+}
+
 struct TurnLifetimesToStatic;
 impl VisitMut for TurnLifetimesToStatic {
 	fn visit_lifetime_mut(&mut self, i: &mut syn::Lifetime) {
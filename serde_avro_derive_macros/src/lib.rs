@@ -63,10 +63,53 @@ use darling::FromDeriveInput;
 /// # }
 /// ```
 ///
+/// # Tuple structs
+///
+/// Tuple structs are represented as records, whose fields are named after
+/// their position in the tuple, unless overridden with the
+/// `#[avro_schema(name = field_name)]` attribute:
+///
+/// ```
+/// use serde_avro_derive::BuildSchema;
+///
+/// #[derive(BuildSchema)]
+/// struct Point(f64, #[avro_schema(name = y)] f64);
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let schema = Point::schema()?;
+///
+/// // The [`serde_avro_fast::schema::BuildSchema`] implementation will
+/// // generate the following schema:
+/// let schema_str = r#"{
+///   "type": "record",
+///   "name": "crate_name.path.to.Point",
+///   "fields": [
+///     {
+///       "name": "_0",
+///       "type": "double"
+///     },
+///     {
+///       "name": "y",
+///       "type": "double"
+///     }
+///   ]
+/// }"#;
+///
+/// # let actual_schema = serde_json::to_string_pretty(&Point::schema_mut())
+/// #     .unwrap()
+/// #     .replace("rust_out.", "crate_name.path.to.");
+/// assert_eq!(actual_schema, schema_str);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Newtype structs (tuple structs with a single field) are instead represented
+/// as their inner type.
+///
 /// # Enums
 ///
 /// Enums are supported. Unit variants are represented as avro enums, while
-/// newtype variants are represented as avro unions.
+/// newtype, struct and tuple variants are represented as avro unions.
 ///
 /// ## Enums with unit variants
 ///
@@ -144,6 +187,61 @@ use darling::FromDeriveInput;
 /// # }
 /// ```
 ///
+/// ## Enums with struct and tuple variants
+///
+/// Struct and tuple variants are represented as records in the union, named
+/// after the variant. Fields of tuple variants are named like those of
+/// [tuple structs](#tuple-structs).
+///
+/// ```
+/// use serde_avro_derive::BuildSchema;
+///
+/// #[derive(BuildSchema)]
+/// enum Event {
+/// 	Created { id: i64 },
+/// 	Moved(i64, i64),
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let schema = Event::schema()?;
+///
+/// // The [`serde_avro_fast::schema::BuildSchema`] implementation will
+/// // generate the following schema:
+/// let schema_str = r#"[
+///   {
+///     "type": "record",
+///     "name": "crate_name.path.to.Event.Created",
+///     "fields": [
+///       {
+///         "name": "id",
+///         "type": "long"
+///       }
+///     ]
+///   },
+///   {
+///     "type": "record",
+///     "name": "crate_name.path.to.Event.Moved",
+///     "fields": [
+///       {
+///         "name": "_0",
+///         "type": "long"
+///       },
+///       {
+///         "name": "_1",
+///         "type": "long"
+///       }
+///     ]
+///   }
+/// ]"#;
+///
+/// # let actual_schema = serde_json::to_string_pretty(&Event::schema_mut())
+/// #     .unwrap()
+/// #     .replace("rust_out.", "crate_name.path.to.");
+/// assert_eq!(actual_schema, schema_str);
+/// # Ok(())
+/// # }
+/// ```
+///
 /// # Customize field schema
 ///
 /// Field attributes can be used to specify logical type or override the
@@ -216,15 +314,71 @@ use darling::FromDeriveInput;
 /// # }
 /// ```
 ///
+/// If a field is serialized in a way that no type describes (e.g. with
+/// `#[serde(with = ...)]`), its whole schema can be built by a function with
+/// signature `fn(&mut SchemaBuilder) -> SchemaKey`, specified with
+/// `#[avro_schema(with = path::to::function)]`. The function is called via
+/// `SchemaBuilder::find_or_build_with`, so it is only called once per schema
+/// even if several fields use it. It may be generic (e.g.
+/// `with = array_of::<T>`), as long as its type arguments are `'static`.
+/// ```
+/// use serde_avro_derive::{
+/// 	serde_avro_fast::schema::{Array, SchemaKey},
+/// 	BuildSchema, SchemaBuilder,
+/// };
+///
+/// #[derive(BuildSchema)]
+/// #[allow(unused)]
+/// struct Custom {
+/// 	// e.g. serialized as an array of strings via `#[serde(with = ...)]`
+/// 	#[avro_schema(with = array_of_strings)]
+/// 	ids: Vec<u128>,
+/// }
+///
+/// fn array_of_strings(builder: &mut SchemaBuilder) -> SchemaKey {
+/// 	let reserved_schema_key = builder.reserve();
+/// 	let new_node = Array::new(builder.find_or_build::<String>()).into();
+/// 	builder.nodes[reserved_schema_key] = new_node;
+/// 	SchemaKey::from_idx(reserved_schema_key)
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let expected_schema = r#"{
+///   "type": "record",
+///   "name": "crate_name.path.to.Custom",
+///   "fields": [
+///     {
+///       "name": "ids",
+///       "type": {
+///         "type": "array",
+///         "items": "string"
+///       }
+///     }
+///   ]
+/// }"#;
+///
+/// # let actual_schema = serde_json::to_string_pretty(&Custom::schema_mut())
+/// #     .unwrap()
+/// #     .replace("rust_out.", "crate_name.path.to.");
+/// assert_eq!(actual_schema, expected_schema);
+/// # Ok(())
+/// # }
+/// ```
+///
 /// # Namespace and name override
 ///
 /// The namespace will be inferred from the module path of the type being
 /// derived. However it is possible to override this using the
 /// `#[avro_schema(namespace = "my.namespace")]` attribute.
 ///
-/// Similarly, the name in the schema can be overridden using the
-/// `#[avro_schema(name = "NameOverride")]` attribute.
-/// In that second case, it is also required to rename the struct from `serde`'s
+/// The name in the schema is that of the type, or the one it is renamed to
+/// from `serde`'s point of view with `#[serde(rename = "...")]`. (This is a
+/// breaking change: previous versions ignored `#[serde(rename = "...")]` on
+/// the type itself, so the schema of such types now has a different name.)
+///
+/// It can also be overridden using the `#[avro_schema(name = NameOverride)]`
+/// attribute, which takes precedence over `#[serde(rename = "...")]`.
+/// In that case, it is also required to rename the struct from `serde`'s
 /// point of view using `#[serde(rename = "NameOverride")]`, otherwise in some
 /// cases where enums are involved, the serializer won't be able to resolve the
 /// union variant.
@@ -264,6 +418,154 @@ use darling::FromDeriveInput;
 /// # }
 /// ```
 ///
+/// # Serde attributes
+///
+/// The schema follows what `serde_derive` serializes, so the following
+/// `#[serde(...)]` attributes are taken into account:
+/// - `rename`, `rename_all` and `rename_all_fields` rename records, fields,
+///   enum symbols and union variants. The resulting names must be valid Avro
+///   names.
+/// - `skip` and `skip_serializing` remove the field or variant from the
+///   schema.
+/// - `skip_serializing_if` makes the field nullable (if it isn't already an
+///   `Option`) with a `null` default, as the serializer writes `null` for
+///   fields that are not serialized. If the schema of the field is already a
+///   union, `null` is added as its first variant. When deserializing a
+///   struct, such `null` fields are treated as absent, so the field should
+///   also have `#[serde(default)]` (unless it's an `Option`).
+/// - `flatten` splices the fields of the flattened record into the record.
+///   Flattening maps is not supported, as their keys can't be known in
+///   advance.
+/// - `tag` (internally tagged enums): every variant is represented as a
+///   record in the union, whose first field is the tag. Fields of the inner
+///   type of newtype variants are spliced into that record.
+///
+/// Untagged and adjacently tagged enums are not supported. Other attributes
+/// don't affect the schema.
+///
+/// ```
+/// use serde_avro_derive::BuildSchema;
+///
+/// #[derive(BuildSchema, serde_derive::Serialize)]
+/// #[serde(tag = "type", rename_all = "snake_case")]
+/// enum Shape {
+/// 	Circle {
+/// 		radius: f64,
+/// 	},
+/// 	Nothing,
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let schema = Shape::schema()?;
+///
+/// // The [`serde_avro_fast::schema::BuildSchema`] implementation will
+/// // generate the following schema:
+/// let schema_str = r#"[
+///   {
+///     "type": "record",
+///     "name": "crate_name.path.to.Shape.circle",
+///     "fields": [
+///       {
+///         "name": "type",
+///         "type": "string"
+///       },
+///       {
+///         "name": "radius",
+///         "type": "double"
+///       }
+///     ]
+///   },
+///   {
+///     "type": "record",
+///     "name": "crate_name.path.to.Shape.nothing",
+///     "fields": [
+///       {
+///         "name": "type",
+///         "type": "string"
+///       }
+///     ]
+///   }
+/// ]"#;
+///
+/// # let actual_schema = serde_json::to_string_pretty(&Shape::schema_mut())
+/// #     .unwrap()
+/// #     .replace("rust_out.", "crate_name.path.to.");
+/// assert_eq!(actual_schema, schema_str);
+/// #
+/// # serde_avro_derive::serde_avro_fast::to_datum_vec(&Shape::Circle { radius: 1. }, &mut serde_avro_derive::serde_avro_fast::ser::SerializerConfig::new(&schema)).unwrap();
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Flattening a map is rejected at compile time:
+/// ```compile_fail
+/// use serde_avro_derive::BuildSchema;
+///
+/// #[derive(BuildSchema, serde_derive::Serialize)]
+/// struct Foo {
+/// 	#[serde(flatten)]
+/// 	extra: std::collections::HashMap<String, i32>,
+/// }
+/// ```
+///
+/// # Documentation and defaults
+///
+/// Doc comments on structs, enums and fields are written to the `doc` of the
+/// corresponding record, enum or record field.
+///
+/// Record fields get a `default` if they have an
+/// `#[avro_schema(default = "...")]` attribute, which contains the default
+/// value as JSON (checked at compile time), or if they are nullable (`Option`
+/// fields, and fields with `skip_serializing_if`), in which case the default
+/// is `null`. `BuildSchema::schema` returns an error if a default doesn't match
+/// the schema of its field.
+///
+/// ```
+/// use serde_avro_derive::BuildSchema;
+///
+/// /// A user of the app
+/// #[derive(BuildSchema)]
+/// #[allow(unused)]
+/// struct User {
+/// 	/// How many times they logged in
+/// 	#[avro_schema(default = "0")]
+/// 	logins: i64,
+/// 	nickname: Option<String>,
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // The [`serde_avro_fast::schema::BuildSchema`] implementation will
+/// // generate the following schema:
+/// let schema_str = r#"{
+///   "type": "record",
+///   "name": "crate_name.path.to.User",
+///   "doc": "A user of the app",
+///   "fields": [
+///     {
+///       "name": "logins",
+///       "doc": "How many times they logged in",
+///       "type": "long",
+///       "default": 0
+///     },
+///     {
+///       "name": "nickname",
+///       "type": [
+///         "null",
+///         "string"
+///       ],
+///       "default": null
+///     }
+///   ]
+/// }"#;
+///
+/// # let actual_schema = serde_json::to_string_pretty(&User::schema_mut())
+/// #     .unwrap()
+/// #     .replace("rust_out.", "crate_name.path.to.");
+/// assert_eq!(actual_schema, schema_str);
+/// # Ok(())
+/// # }
+/// ```
+///
 /// # Generics
 ///
 /// Generics are supported - see
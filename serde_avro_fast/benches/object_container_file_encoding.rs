@@ -1,4 +1,4 @@
-#![allow(missing_docs)]
+#![allow(missing_docs, clippy::result_large_err)]
 
 use serde_avro_fast::object_container_file_encoding::{Compression, CompressionLevel};
 
@@ -129,7 +129,7 @@ fn bench_object_container_file_serialization(c: &mut Criterion) {
 			},
 		})
 		.collect();
-	for &(name, compression_codec, apache_codec) in &[
+	for (name, compression_codec, apache_codec) in [
 		("null", Compression::Null, apache_avro::Codec::Null),
 		#[cfg(feature = "deflate")]
 		(
@@ -162,6 +162,7 @@ fn bench_object_container_file_serialization(c: &mut Criterion) {
 			"zstandard",
 			Compression::Zstandard {
 				level: CompressionLevel::default(),
+				dictionary: None,
 			},
 			apache_avro::Codec::Zstandard,
 		),
@@ -173,7 +174,7 @@ fn bench_object_container_file_serialization(c: &mut Criterion) {
 				b.iter(|| {
 					serde_avro_fast::object_container_file_encoding::write_all(
 						&schema,
-						compression_codec,
+						compression_codec.clone(),
 						Vec::new(),
 						inputs,
 					)
@@ -199,7 +200,7 @@ fn bench_object_container_file_serialization(c: &mut Criterion) {
 
 fn bench_object_container_file_deserialization(c: &mut Criterion) {
 	let schema: serde_avro_fast::Schema = RAW_BIG_SCHEMA.parse().unwrap();
-	for &(name, codec) in &[
+	for (name, codec) in [
 		("null", Compression::Null),
 		#[cfg(feature = "deflate")]
 		(
@@ -229,6 +230,7 @@ fn bench_object_container_file_deserialization(c: &mut Criterion) {
 			"zstandard",
 			Compression::Zstandard {
 				level: CompressionLevel::default(),
+				dictionary: None,
 			},
 		),
 	] {
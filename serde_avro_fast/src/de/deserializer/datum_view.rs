@@ -0,0 +1,202 @@
+use super::*;
+
+use std::cell::RefCell;
+
+/// Lazy, zero-copy view over a serialized avro datum
+///
+/// This allows reading only a few fields of a potentially large record,
+/// without deserializing the rest of it:
+/// ```
+/// let schema: serde_avro_fast::Schema = r#"
+/// {
+/// 	"type": "record",
+/// 	"name": "Order",
+/// 	"fields": [
+/// 		{ "name": "comment", "type": "string" },
+/// 		{
+/// 			"name": "customer",
+/// 			"type": {
+/// 				"type": "record",
+/// 				"name": "Customer",
+/// 				"fields": [
+/// 					{ "name": "age", "type": "int" },
+/// 					{ "name": "id", "type": "string" }
+/// 				]
+/// 			}
+/// 		}
+/// 	]
+/// }
+/// "#
+/// .parse()
+/// .expect("Failed to parse schema");
+///
+/// let avro_datum = &[6, 102, 111, 111, 84, 6, 98, 97, 114];
+///
+/// let view = serde_avro_fast::de::DatumView::new(avro_datum, &schema)?;
+/// let id: &str = view.field("customer")?.field("id")?.as_str()?;
+/// assert_eq!(id, "bar");
+/// # Ok::<_, serde_avro_fast::de::DeError>(())
+/// ```
+///
+/// Only the path that is requested is decoded: fields that precede the
+/// requested one are skipped in the same manner as
+/// [`deserialize_ignored_any`](serde::Deserializer::deserialize_ignored_any)
+/// would (e.g. without UTF-8 validation and using block sizes when available),
+/// and the offsets of the fields of a record are cached in its `DatumView`, so
+/// that accessing several fields of the same record doesn't re-skip the
+/// preceding ones.
+///
+/// Unions are resolved transparently: the view points to the variant that is
+/// actually serialized. (Use [`is_null`](Self::is_null) to check for a `null`
+/// variant.)
+pub struct DatumView<'a, 's> {
+	/// Starts at the beginning of the datum, but may extend further than its
+	/// end
+	slice: &'a [u8],
+	/// Never a union: unions are resolved on construction
+	schema_node: NodeRef<'s>,
+	/// `field_offsets[i]` is the start of the `i`-th field of the record
+	/// within `slice`, for all the fields we have already located
+	field_offsets: RefCell<Vec<usize>>,
+}
+
+impl<'a, 's> DatumView<'a, 's> {
+	/// Construct a `DatumView` over a datum serialized with the given schema
+	///
+	/// This only fails if the schema root is a union and the union
+	/// discriminant can't be read.
+	pub fn new(slice: &'a [u8], schema: &'s Schema) -> Result<Self, DeError> {
+		Self::from_schema_node(slice, schema.root())
+	}
+
+	fn from_schema_node(
+		mut slice: &'a [u8],
+		mut schema_node: NodeRef<'s>,
+	) -> Result<Self, DeError> {
+		while let SchemaNode::Union(ref union) = *schema_node.as_ref() {
+			let mut state = DeserializerState::from_schema_node(SliceRead::new(slice), schema_node);
+			let union_discriminant = read_discriminant(&mut state)?;
+			schema_node = *union
+				.variants
+				.get(union_discriminant)
+				.ok_or_else(|| DeError::new("Could not find union discriminant in schema"))?;
+			slice = state.reader.remaining();
+		}
+		Ok(Self {
+			slice,
+			schema_node,
+			field_offsets: RefCell::new(Vec::new()),
+		})
+	}
+
+	/// Obtain a view over the field of this record that has the given name
+	///
+	/// Fails if this datum is not a record, if the record has no such field,
+	/// or if the fields that precede it can't be skipped.
+	pub fn field(&self, name: &str) -> Result<DatumView<'a, 's>, DeError> {
+		let record = self.record()?;
+		match record.per_name_lookup.get(name) {
+			Some(&field_idx) => self.field_at_idx(record, field_idx),
+			None => Err(DeError::custom(format_args!(
+				"Record {} has no field named {name:?}",
+				record.name.fully_qualified_name()
+			))),
+		}
+	}
+
+	/// Obtain a view over the field of this record that is at the given
+	/// position (in schema order)
+	pub fn field_by_position(&self, position: usize) -> Result<DatumView<'a, 's>, DeError> {
+		let record = self.record()?;
+		if position >= record.fields.len() {
+			return Err(DeError::custom(format_args!(
+				"Record {} only has {} fields",
+				record.name.fully_qualified_name(),
+				record.fields.len()
+			)));
+		}
+		self.field_at_idx(record, position)
+	}
+
+	fn record(&self) -> Result<&'s Record<'s>, DeError> {
+		match *self.schema_node.as_ref() {
+			SchemaNode::Record(ref record) => Ok(record),
+			_ => Err(DeError::new(
+				"Attempted to access a field of a datum whose schema is not a record",
+			)),
+		}
+	}
+
+	fn field_at_idx(
+		&self,
+		record: &'s Record<'s>,
+		field_idx: usize,
+	) -> Result<DatumView<'a, 's>, DeError> {
+		let mut field_offsets = self.field_offsets.borrow_mut();
+		if field_offsets.is_empty() {
+			field_offsets.push(0);
+		}
+		while field_offsets.len() <= field_idx {
+			let last_located_idx = field_offsets.len() - 1;
+			let start = field_offsets[last_located_idx];
+			let field_slice = &self.slice[start..];
+			let mut state = DeserializerState::from_schema_node(
+				SliceRead::new(field_slice),
+				record.fields[last_located_idx].schema,
+			);
+			IgnoredAny::deserialize(state.deserializer())?;
+			field_offsets.push(start + (field_slice.len() - state.reader.remaining().len()));
+		}
+		Self::from_schema_node(
+			&self.slice[field_offsets[field_idx]..],
+			record.fields[field_idx].schema,
+		)
+	}
+
+	/// Deserialize the datum this view points to
+	///
+	/// This works the same as [`from_datum_slice`](crate::from_datum_slice),
+	/// so the resulting value may borrow from the original slice.
+	pub fn deserialize<T: Deserialize<'a>>(&self) -> Result<T, DeError> {
+		T::deserialize(
+			DeserializerState::from_schema_node(SliceRead::new(self.slice), self.schema_node)
+				.deserializer(),
+		)
+	}
+
+	/// Whether this datum is `null` (typically, the `null` variant of an
+	/// optional field)
+	pub fn is_null(&self) -> bool {
+		matches!(*self.schema_node.as_ref(), SchemaNode::Null)
+	}
+
+	/// Borrow the string this view points to from the original slice
+	pub fn as_str(&self) -> Result<&'a str, DeError> {
+		self.deserialize()
+	}
+
+	/// Borrow the bytes (or fixed) this view points to from the original slice
+	pub fn as_bytes(&self) -> Result<&'a [u8], DeError> {
+		self.deserialize()
+	}
+
+	/// Read the boolean this view points to
+	pub fn as_bool(&self) -> Result<bool, DeError> {
+		self.deserialize()
+	}
+
+	/// Read the integer this view points to
+	pub fn as_i32(&self) -> Result<i32, DeError> {
+		self.deserialize()
+	}
+
+	/// Read the integer this view points to
+	pub fn as_i64(&self) -> Result<i64, DeError> {
+		self.deserialize()
+	}
+
+	/// Read the floating point number this view points to
+	pub fn as_f64(&self) -> Result<f64, DeError> {
+		self.deserialize()
+	}
+}
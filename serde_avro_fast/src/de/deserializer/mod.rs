@@ -1,10 +1,12 @@
 mod allowed_depth;
+mod datum_view;
 mod types;
 mod unit_variant_enum_access;
 
 use {types::*, unit_variant_enum_access::UnitVariantEnumAccess};
 
 pub(crate) use allowed_depth::AllowedDepth;
+pub use datum_view::DatumView;
 
 use super::*;
 
@@ -57,6 +59,7 @@ impl<'de, R: ReadSlice<'de>> Deserializer<'de> for DatumDeserializer<'_, '_, R>
 					record_fields: record.fields.iter(),
 					state: self.state,
 					allowed_depth: self.allowed_depth.dec()?,
+					omit_null_fields: false,
 				})
 			}
 			SchemaNode::Enum(ref enum_) => read_enum_as_str(self.state, &enum_.symbols, visitor),
@@ -72,7 +75,12 @@ impl<'de, R: ReadSlice<'de>> Deserializer<'de> for DatumDeserializer<'_, '_, R>
 			SchemaNode::BigDecimal => {
 				read_decimal(self.state, DecimalMode::Big, VisitorHint::Str, visitor)
 			}
-			SchemaNode::Uuid => read_length_delimited(self.state, StringVisitor(visitor)),
+			SchemaNode::Uuid(UuidRepr::String) => {
+				read_length_delimited(self.state, StringVisitor(visitor))
+			}
+			SchemaNode::Uuid(UuidRepr::Fixed(_)) => {
+				self.state.read_slice(16, BytesVisitor(visitor))
+			}
 			SchemaNode::Date => visitor.visit_i32(self.state.read_varint()?),
 			SchemaNode::TimeMillis => visitor.visit_i32(self.state.read_varint()?),
 			SchemaNode::TimeMicros => visitor.visit_i64(self.state.read_varint()?),
@@ -202,6 +210,21 @@ impl<'de, R: ReadSlice<'de>> Deserializer<'de> for DatumDeserializer<'_, '_, R>
 			SchemaNode::Fixed(ref fixed) => {
 				self.state.read_slice(fixed.size, StringVisitor(visitor))
 			}
+			// If we get hinted on str for temporal logical types, we provide the string
+			// representations that chrono, time and jiff expect
+			#[cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+			SchemaNode::Date
+			| SchemaNode::TimeMillis
+			| SchemaNode::TimeMicros
+			| SchemaNode::TimestampMillis
+			| SchemaNode::TimestampMicros => read_temporal_as_str(self.state, self.schema_node, visitor),
+			// uuid::Uuid and String expect the hyphenated representation
+			#[cfg(feature = "uuid")]
+			SchemaNode::Uuid(UuidRepr::Fixed(_)) => read_uuid_fixed_as_str(self.state, visitor),
+			#[cfg(not(feature = "uuid"))]
+			SchemaNode::Uuid(UuidRepr::Fixed(ref fixed)) => {
+				self.state.read_slice(fixed.size, StringVisitor(visitor))
+			}
 			_ => self.deserialize_any(visitor),
 		}
 	}
@@ -296,7 +319,8 @@ impl<'de, R: ReadSlice<'de>> Deserializer<'de> for DatumDeserializer<'_, '_, R>
 	where
 		V: Visitor<'de>,
 	{
-		// Allows deserializing Duration as (u32, u32, u32)
+		// Allows deserializing Duration as (u32, u32, u32), and decimals as
+		// (unscaled, scale)
 		match *self.schema_node {
 			SchemaNode::Array(elements_schema) => visitor.visit_seq(ArraySeqAccess {
 				elements_schema: elements_schema.as_ref(),
@@ -305,6 +329,28 @@ impl<'de, R: ReadSlice<'de>> Deserializer<'de> for DatumDeserializer<'_, '_, R>
 			SchemaNode::Duration if len == 3 => visitor.visit_seq(DurationMapAndSeqAccess {
 				duration_buf: &self.state.read_const_size_buf::<12>()?,
 			}),
+			// Allows deserializing decimals as their raw (unscaled, scale) representation
+			SchemaNode::Decimal(ref decimal) if len == 2 => {
+				read_decimal_raw(self.state, DecimalMode::Regular(decimal), visitor)
+			}
+			SchemaNode::BigDecimal if len == 2 => {
+				read_decimal_raw(self.state, DecimalMode::Big, visitor)
+			}
+			// Allows deserializing records as tuples, matching fields by position
+			// (e.g. tuple structs and tuple variants)
+			SchemaNode::Record(ref record) if len == record.fields.len() => {
+				visitor.visit_seq(RecordSeqAccess {
+					record_fields: record.fields.iter(),
+					state: self.state,
+					allowed_depth: self.allowed_depth.dec()?,
+				})
+			}
+			SchemaNode::Union(ref union) => Self {
+				schema_node: read_union_discriminant(self.state, union)?,
+				state: self.state,
+				allowed_depth: self.allowed_depth.dec()?,
+			}
+			.deserialize_tuple(len, visitor),
 			_ => self.deserialize_any(visitor),
 		}
 	}
@@ -331,20 +377,47 @@ impl<'de, R: ReadSlice<'de>> Deserializer<'de> for DatumDeserializer<'_, '_, R>
 
 	fn deserialize_struct<V>(
 		self,
-		_: &'static str,
-		_: &'static [&'static str],
+		name: &'static str,
+		fields: &'static [&'static str],
 		visitor: V,
 	) -> Result<V::Value, Self::Error>
 	where
 		V: Visitor<'de>,
 	{
-		self.deserialize_map(visitor)
+		// Allows deserializing `std::time::Duration` and `std::time::SystemTime`,
+		// which deserialize from structs of seconds and nanoseconds
+		match (self.schema_node, name, fields) {
+			(SchemaNode::Duration, "Duration", ["secs", "nanos"]) => {
+				read_std_duration(self.state, visitor)
+			}
+			(
+				SchemaNode::TimestampMillis | SchemaNode::TimestampMicros,
+				"SystemTime",
+				["secs_since_epoch", "nanos_since_epoch"],
+			) => read_std_system_time(self.state, self.schema_node, visitor),
+			(SchemaNode::Union(ref union), _, _) => Self {
+				schema_node: read_union_discriminant(self.state, union)?,
+				state: self.state,
+				allowed_depth: self.allowed_depth.dec()?,
+			}
+			.deserialize_struct(name, fields, visitor),
+			// Fields that were skipped when serializing the struct are
+			// serialized as `null`, so they are omitted here to let
+			// `#[serde(default)]` apply
+			(SchemaNode::Record(ref record), _, _) => visitor.visit_map(RecordMapAccess {
+				record_fields: record.fields.iter(),
+				state: self.state,
+				allowed_depth: self.allowed_depth.dec()?,
+				omit_null_fields: true,
+			}),
+			_ => self.deserialize_map(visitor),
+		}
 	}
 
 	fn deserialize_enum<V>(
 		self,
 		_: &'static str,
-		_: &'static [&'static str],
+		variants: &'static [&'static str],
 		visitor: V,
 	) -> Result<V::Value, Self::Error>
 	where
@@ -360,6 +433,7 @@ impl<'de, R: ReadSlice<'de>> Deserializer<'de> for DatumDeserializer<'_, '_, R>
 				variant_schema: read_union_discriminant(self.state, union)?,
 				state: self.state,
 				allowed_depth: self.allowed_depth.dec()?,
+				variants,
 			}),
 			ref possible_unit_variant_identifier @ (SchemaNode::Int
 			| SchemaNode::Long
@@ -380,7 +454,7 @@ impl<'de, R: ReadSlice<'de>> Deserializer<'de> for DatumDeserializer<'_, '_, R>
 			| SchemaNode::Record(_)
 			| SchemaNode::Decimal(_)
 			| SchemaNode::BigDecimal
-			| SchemaNode::Uuid
+			| SchemaNode::Uuid(_)
 			| SchemaNode::Date
 			| SchemaNode::TimeMillis
 			| SchemaNode::TimeMicros
@@ -390,6 +464,7 @@ impl<'de, R: ReadSlice<'de>> Deserializer<'de> for DatumDeserializer<'_, '_, R>
 				state: self.state,
 				variant_schema: not_unit_variant_identifier,
 				allowed_depth: self.allowed_depth.dec()?,
+				variants,
 			}),
 		}
 	}
@@ -1,6 +1,7 @@
 use super::*;
 
 use {
+	crate::decimal::Unscaled,
 	rust_decimal::prelude::ToPrimitive as _,
 	std::{io::Read, marker::PhantomData},
 };
@@ -19,6 +20,112 @@ pub(in super::super) fn read_decimal<'de, R, V>(
 where
 	R: ReadSlice<'de>,
 	V: Visitor<'de>,
+{
+	let (unscaled, scale) = read_unscaled_and_scale(state, decimal_mode)?;
+	let unscaled = match unscaled {
+		Unscaled::I128(unscaled) => unscaled,
+		Unscaled::Big(ref big) => {
+			if scale == 0 && hint == VisitorHint::U128 {
+				if let Some(v) = unscaled.to_u128() {
+					return visitor.visit_u128(v);
+				}
+			}
+			let formatted = crate::decimal::format(big, scale).map_err(DeError::custom)?;
+			return visit_formatted(formatted, hint, visitor);
+		}
+	};
+	if scale == 0 {
+		match hint {
+			VisitorHint::U64 => {
+				if let Ok(v) = unscaled.try_into() {
+					return visitor.visit_u64(v);
+				} else if unscaled < 0 {
+					return visitor.visit_i128(unscaled);
+				}
+			}
+			VisitorHint::I64 => {
+				return if let Ok(v) = unscaled.try_into() {
+					visitor.visit_i64(v)
+				} else {
+					visitor.visit_i128(unscaled)
+				};
+			}
+			VisitorHint::U128 => {
+				return if let Ok(v) = unscaled.try_into() {
+					visitor.visit_u128(v)
+				} else {
+					visitor.visit_i128(unscaled)
+				};
+			}
+			VisitorHint::I128 => {
+				return visitor.visit_i128(unscaled);
+			}
+			VisitorHint::Str | VisitorHint::F64 => {}
+		}
+	}
+	let decimal = match rust_decimal::Decimal::try_from_i128_with_scale(unscaled, scale) {
+		Ok(decimal) => decimal,
+		Err(_) => {
+			// Too large for rust_decimal's 96 bit mantissa, or scale larger than 28
+			let formatted = crate::decimal::format_i128(unscaled, scale);
+			return visit_formatted(formatted, hint, visitor);
+		}
+	};
+	if hint == VisitorHint::F64 {
+		if let Some(float) = decimal.to_f64() {
+			return visitor.visit_f64(float);
+		}
+	}
+	serde::Serialize::serialize(
+		&decimal,
+		SerializeToVisitorStr {
+			visitor,
+			_lifetime: PhantomData,
+		},
+	)
+}
+
+fn visit_formatted<'de, V>(
+	formatted: String,
+	hint: VisitorHint,
+	visitor: V,
+) -> Result<V::Value, DeError>
+where
+	V: Visitor<'de>,
+{
+	if hint == VisitorHint::F64 {
+		if let Ok(float) = formatted.parse() {
+			return visitor.visit_f64(float);
+		}
+	}
+	visitor.visit_string(formatted)
+}
+
+/// Provides the decimal as an `(unscaled, scale)` tuple, where `unscaled` is
+/// the big-endian two's complement representation of the unscaled integer
+/// (or that integer itself if hinted so), and `scale` is a `u32`
+pub(in super::super) fn read_decimal_raw<'de, R, V>(
+	state: &mut DeserializerState<'_, R>,
+	decimal_mode: DecimalMode<'_>,
+	visitor: V,
+) -> Result<V::Value, DeError>
+where
+	R: ReadSlice<'de>,
+	V: Visitor<'de>,
+{
+	let (unscaled, scale) = read_unscaled_and_scale(state, decimal_mode)?;
+	visitor.visit_seq(DecimalRawSeqAccess {
+		unscaled: Some(unscaled),
+		scale: Some(scale),
+	})
+}
+
+fn read_unscaled_and_scale<'de, R>(
+	state: &mut DeserializerState<'_, R>,
+	decimal_mode: DecimalMode<'_>,
+) -> Result<(Unscaled, u32), DeError>
+where
+	R: ReadSlice<'de>,
 {
 	let (size, mut reader) = match decimal_mode {
 		DecimalMode::Big => {
@@ -53,21 +160,32 @@ where
 			..
 		}) => (fixed.size, ReaderEither::Reader(&mut state.reader)),
 	};
-	let mut buf = [0u8; 16];
-	let start = buf.len().checked_sub(size).ok_or_else(|| {
-		DeError::custom(format_args!(
-			"Decimals of size larger than 16 are not supported (got size {size})"
-		))
-	})?;
-	reader.read_exact(&mut buf[start..]).map_err(DeError::io)?;
-	if buf.get(start).is_some_and(|&v| v & 0x80 != 0) {
-		// This is a negative number in CA2 repr, we need to maintain that for the
-		// larger number
-		for v in &mut buf[0..start] {
-			*v = 0xFF;
+	let unscaled = match 16usize.checked_sub(size) {
+		Some(start) => {
+			let mut buf = [0u8; 16];
+			reader.read_exact(&mut buf[start..]).map_err(DeError::io)?;
+			if buf.get(start).is_some_and(|&v| v & 0x80 != 0) {
+				// This is a negative number in CA2 repr, we need to maintain that for the
+				// larger number
+				for v in &mut buf[0..start] {
+					*v = 0xFF;
+				}
+			}
+			Unscaled::I128(i128::from_be_bytes(buf))
 		}
-	}
-	let unscaled = i128::from_be_bytes(buf);
+		None => {
+			// Don't trust the size from the stream for preallocation
+			let mut buf = Vec::new();
+			(&mut reader)
+				.take(size as u64)
+				.read_to_end(&mut buf)
+				.map_err(DeError::io)?;
+			if buf.len() != size {
+				return Err(DeError::io(std::io::ErrorKind::UnexpectedEof.into()));
+			}
+			Unscaled::from_be_bytes(buf)
+		}
+	};
 	let scale = match decimal_mode {
 		DecimalMode::Big => integer_encoding::VarIntReader::read_varint::<i64>(&mut reader)
 			.map_err(DeError::io)?
@@ -92,49 +210,7 @@ where
 		}
 		ReaderEither::Reader(_) => {}
 	}
-	if scale == 0 {
-		match hint {
-			VisitorHint::U64 => {
-				if let Ok(v) = unscaled.try_into() {
-					return visitor.visit_u64(v);
-				} else if unscaled < 0 {
-					return visitor.visit_i128(unscaled);
-				}
-			}
-			VisitorHint::I64 => {
-				return if let Ok(v) = unscaled.try_into() {
-					visitor.visit_i64(v)
-				} else {
-					visitor.visit_i128(unscaled)
-				};
-			}
-			VisitorHint::U128 => {
-				return if let Ok(v) = unscaled.try_into() {
-					visitor.visit_u128(v)
-				} else {
-					visitor.visit_i128(unscaled)
-				};
-			}
-			VisitorHint::I128 => {
-				return visitor.visit_i128(unscaled);
-			}
-			VisitorHint::Str | VisitorHint::F64 => {}
-		}
-	}
-	let decimal = rust_decimal::Decimal::try_from_i128_with_scale(unscaled, scale)
-		.map_err(|e| DeError::custom(format_args!("Could not parse decimal from i128: {e}")))?;
-	if hint == VisitorHint::F64 {
-		if let Some(float) = decimal.to_f64() {
-			return visitor.visit_f64(float);
-		}
-	}
-	serde::Serialize::serialize(
-		&decimal,
-		SerializeToVisitorStr {
-			visitor,
-			_lifetime: PhantomData,
-		},
-	)
+	Ok((unscaled, scale))
 }
 
 #[derive(PartialEq, Eq)]
@@ -184,3 +260,94 @@ impl<R: Read> Read for ReaderEither<'_, R> {
 		}
 	}
 }
+
+struct DecimalRawSeqAccess {
+	unscaled: Option<Unscaled>,
+	scale: Option<u32>,
+}
+
+impl<'de> SeqAccess<'de> for DecimalRawSeqAccess {
+	type Error = DeError;
+
+	fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+	where
+		T: DeserializeSeed<'de>,
+	{
+		if let Some(unscaled) = self.unscaled.take() {
+			seed.deserialize(UnscaledDeserializer(unscaled)).map(Some)
+		} else if let Some(scale) = self.scale.take() {
+			seed.deserialize(value::U32Deserializer::new(scale))
+				.map(Some)
+		} else {
+			Ok(None)
+		}
+	}
+
+	fn size_hint(&self) -> Option<usize> {
+		Some(usize::from(self.unscaled.is_some()) + usize::from(self.scale.is_some()))
+	}
+}
+
+struct UnscaledDeserializer(Unscaled);
+
+impl<'de> Deserializer<'de> for UnscaledDeserializer {
+	type Error = DeError;
+
+	fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		self.0.with_be_bytes(|bytes| visitor.visit_bytes(bytes))
+	}
+
+	fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		match self.0 {
+			Unscaled::I128(unscaled) => visitor.visit_i128(unscaled),
+			Unscaled::Big(_) => Err(DeError::new(
+				"Decimal unscaled integer is larger than 16 bytes and does not fit in an i128",
+			)),
+		}
+	}
+
+	serde::forward_to_deserialize_any! {
+		bool f32 f64 char str string
+		bytes byte_buf option unit unit_struct newtype_struct seq tuple
+		tuple_struct map struct enum identifier ignored_any
+	}
+
+	// Integers all go through i128, and are converted by the visitor
+	fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.deserialize_i128(visitor)
+	}
+	fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.deserialize_i128(visitor)
+	}
+	fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.deserialize_i128(visitor)
+	}
+	fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.deserialize_i128(visitor)
+	}
+	fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.deserialize_i128(visitor)
+	}
+	fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.deserialize_i128(visitor)
+	}
+	fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.deserialize_i128(visitor)
+	}
+	fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		self.deserialize_i128(visitor)
+	}
+	// Except for u128, as there are values that fit in a u128 but not in an i128
+	fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+		match self.0.to_u128() {
+			Some(unscaled) => visitor.visit_u128(unscaled),
+			None => self.deserialize_i128(visitor),
+		}
+	}
+}
@@ -6,11 +6,21 @@ mod duration;
 mod enums;
 mod length_delimited;
 mod record;
+mod std_time;
+#[cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+mod temporal;
 mod union;
+#[cfg(feature = "uuid")]
+mod uuid;
 
 pub(super) use {
 	blocks::*, boolean::*, decimal::*, discriminant::*, duration::*, enums::*, length_delimited::*,
-	record::*, union::*,
+	record::*, std_time::*, union::*,
 };
 
+#[cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+pub(super) use temporal::*;
+#[cfg(feature = "uuid")]
+pub(super) use uuid::*;
+
 use super::*;
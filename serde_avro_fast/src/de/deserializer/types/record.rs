@@ -4,6 +4,13 @@ pub(in super::super) struct RecordMapAccess<'r, 's, R> {
 	pub(in super::super) state: &'r mut DeserializerState<'s, R>,
 	pub(in super::super) record_fields: std::slice::Iter<'s, RecordField<'s>>,
 	pub(in super::super) allowed_depth: AllowedDepth,
+	/// Whether nullable fields that are `null` should be omitted, as if they
+	/// were absent
+	///
+	/// This is used when deserializing structs: the serializer writes `null`
+	/// for the fields they skip (e.g. with `skip_serializing_if`), so
+	/// omitting them lets `#[serde(default)]` apply.
+	pub(in super::super) omit_null_fields: bool,
 }
 impl<'de, R: ReadSlice<'de>> MapAccess<'de> for RecordMapAccess<'_, '_, R> {
 	type Error = DeError;
@@ -12,10 +19,17 @@ impl<'de, R: ReadSlice<'de>> MapAccess<'de> for RecordMapAccess<'_, '_, R> {
 	where
 		T: DeserializeSeed<'de>,
 	{
-		Ok(match self.record_fields.as_slice().first() {
-			None => None,
-			Some(field) => Some(seed.deserialize(value::StrDeserializer::new(&field.name))?),
-		})
+		while let Some(field) = self.record_fields.as_slice().first() {
+			if self.omit_null_fields && is_null_union_value(self.state, field.schema.as_ref())? {
+				read_discriminant(self.state)?;
+				self.record_fields.next();
+				continue;
+			}
+			return Ok(Some(
+				seed.deserialize(value::StrDeserializer::new(&field.name))?,
+			));
+		}
+		Ok(None)
 	}
 
 	fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
@@ -34,3 +48,61 @@ impl<'de, R: ReadSlice<'de>> MapAccess<'de> for RecordMapAccess<'_, '_, R> {
 		})
 	}
 }
+
+/// Whether the next value to be read is the `null` variant of this union,
+/// without consuming anything
+fn is_null_union_value<'de, R>(
+	state: &mut DeserializerState<'_, R>,
+	schema_node: &SchemaNode<'_>,
+) -> Result<bool, DeError>
+where
+	R: ReadSlice<'de>,
+{
+	let SchemaNode::Union(ref union) = *schema_node else {
+		return Ok(false);
+	};
+	let Some(null_discriminant) = union
+		.variants
+		.iter()
+		.position(|variant| matches!(*variant.as_ref(), SchemaNode::Null))
+	else {
+		return Ok(false);
+	};
+	// Zig-zag encoded on a single byte if it's small enough, which is always
+	// the case in practice as `null` is typically the first variant
+	Ok(match u8::try_from(null_discriminant * 2) {
+		Ok(encoded) if encoded < 0x80 => state.reader.peek_u8()? == Some(encoded),
+		_ => false,
+	})
+}
+
+/// Allows deserializing records as tuples (and tuple structs, tuple
+/// variants...), matching fields by position
+pub(in super::super) struct RecordSeqAccess<'r, 's, R> {
+	pub(in super::super) state: &'r mut DeserializerState<'s, R>,
+	pub(in super::super) record_fields: std::slice::Iter<'s, RecordField<'s>>,
+	pub(in super::super) allowed_depth: AllowedDepth,
+}
+impl<'de, R: ReadSlice<'de>> SeqAccess<'de> for RecordSeqAccess<'_, '_, R> {
+	type Error = DeError;
+
+	fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+	where
+		T: DeserializeSeed<'de>,
+	{
+		match self.record_fields.next() {
+			None => Ok(None),
+			Some(field) => seed
+				.deserialize(DatumDeserializer {
+					schema_node: field.schema.as_ref(),
+					state: self.state,
+					allowed_depth: self.allowed_depth,
+				})
+				.map(Some),
+		}
+	}
+
+	fn size_hint(&self) -> Option<usize> {
+		Some(self.record_fields.len())
+	}
+}
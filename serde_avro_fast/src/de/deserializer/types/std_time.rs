@@ -0,0 +1,77 @@
+//! Deserialization of avro `duration` and `timestamp-*` as
+//! `std::time::Duration` and `std::time::SystemTime` respectively, whose
+//! serde implementations deserialize from structs of seconds and nanoseconds
+
+use super::*;
+
+pub(in super::super) fn read_std_duration<'de, R, V>(
+	state: &mut DeserializerState<'_, R>,
+	visitor: V,
+) -> Result<V::Value, DeError>
+where
+	R: ReadSlice<'de>,
+	V: Visitor<'de>,
+{
+	let buf = state.read_const_size_buf::<12>()?;
+	let [months, days, millis] =
+		[0, 4, 8].map(|start| u32::from_le_bytes(buf[start..start + 4].try_into().unwrap()));
+	if months != 0 {
+		return Err(DeError::new(
+			"Durations with a non-zero number of months can not be deserialized as \
+				std::time::Duration, as months don't have a fixed length",
+		));
+	}
+	visit_secs_and_nanos(
+		visitor,
+		["secs", "nanos"],
+		u64::from(days) * 86_400 + u64::from(millis / 1_000),
+		u64::from(millis % 1_000) * 1_000_000,
+	)
+}
+
+pub(in super::super) fn read_std_system_time<'de, R, V>(
+	state: &mut DeserializerState<'_, R>,
+	schema_node: &SchemaNode<'_>,
+	visitor: V,
+) -> Result<V::Value, DeError>
+where
+	R: ReadSlice<'de>,
+	V: Visitor<'de>,
+{
+	let nanos_per_unit: u64 = match *schema_node {
+		SchemaNode::TimestampMillis => 1_000_000,
+		SchemaNode::TimestampMicros => 1_000,
+		_ => {
+			return Err(DeError::custom(format_args!(
+				"{schema_node:?} is not a timestamp"
+			)))
+		}
+	};
+	let timestamp: i64 = state.read_varint()?;
+	let timestamp = u64::try_from(timestamp).map_err(|_| {
+		DeError::new(
+			"Timestamps before the UNIX epoch can not be deserialized as std::time::SystemTime",
+		)
+	})?;
+	let units_per_sec = 1_000_000_000 / nanos_per_unit;
+	visit_secs_and_nanos(
+		visitor,
+		["secs_since_epoch", "nanos_since_epoch"],
+		timestamp / units_per_sec,
+		timestamp % units_per_sec * nanos_per_unit,
+	)
+}
+
+fn visit_secs_and_nanos<'de, V>(
+	visitor: V,
+	[secs_field, nanos_field]: [&'static str; 2],
+	secs: u64,
+	nanos: u64,
+) -> Result<V::Value, DeError>
+where
+	V: Visitor<'de>,
+{
+	visitor.visit_map(serde::de::value::MapDeserializer::new(
+		[(secs_field, secs), (nanos_field, nanos)].into_iter(),
+	))
+}
@@ -0,0 +1,37 @@
+use super::*;
+
+use crate::temporal::{self, TimeUnit};
+
+/// Read a date, time of day or timestamp, and provide it to the visitor as
+/// the string representation that the serde implementations of `chrono`,
+/// `time` and `jiff` use
+pub(in super::super) fn read_temporal_as_str<'de, R, V>(
+	state: &mut DeserializerState<'_, R>,
+	schema_node: &SchemaNode<'_>,
+	visitor: V,
+) -> Result<V::Value, DeError>
+where
+	R: ReadSlice<'de>,
+	V: Visitor<'de>,
+{
+	let formatted = match *schema_node {
+		SchemaNode::Date => temporal::format_date(state.read_varint()?),
+		SchemaNode::TimeMillis => {
+			temporal::format_time(i64::from(state.read_varint::<i32>()?), TimeUnit::Millis)
+		}
+		SchemaNode::TimeMicros => temporal::format_time(state.read_varint()?, TimeUnit::Micros),
+		SchemaNode::TimestampMillis => {
+			temporal::format_timestamp(state.read_varint()?, TimeUnit::Millis)
+		}
+		SchemaNode::TimestampMicros => {
+			temporal::format_timestamp(state.read_varint()?, TimeUnit::Micros)
+		}
+		_ => {
+			return Err(DeError::custom(format_args!(
+				"{schema_node:?} is not a date, time or timestamp"
+			)))
+		}
+	}
+	.map_err(|e| DeError::custom(format_args!("Failed to format {schema_node:?}: {e}")))?;
+	visitor.visit_string(formatted)
+}
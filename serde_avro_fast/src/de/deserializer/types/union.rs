@@ -18,6 +18,9 @@ pub(in super::super) struct SchemaTypeNameEnumAccess<'r, 's, R> {
 	pub(in super::super) state: &'r mut DeserializerState<'s, R>,
 	pub(in super::super) variant_schema: &'s SchemaNode<'s>,
 	pub(in super::super) allowed_depth: AllowedDepth,
+	/// The variants of the enum being deserialized, as provided by
+	/// `deserialize_enum`
+	pub(in super::super) variants: &'static [&'static str],
 }
 
 impl<'de, 'r, 's, R> EnumAccess<'de> for SchemaTypeNameEnumAccess<'r, 's, R>
@@ -33,6 +36,7 @@ where
 	{
 		seed.deserialize(SchemaTypeNameDeserializer {
 			schema_node: self.variant_schema,
+			variants: self.variants,
 		})
 		.map(|value| {
 			(
@@ -53,6 +57,21 @@ where
 /// inlined so that const propagation will get rid of the string matching
 struct SchemaTypeNameDeserializer<'s> {
 	schema_node: &'s SchemaNode<'s>,
+	variants: &'static [&'static str],
+}
+
+impl<'s> SchemaTypeNameDeserializer<'s> {
+	/// Named types are identified by their fully qualified name, unless the
+	/// enum only has a variant with their short name (e.g. struct variants
+	/// derived as records named `namespace.Enum.Variant`)
+	fn named_type_variant(&self, name: &'s Name) -> &'s str {
+		let fully_qualified_name = name.fully_qualified_name();
+		if !self.variants.contains(&fully_qualified_name) && self.variants.contains(&name.name()) {
+			name.name()
+		} else {
+			fully_qualified_name
+		}
+	}
 }
 
 impl<'de> Deserializer<'de> for SchemaTypeNameDeserializer<'_> {
@@ -77,19 +96,20 @@ impl<'de> Deserializer<'de> for SchemaTypeNameDeserializer<'_> {
 				// Supposedly disallowed but easy to support if we get to this point
 				"Union"
 			}
-			SchemaNode::Record(record) => record.name.fully_qualified_name(),
-			SchemaNode::Enum(enum_) => enum_.name.fully_qualified_name(),
-			SchemaNode::Fixed(fixed) => fixed.name.fully_qualified_name(),
+			SchemaNode::Record(record) => self.named_type_variant(&record.name),
+			SchemaNode::Enum(enum_) => self.named_type_variant(&enum_.name),
+			SchemaNode::Fixed(fixed) => self.named_type_variant(&fixed.name),
 			SchemaNode::Decimal(Decimal {
 				repr: DecimalRepr::Fixed(fixed),
 				..
-			}) => fixed.name.fully_qualified_name(),
+			}) => self.named_type_variant(&fixed.name),
 			SchemaNode::Decimal(Decimal {
 				repr: DecimalRepr::Bytes,
 				..
 			}) => "Decimal",
 			SchemaNode::BigDecimal => "BigDecimal",
-			SchemaNode::Uuid => "Uuid",
+			SchemaNode::Uuid(UuidRepr::Fixed(fixed)) => self.named_type_variant(&fixed.name),
+			SchemaNode::Uuid(UuidRepr::String) => "Uuid",
 			SchemaNode::Date => "Date",
 			SchemaNode::TimeMillis => "TimeMillis",
 			SchemaNode::TimeMicros => "TimeMicros",
@@ -138,13 +158,14 @@ where
 
 	fn struct_variant<V>(
 		self,
-		_fields: &'static [&'static str],
+		fields: &'static [&'static str],
 		visitor: V,
 	) -> Result<V::Value, Self::Error>
 	where
 		V: Visitor<'de>,
 	{
-		self.datum_deserializer.deserialize_map(visitor)
+		self.datum_deserializer
+			.deserialize_struct("", fields, visitor)
 	}
 }
 
@@ -174,7 +195,7 @@ impl<'de, R: ReadSlice<'de>> Deserializer<'de>
 	fn deserialize_enum<V>(
 		self,
 		_name: &'static str,
-		_variants: &'static [&'static str],
+		variants: &'static [&'static str],
 		visitor: V,
 	) -> Result<V::Value, Self::Error>
 	where
@@ -184,6 +205,7 @@ impl<'de, R: ReadSlice<'de>> Deserializer<'de>
 			state: self.inner.state,
 			variant_schema: self.inner.schema_node,
 			allowed_depth: self.inner.allowed_depth,
+			variants,
 		})
 	}
 
@@ -0,0 +1,19 @@
+use super::*;
+
+/// Read a uuid encoded as `fixed(16)`, and provide it to the visitor as its
+/// hyphenated string representation, which is what `uuid::Uuid` (and the
+/// `string` representation of the uuid logical type) use
+pub(in super::super) fn read_uuid_fixed_as_str<'de, R, V>(
+	state: &mut DeserializerState<'_, R>,
+	visitor: V,
+) -> Result<V::Value, DeError>
+where
+	R: ReadSlice<'de>,
+	V: Visitor<'de>,
+{
+	let uuid = ::uuid::Uuid::from_bytes(state.read_const_size_buf::<16>()?);
+	visitor.visit_str(
+		uuid.hyphenated()
+			.encode_lower(&mut ::uuid::Uuid::encode_buffer()),
+	)
+}
@@ -30,6 +30,10 @@ pub trait Read: std::io::Read + Sized + private::Sealed {
 		self.read_exact(&mut buf).map_err(DeError::io)?;
 		Ok(buf)
 	}
+	/// Look at the next byte of the underlying buffer without consuming it
+	///
+	/// Returns `None` at EOF.
+	fn peek_u8(&mut self) -> Result<Option<u8>, DeError>;
 	/// Skip `n_bytes` bytes from the underlying buffer
 	fn skip_bytes(&mut self, n_bytes: u64) -> Result<(), DeError> {
 		let written = std::io::copy(
@@ -65,13 +69,30 @@ mod private {
 }
 
 /// Implements `Read<'de>` reading from `&'de [u8]`
+#[derive(Clone)]
 pub struct SliceRead<'de> {
 	slice: &'de [u8],
+	/// Length of the slice this was originally constructed from, which
+	/// allows computing how far we are into it
+	input_len: usize,
 }
 impl<'de> SliceRead<'de> {
 	/// Construct a `SliceRead` from a `&'de [u8]`
 	pub fn new(slice: &'de [u8]) -> Self {
-		Self { slice }
+		Self {
+			slice,
+			input_len: slice.len(),
+		}
+	}
+
+	/// The part of the original slice that has not been read yet
+	pub(crate) fn remaining(&self) -> &'de [u8] {
+		self.slice
+	}
+
+	/// How many bytes of the original slice have been read so far
+	pub(crate) fn position(&self) -> usize {
+		self.input_len - self.slice.len()
 	}
 }
 impl private::Sealed for SliceRead<'_> {}
@@ -90,6 +111,9 @@ impl<'de> Read for SliceRead<'de> {
 			}
 		}
 	}
+	fn peek_u8(&mut self) -> Result<Option<u8>, DeError> {
+		Ok(self.slice.first().copied())
+	}
 	fn skip_bytes(&mut self, n_bytes: u64) -> Result<(), DeError> {
 		let n_bytes: usize = n_bytes
 			.try_into()
@@ -168,6 +192,10 @@ impl<R> ReaderRead<R> {
 	pub fn into_inner(self) -> R {
 		self.reader
 	}
+
+	pub(crate) fn inner_mut(&mut self) -> &mut R {
+		&mut self.reader
+	}
 }
 impl<R: std::io::BufRead> Read for ReaderRead<R> {
 	fn read_varint<I>(&mut self) -> Result<I, DeError>
@@ -187,6 +215,10 @@ impl<R: std::io::BufRead> Read for ReaderRead<R> {
 			}
 		}
 	}
+	fn peek_u8(&mut self) -> Result<Option<u8>, DeError> {
+		use std::io::BufRead;
+		Ok(self.fill_buf().map_err(DeError::io)?.first().copied())
+	}
 }
 impl<'de, R: std::io::BufRead> ReadSlice<'de> for ReaderRead<R> {
 	fn read_slice<V>(&mut self, n: usize, read_visitor: V) -> Result<V::Value, DeError>
@@ -38,7 +38,10 @@ impl<'de> Take for SliceRead<'de> {
 		let (start, end) = self.slice.split_at(block_size);
 		Ok(SliceReadTake {
 			inner_slice_read: SliceRead::new(start),
-			left_after_take: SliceRead::new(end),
+			left_after_take: SliceRead {
+				slice: end,
+				input_len: self.input_len,
+			},
 		})
 	}
 }
@@ -62,6 +65,10 @@ impl Read for SliceReadTake<'_> {
 	fn read_const_size_buf<const N: usize>(&mut self) -> Result<[u8; N], DeError> {
 		self.inner_slice_read.read_const_size_buf()
 	}
+
+	fn peek_u8(&mut self) -> Result<Option<u8>, DeError> {
+		self.inner_slice_read.peek_u8()
+	}
 }
 impl<'de> ReadSlice<'de> for SliceReadTake<'de> {
 	fn read_slice<V>(&mut self, n: usize, read_visitor: V) -> Result<V::Value, DeError>
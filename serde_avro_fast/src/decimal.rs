@@ -0,0 +1,254 @@
+//! Exact conversions between decimal strings and the unscaled integer + scale
+//! representation of avro decimals
+//!
+//! `rust_decimal` is used whenever it can represent the number, but it is
+//! limited to a 96 bit mantissa (28 digits) and a scale of 28, whereas e.g.
+//! databases commonly use a precision of 38. The functions of this module
+//! handle any number that fits in an `i128` without additional dependencies,
+//! and any number at all when either the `num-bigint` or `bigdecimal` feature
+//! is enabled.
+
+#[cfg(feature = "num-bigint")]
+use num_bigint as bigint;
+
+#[cfg(all(feature = "bigdecimal", not(feature = "num-bigint")))]
+use bigdecimal::num_bigint as bigint;
+
+/// Maximum number of decimal digits that always fit in an `i128`
+const I128_MAX_DIGITS: usize = 38;
+
+/// Protects against e.g. `1E+999999999` allocating a gigabyte of zeroes
+const MAX_DIGITS: usize = 4096;
+
+/// The unscaled integer of a decimal
+pub(crate) enum Unscaled {
+	I128(i128),
+	/// Big-endian two's complement, for numbers that may not fit in an `i128`
+	Big(Vec<u8>),
+}
+
+impl Unscaled {
+	/// From the big-endian two's complement representation of the number, which
+	/// may have more than 16 bytes (e.g. `fixed(17)`) even if it fits in an
+	/// `i128`
+	pub(crate) fn from_be_bytes(mut bytes: Vec<u8>) -> Self {
+		// Drop the leading bytes that only extend the sign
+		let redundant = bytes
+			.windows(2)
+			.take_while(|w| matches!((w[0], w[1] & 0x80), (0, 0) | (0xFF, 0x80)))
+			.count()
+			.min(bytes.len().saturating_sub(16));
+		bytes.drain(..redundant);
+		if bytes.len() <= 16 {
+			let mut buf = if bytes.first().is_some_and(|&b| b & 0x80 != 0) {
+				[0xFF; 16]
+			} else {
+				[0; 16]
+			};
+			buf[16 - bytes.len()..].copy_from_slice(&bytes);
+			Unscaled::I128(i128::from_be_bytes(buf))
+		} else {
+			Unscaled::Big(bytes)
+		}
+	}
+
+	/// The number as a `u128`, if it fits (e.g. `u128::MAX`, which doesn't fit
+	/// in an `i128`)
+	pub(crate) fn to_u128(&self) -> Option<u128> {
+		match *self {
+			Unscaled::I128(n) => n.try_into().ok(),
+			Unscaled::Big(ref bytes) => {
+				if bytes.first().is_some_and(|&b| b & 0x80 != 0) {
+					return None;
+				}
+				let significant = &bytes[bytes.iter().take_while(|&&b| b == 0).count()..];
+				let mut buf = [0; 16];
+				buf.get_mut(16_usize.checked_sub(significant.len())?..)?
+					.copy_from_slice(significant);
+				Some(u128::from_be_bytes(buf))
+			}
+		}
+	}
+
+	/// Big-endian two's complement representation of the number
+	pub(crate) fn with_be_bytes<T>(&self, f: impl FnOnce(&[u8]) -> T) -> T {
+		match *self {
+			Unscaled::I128(n) => f(&n.to_be_bytes()),
+			Unscaled::Big(ref bytes) => f(bytes),
+		}
+	}
+}
+
+/// Parse a decimal string (`-123.45`, `1.2345E+2`...) into its unscaled
+/// integer and scale
+///
+/// If `target_scale` is specified, the number is rescaled to it. If that would
+/// require dropping non-zero digits, this errors instead of rounding.
+///
+/// If `target_scale` is not specified, the scale is that of the string
+/// representation, unless it would be negative, in which case it's `0`.
+pub(crate) fn parse(s: &str, target_scale: Option<u32>) -> Result<(Unscaled, u32), String> {
+	let invalid = || format!("Invalid decimal number: {s:?}");
+
+	let (negative, unsigned) = match s.as_bytes().first() {
+		Some(b'-') => (true, &s[1..]),
+		Some(b'+') => (false, &s[1..]),
+		_ => (false, s),
+	};
+	let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+		Some(e_idx) => (
+			&unsigned[..e_idx],
+			unsigned[e_idx + 1..]
+				.parse::<i64>()
+				.map_err(|_| invalid())?,
+		),
+		None => (unsigned, 0),
+	};
+	let (integer_part, fractional_part) = match mantissa.split_once('.') {
+		Some((integer_part, fractional_part)) => (integer_part, fractional_part),
+		None => (mantissa, ""),
+	};
+	if (integer_part.is_empty() && fractional_part.is_empty())
+		|| !integer_part
+			.bytes()
+			.chain(fractional_part.bytes())
+			.all(|b| b.is_ascii_digit())
+	{
+		return Err(invalid());
+	}
+
+	let mut digits: String = integer_part
+		.chars()
+		.chain(fractional_part.chars())
+		.collect();
+	let scale = i64::try_from(fractional_part.len())
+		.ok()
+		.and_then(|len| len.checked_sub(exponent))
+		.ok_or_else(invalid)?;
+	let target_scale = match target_scale {
+		Some(target_scale) => i64::from(target_scale),
+		None => scale.max(0),
+	};
+	if target_scale >= scale {
+		let n_zeroes = usize::try_from(target_scale - scale)
+			.ok()
+			.filter(|&n_zeroes| n_zeroes <= MAX_DIGITS)
+			.ok_or_else(|| format!("Decimal number {s:?} has too many digits"))?;
+		digits.extend(std::iter::repeat_n('0', n_zeroes));
+	} else {
+		let n_dropped = usize::try_from(scale - target_scale)
+			.unwrap_or(usize::MAX)
+			.min(digits.len());
+		if digits[digits.len() - n_dropped..]
+			.bytes()
+			.any(|b| b != b'0')
+		{
+			return Err(format!(
+				"Decimal number {s:?} can not be represented with scale {target_scale} \
+					without losing precision"
+			));
+		}
+		digits.truncate(digits.len() - n_dropped);
+	}
+	let target_scale = u32::try_from(target_scale).map_err(|_| invalid())?;
+
+	let digits = digits.trim_start_matches('0');
+	let unscaled = if digits.len() <= I128_MAX_DIGITS {
+		let magnitude: i128 = if digits.is_empty() {
+			0
+		} else {
+			digits.parse().map_err(|_| invalid())?
+		};
+		Unscaled::I128(if negative { -magnitude } else { magnitude })
+	} else {
+		Unscaled::Big(parse_big(negative, digits)?)
+	};
+	Ok((unscaled, target_scale))
+}
+
+/// Format a decimal from its unscaled integer (big-endian two's complement)
+/// and scale
+pub(crate) fn format(unscaled: &[u8], scale: u32) -> Result<String, String> {
+	if unscaled.len() <= 16 {
+		let mut buf = if unscaled.first().is_some_and(|&b| b & 0x80 != 0) {
+			[0xFF; 16]
+		} else {
+			[0; 16]
+		};
+		buf[16 - unscaled.len()..].copy_from_slice(unscaled);
+		Ok(format_i128(i128::from_be_bytes(buf), scale))
+	} else {
+		format_big(unscaled, scale)
+	}
+}
+
+/// Format a decimal from its unscaled integer and scale
+pub(crate) fn format_i128(unscaled: i128, scale: u32) -> String {
+	insert_decimal_point(unscaled < 0, &unscaled.unsigned_abs().to_string(), scale)
+}
+
+fn insert_decimal_point(negative: bool, digits: &str, scale: u32) -> String {
+	let scale = usize::try_from(scale).unwrap_or(usize::MAX);
+	let mut formatted = String::with_capacity(digits.len().max(scale) + 3);
+	if negative {
+		formatted.push('-');
+	}
+	match digits.len().checked_sub(scale) {
+		Some(0) | None => {
+			formatted.push('0');
+			if scale > 0 {
+				formatted.push('.');
+				formatted.extend(std::iter::repeat_n('0', scale - digits.len()));
+				formatted.push_str(digits);
+			}
+		}
+		Some(integer_len) => {
+			formatted.push_str(&digits[..integer_len]);
+			if scale > 0 {
+				formatted.push('.');
+				formatted.push_str(&digits[integer_len..]);
+			}
+		}
+	}
+	formatted
+}
+
+#[cfg(any(feature = "num-bigint", feature = "bigdecimal"))]
+fn parse_big(negative: bool, digits: &str) -> Result<Vec<u8>, String> {
+	let magnitude: bigint::BigInt = digits.parse().map_err(|e| format!("{e}"))?;
+	Ok(if negative { -magnitude } else { magnitude }.to_signed_bytes_be())
+}
+
+#[cfg(any(feature = "num-bigint", feature = "bigdecimal"))]
+fn format_big(unscaled: &[u8], scale: u32) -> Result<String, String> {
+	let unscaled = bigint::BigInt::from_signed_bytes_be(unscaled);
+	Ok(insert_decimal_point(
+		unscaled.sign() == bigint::Sign::Minus,
+		&unscaled.magnitude().to_string(),
+		scale,
+	))
+}
+
+#[cfg(not(any(feature = "num-bigint", feature = "bigdecimal")))]
+fn parse_big(_negative: bool, digits: &str) -> Result<Vec<u8>, String> {
+	Err(big_requires_feature(format_args!(
+		"{} digits",
+		digits.len()
+	)))
+}
+
+#[cfg(not(any(feature = "num-bigint", feature = "bigdecimal")))]
+fn format_big(unscaled: &[u8], _scale: u32) -> Result<String, String> {
+	Err(big_requires_feature(format_args!(
+		"{} bytes",
+		unscaled.len()
+	)))
+}
+
+#[cfg(not(any(feature = "num-bigint", feature = "bigdecimal")))]
+fn big_requires_feature(size: std::fmt::Arguments<'_>) -> String {
+	format!(
+		"Decimals whose unscaled integer does not fit in an i128 (got {size}) \
+			require the `num-bigint` or `bigdecimal` feature"
+	)
+}
@@ -143,6 +143,11 @@ pub use single_object_encoding::{
 
 pub mod object_container_file_encoding;
 
+mod decimal;
+
+#[cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+mod temporal;
+
 /// Deserialize from an avro "datum" (raw data, no headers...) slice
 ///
 /// This is zero-alloc.
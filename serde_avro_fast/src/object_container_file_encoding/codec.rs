@@ -0,0 +1,215 @@
+use super::Compression;
+
+use crate::{de::DeError, ser::SerError};
+
+use std::sync::{Arc, LazyLock};
+
+/// A compression codec that is not built into this crate
+///
+/// To write files with such a codec, use [`Compression::Custom`]. To read
+/// them, register the codec in a [`CodecRegistry`] that is provided when
+/// building the [`Reader`](super::Reader) (e.g.
+/// [`Reader::new_with_codecs`](super::Reader::new_with_codecs)).
+///
+/// # Example
+/// ```
+/// use serde_avro_fast::object_container_file_encoding::{
+/// 	Codec, CodecRegistry, Compression, CustomCodec, Reader, WriterBuilder,
+/// };
+///
+/// /// Stores blocks reversed
+/// struct Reverse;
+/// impl Codec for Reverse {
+/// 	fn name(&self) -> &'static str {
+/// 		"reverse"
+/// 	}
+/// 	fn compress(&self, uncompressed: &[u8], compressed: &mut Vec<u8>) -> std::io::Result<()> {
+/// 		compressed.extend(uncompressed.iter().rev());
+/// 		Ok(())
+/// 	}
+/// 	fn decompress(&self, compressed: &[u8], decompressed: &mut Vec<u8>) -> std::io::Result<()> {
+/// 		decompressed.extend(compressed.iter().rev());
+/// 		Ok(())
+/// 	}
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let schema: serde_avro_fast::Schema = r#""string""#.parse()?;
+/// let mut serializer_config = serde_avro_fast::ser::SerializerConfig::new(&schema);
+/// let mut writer = WriterBuilder::new(&mut serializer_config)
+/// 	.compression(Compression::Custom(CustomCodec::new(Reverse)))
+/// 	.build(Vec::new())?;
+/// writer.serialize_all(["foo", "bar"])?;
+/// let file = writer.into_inner()?;
+///
+/// let codecs = CodecRegistry::new().register(Reverse);
+/// let mut reader = Reader::new_with_codecs(
+/// 	serde_avro_fast::de::read::SliceRead::new(&file),
+/// 	&codecs,
+/// )?;
+/// assert_eq!(reader.metadata().codec(), Some("reverse"));
+/// let decoded = reader.deserialize().collect::<Result<Vec<String>, _>>()?;
+/// assert_eq!(decoded, ["foo", "bar"]);
+/// # Ok(())
+/// # }
+/// ```
+pub trait Codec: Send + Sync {
+	/// The name of the codec, as written in the `avro.codec` entry of the
+	/// header
+	///
+	/// This can't be the name of a codec of the Avro specification or of a
+	/// codec built into this crate (whether or not the corresponding feature is
+	/// enabled), nor start with `avro.`, as readers would otherwise decode the
+	/// file with another codec. Writers error when built with such a codec.
+	fn name(&self) -> &'static str;
+
+	/// Compress a whole block
+	///
+	/// `compressed` is empty when this is called.
+	fn compress(&self, uncompressed: &[u8], compressed: &mut Vec<u8>) -> std::io::Result<()>;
+
+	/// Decompress a whole block
+	///
+	/// `decompressed` is empty when this is called.
+	fn decompress(&self, compressed: &[u8], decompressed: &mut Vec<u8>) -> std::io::Result<()>;
+}
+
+/// A [`Codec`] that can be used as [`Compression::Custom`]
+///
+/// Two `CustomCodec`s are considered equal if their [`name`](Codec::name) is
+/// the same.
+///
+/// This is cheap to clone.
+#[derive(Clone)]
+pub struct CustomCodec {
+	codec: Arc<dyn Codec>,
+}
+
+impl CustomCodec {
+	/// Wrap a [`Codec`]
+	pub fn new(codec: impl Codec + 'static) -> Self {
+		Self::from_arc(Arc::new(codec))
+	}
+
+	/// Wrap a [`Codec`] that is already shared
+	pub fn from_arc(codec: Arc<dyn Codec>) -> Self {
+		Self { codec }
+	}
+
+	/// The name of the codec, as written in the `avro.codec` entry of the
+	/// header
+	pub fn name(&self) -> &'static str {
+		self.codec.name()
+	}
+
+	/// The underlying codec
+	pub fn codec(&self) -> &dyn Codec {
+		&*self.codec
+	}
+
+	/// Check that files written with this codec will be decoded with it
+	///
+	/// See [`Codec::name`].
+	pub(super) fn check_name(&self) -> Result<(), SerError> {
+		let name = self.name();
+		if RESERVED_NAMES.contains(&name) || name.starts_with("avro.") {
+			Err(<SerError as serde::ser::Error>::custom(format_args!(
+				"Custom codec can't be named {name:?}, as that name is reserved for the codecs \
+					of the Avro specification or built into serde_avro_fast"
+			)))
+		} else {
+			Ok(())
+		}
+	}
+}
+
+/// Names of the codecs of the Avro specification and of those built into this
+/// crate, regardless of enabled features
+const RESERVED_NAMES: &[&str] = &[
+	"null",
+	"deflate",
+	"bzip2",
+	"snappy",
+	"xz",
+	"zstandard",
+	"lz4",
+];
+
+impl PartialEq for CustomCodec {
+	fn eq(&self, other: &Self) -> bool {
+		self.name() == other.name()
+	}
+}
+impl Eq for CustomCodec {}
+impl std::hash::Hash for CustomCodec {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		self.name().hash(state);
+	}
+}
+impl std::fmt::Debug for CustomCodec {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_tuple("CustomCodec").field(&self.name()).finish()
+	}
+}
+
+/// Set of [`Codec`]s that codecs found in the header of object container
+/// files are resolved against, when they are not built into this crate
+///
+/// Codecs built into this crate (including those enabled by features) take
+/// precedence over registered ones with the same name.
+///
+/// See [`Codec`] for an example.
+#[derive(Clone, Debug, Default)]
+pub struct CodecRegistry {
+	codecs: Vec<CustomCodec>,
+}
+
+impl CodecRegistry {
+	/// Registry with no codecs
+	pub const fn new() -> Self {
+		Self { codecs: Vec::new() }
+	}
+
+	/// Register a codec
+	///
+	/// If a codec with the same [`name`](Codec::name) was already registered,
+	/// it is replaced.
+	pub fn register(self, codec: impl Codec + 'static) -> Self {
+		self.register_custom(CustomCodec::new(codec))
+	}
+
+	pub(super) fn register_custom(mut self, codec: CustomCodec) -> Self {
+		self.codecs.retain(|registered| registered != &codec);
+		self.codecs.push(codec);
+		self
+	}
+
+	/// Get the registered codec with the given name
+	pub fn get(&self, name: &str) -> Option<&CustomCodec> {
+		self.codecs.iter().find(|codec| codec.name() == name)
+	}
+
+	/// The [`Compression`] that blocks of a file are compressed with, given
+	/// the `avro.codec` entry of its header
+	pub(super) fn resolve(&self, name: &str) -> Result<Compression, DeError> {
+		let builtin = super::BUILTIN_COMPRESSIONS
+			.iter()
+			.find(|compression| compression.codec().name() == name);
+		match (builtin, self.get(name)) {
+			(Some(compression), _) => Ok(compression.clone()),
+			(None, Some(codec)) => Ok(Compression::Custom(codec.clone())),
+			(None, None) => {
+				static BUILTIN_CODEC_NAMES: LazyLock<Vec<&str>> = LazyLock::new(|| {
+					super::BUILTIN_COMPRESSIONS
+						.iter()
+						.map(|compression| compression.codec().name())
+						.collect()
+				});
+				Err(<DeError as serde::de::Error>::unknown_variant(
+					name,
+					&BUILTIN_CODEC_NAMES,
+				))
+			}
+		}
+	}
+}
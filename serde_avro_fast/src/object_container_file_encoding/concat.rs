@@ -0,0 +1,147 @@
+use crate::{
+	de::{
+		self,
+		read::{Read, ReadSlice},
+		DeError,
+	},
+	object_container_file_encoding::{RawUserMetadata, Reader, WriterBuilder},
+	ser::{SerError, SerializerConfig},
+};
+
+/// Concatenate [object container files](https://avro.apache.org/docs/current/specification/#object-container-files)
+/// without deserializing their records
+///
+/// The output file uses the schema and compression codec of the first input
+/// (with the default compression level). Blocks of inputs that use the same
+/// codec (and the same zstandard dictionary, if any) are copied verbatim, only
+/// their sync marker is rewritten. Blocks of inputs that use a different codec
+/// are decompressed then compressed again.
+///
+/// All inputs are required to have the same schema as the first one, as
+/// determined by their
+/// [`rabin_fingerprint`](crate::Schema::rabin_fingerprint). The user metadata
+/// of the first input is carried over to the output as-is, while that of the
+/// other inputs is not.
+///
+/// Blocks that need to be decompressed are subject to the
+/// [`max_decompressed_block_size`](Reader::set_max_decompressed_block_size) of
+/// their input.
+///
+/// # Example
+/// ```
+/// use serde_avro_fast::object_container_file_encoding::{self, Compression, Reader};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let schema: serde_avro_fast::Schema = r#""long""#.parse()?;
+///
+/// let file_1 = object_container_file_encoding::write_all(
+/// 	&schema,
+/// 	Compression::Null,
+/// 	Vec::new(),
+/// 	[1, 2],
+/// )?;
+/// let file_2 = object_container_file_encoding::write_all(
+/// 	&schema,
+/// 	Compression::Null,
+/// 	Vec::new(),
+/// 	[3],
+/// )?;
+///
+/// let concatenated = object_container_file_encoding::concat(
+/// 	[Reader::from_slice(&file_1)?, Reader::from_slice(&file_2)?],
+/// 	Vec::new(),
+/// )?;
+///
+/// let decoded = Reader::from_slice(&concatenated)?
+/// 	.deserialize()
+/// 	.collect::<Result<Vec<i64>, _>>()?;
+/// assert_eq!(decoded, [1, 2, 3]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn concat<'de, R, W>(
+	inputs: impl IntoIterator<Item = Reader<R>>,
+	output: W,
+) -> Result<W, ConcatError>
+where
+	R: Read + ReadSlice<'de> + de::read::take::Take + std::io::BufRead,
+	<R as de::read::take::Take>::Take: ReadSlice<'de> + std::io::BufRead,
+	W: std::io::Write,
+{
+	let mut inputs = inputs.into_iter();
+	let first_input = inputs.next().ok_or(ConcatError::NoInputs)?;
+	let schema = first_input.schema().clone();
+	let compression = first_input.compression().clone();
+	let user_metadata = first_input
+		.metadata()
+		.user_metadata::<RawUserMetadata>()
+		.map_err(|de_error| ConcatError::Read {
+			input_index: 0,
+			de_error,
+		})?;
+
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.compression(compression.clone())
+		.build_with_user_metadata(output, user_metadata)
+		.map_err(ConcatError::Write)?;
+
+	let mut decompression_buffer = Vec::new();
+	for (input_index, mut input) in std::iter::once(first_input).chain(inputs).enumerate() {
+		if input.schema().rabin_fingerprint() != schema.rabin_fingerprint() {
+			return Err(ConcatError::IncompatibleSchema { input_index });
+		}
+		let read_error = |de_error| ConcatError::Read {
+			input_index,
+			de_error,
+		};
+		let input_compression = input.compression().clone();
+		while let Some(raw_block) = input.next_raw_block().map_err(read_error)? {
+			if input_compression == compression {
+				writer.push_raw_block(&raw_block)
+			} else {
+				let decompressed = input_compression
+					.decompress_block(
+						&raw_block.compressed_bytes,
+						&mut decompression_buffer,
+						input.max_decompressed_block_size,
+					)
+					.map_err(read_error)?;
+				writer.push_serialized(decompressed, raw_block.n_objects)
+			}
+			.map_err(ConcatError::Write)?;
+		}
+	}
+
+	writer.into_inner().map_err(ConcatError::Write)
+}
+
+/// Errors that may happen when attempting to [`concat`](concat()) object container files
+#[derive(Debug, thiserror::Error)]
+pub enum ConcatError {
+	/// There needs to be at least one input to know the schema of the output
+	#[error("No input object container file to concatenate")]
+	NoInputs,
+	/// The schema of an input does not have the same
+	/// [`rabin_fingerprint`](crate::Schema::rabin_fingerprint) as that of the
+	/// first input
+	#[error(
+		"Schema of input object container file #{input_index} is not the same as that of the \
+			first input"
+	)]
+	IncompatibleSchema {
+		/// The index of the offending input, in the order they were provided
+		input_index: usize,
+	},
+	/// Failed to read a block from an input
+	#[error("Failed to read input object container file #{input_index}: {de_error}")]
+	Read {
+		/// The index of the offending input, in the order they were provided
+		input_index: usize,
+		/// The underlying error
+		de_error: DeError,
+	},
+	/// Failed to write to the output
+	#[error("Failed to write concatenated object container file: {}", _0)]
+	Write(SerError),
+}
@@ -0,0 +1,284 @@
+use crate::de::DeError;
+
+use {
+	serde::{
+		de::{IntoDeserializer, Visitor},
+		Deserialize,
+	},
+	std::borrow::Cow,
+};
+
+/// The metadata of the header of an object container file
+///
+/// This gives raw access to all the entries of the header, in the order they
+/// were written: those reserved by avro (`avro.schema`, `avro.codec`...) as
+/// well as user-specified ones, including entries this crate does not
+/// interpret.
+///
+/// Obtained via [`Reader::metadata`](super::Reader::metadata).
+///
+/// # Example
+/// ```
+/// use serde_avro_fast::object_container_file_encoding::{Reader, WriterBuilder};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let schema: serde_avro_fast::Schema = r#""long""#.parse()?;
+/// let mut serializer_config = serde_avro_fast::ser::SerializerConfig::new(&schema);
+/// let mut writer = WriterBuilder::new(&mut serializer_config).build_with_user_metadata(
+/// 	Vec::new(),
+/// 	std::collections::HashMap::from([("created_by", "me")]),
+/// )?;
+/// writer.serialize(1)?;
+/// let file = writer.into_inner()?;
+///
+/// let reader = Reader::from_slice(&file)?;
+/// let metadata = reader.metadata();
+/// assert_eq!(metadata.codec(), Some("null"));
+/// assert_eq!(metadata.schema_json(), Some(r#""long""#));
+/// assert_eq!(metadata.get_str("created_by"), Some("me"));
+/// assert_eq!(
+/// 	metadata.user_entries().collect::<Vec<_>>(),
+/// 	[("created_by", b"me".as_slice())]
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Metadata {
+	entries: Vec<(String, Vec<u8>)>,
+}
+
+impl Metadata {
+	/// Get the raw value of the entry with the given key
+	pub fn get(&self, key: &str) -> Option<&[u8]> {
+		self.entries
+			.iter()
+			.find(|(entry_key, _)| entry_key == key)
+			.map(|(_, value)| value.as_slice())
+	}
+
+	/// Get the value of the entry with the given key as a string
+	///
+	/// Returns `None` if there is no such entry or if its value is not valid
+	/// UTF-8.
+	pub fn get_str(&self, key: &str) -> Option<&str> {
+		self.get(key)
+			.and_then(|value| std::str::from_utf8(value).ok())
+	}
+
+	/// The schema JSON, as written in the `avro.schema` entry
+	///
+	/// Note that this is not minified, contrary to
+	/// [`Schema::json`](crate::Schema::json).
+	pub fn schema_json(&self) -> Option<&str> {
+		self.get_str("avro.schema")
+	}
+
+	/// The name of the codec, as written in the `avro.codec` entry
+	///
+	/// See also [`Reader::compression_codec`](super::Reader::compression_codec).
+	pub fn codec(&self) -> Option<&str> {
+		self.get_str("avro.codec")
+	}
+
+	/// Iterate over all the entries, in the order they were written
+	pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> {
+		self.entries
+			.iter()
+			.map(|(key, value)| (key.as_str(), value.as_slice()))
+	}
+
+	/// Iterate over the entries whose key does not start with `avro.`, that
+	/// is, those that are not reserved by the specification
+	pub fn user_entries(&self) -> impl Iterator<Item = (&str, &[u8])> {
+		self.iter().filter(|(key, _)| !key.starts_with("avro."))
+	}
+
+	/// Deserialize the user metadata into `M`
+	///
+	/// This behaves like the metadata returned by
+	/// [`Reader::new_and_metadata`](super::Reader::new_and_metadata), but may
+	/// be called after the `Reader` is built, possibly with different types.
+	pub fn user_metadata<'a, M: Deserialize<'a>>(&'a self) -> Result<M, DeError> {
+		deserialize_header_metadata::<M>(
+			self.entries
+				.iter()
+				.map(|(key, value)| (Cow::Borrowed(key.as_str()), Cow::Borrowed(value.as_slice()))),
+		)
+		.map(|header_metadata| header_metadata.user_metadata)
+	}
+}
+
+/// Read the entries of the metadata map of the header, borrowing from the
+/// input if possible, then deserialize the avro-reserved entries and the user
+/// metadata from them
+pub(super) fn read_metadata<'de, M, D>(
+	deserializer: D,
+) -> Result<(Metadata, super::HeaderMetadata<String, M>), DeError>
+where
+	M: Deserialize<'de>,
+	D: serde::Deserializer<'de, Error = DeError>,
+{
+	let HeaderEntries(entries) = HeaderEntries::deserialize(deserializer)?;
+	let header_metadata = deserialize_header_metadata(
+		entries
+			.iter()
+			.map(|(MaybeBorrowed(key), MaybeBorrowed(value))| (key.clone(), value.clone())),
+	)?;
+	let metadata = Metadata {
+		entries: entries
+			.into_iter()
+			.map(|(MaybeBorrowed(key), MaybeBorrowed(value))| {
+				(key.into_owned(), value.into_owned())
+			})
+			.collect(),
+	};
+	Ok((metadata, header_metadata))
+}
+
+fn deserialize_header_metadata<'de, M: Deserialize<'de>>(
+	entries: impl Iterator<Item = (Cow<'de, str>, Cow<'de, [u8]>)>,
+) -> Result<super::HeaderMetadata<String, M>, DeError> {
+	super::HeaderMetadata::deserialize(serde::de::value::MapDeserializer::new(
+		entries.map(|(key, value)| (MaybeBorrowed(key), MaybeBorrowed(value))),
+	))
+}
+
+/// All the entries of the metadata map, in order
+struct HeaderEntries<'de>(Vec<(MaybeBorrowed<'de, str>, MaybeBorrowed<'de, [u8]>)>);
+
+impl<'de> Deserialize<'de> for HeaderEntries<'de> {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct HeaderEntriesVisitor;
+		impl<'de> Visitor<'de> for HeaderEntriesVisitor {
+			type Value = HeaderEntries<'de>;
+			fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				formatter.write_str("a map of bytes")
+			}
+			fn visit_map<A: serde::de::MapAccess<'de>>(
+				self,
+				mut map: A,
+			) -> Result<Self::Value, A::Error> {
+				let mut entries = Vec::new();
+				while let Some(entry) = map.next_entry()? {
+					entries.push(entry);
+				}
+				Ok(HeaderEntries(entries))
+			}
+		}
+		deserializer.deserialize_map(HeaderEntriesVisitor)
+	}
+}
+
+/// Key or value of the metadata map, that borrows from the input if possible
+///
+/// This also serves as the deserializer that these are fed back into when
+/// deserializing the user metadata, so that it may also borrow from the input.
+struct MaybeBorrowed<'de, T: ?Sized + ToOwned>(Cow<'de, T>);
+
+impl<'de> Deserialize<'de> for MaybeBorrowed<'de, str> {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct StrVisitor;
+		impl<'de> Visitor<'de> for StrVisitor {
+			type Value = MaybeBorrowed<'de, str>;
+			fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				formatter.write_str("a string")
+			}
+			fn visit_borrowed_str<E: serde::de::Error>(
+				self,
+				v: &'de str,
+			) -> Result<Self::Value, E> {
+				Ok(MaybeBorrowed(Cow::Borrowed(v)))
+			}
+			fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+				Ok(MaybeBorrowed(Cow::Owned(v.to_owned())))
+			}
+			fn visit_string<E: serde::de::Error>(self, v: String) -> Result<Self::Value, E> {
+				Ok(MaybeBorrowed(Cow::Owned(v)))
+			}
+		}
+		deserializer.deserialize_str(StrVisitor)
+	}
+}
+
+impl<'de> Deserialize<'de> for MaybeBorrowed<'de, [u8]> {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct BytesVisitor;
+		impl<'de> Visitor<'de> for BytesVisitor {
+			type Value = MaybeBorrowed<'de, [u8]>;
+			fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				formatter.write_str("bytes")
+			}
+			fn visit_borrowed_bytes<E: serde::de::Error>(
+				self,
+				v: &'de [u8],
+			) -> Result<Self::Value, E> {
+				Ok(MaybeBorrowed(Cow::Borrowed(v)))
+			}
+			fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+				Ok(MaybeBorrowed(Cow::Owned(v.to_owned())))
+			}
+			fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+				Ok(MaybeBorrowed(Cow::Owned(v)))
+			}
+		}
+		deserializer.deserialize_bytes(BytesVisitor)
+	}
+}
+
+impl<'de> serde::Deserializer<'de> for MaybeBorrowed<'de, str> {
+	type Error = DeError;
+	fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+		match self.0 {
+			Cow::Borrowed(v) => visitor.visit_borrowed_str(v),
+			Cow::Owned(v) => visitor.visit_string(v),
+		}
+	}
+	serde::forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+		bytes byte_buf option unit unit_struct newtype_struct seq tuple
+		tuple_struct map struct enum identifier ignored_any
+	}
+}
+
+impl<'de> serde::Deserializer<'de> for MaybeBorrowed<'de, [u8]> {
+	type Error = DeError;
+	fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+		match self.0 {
+			Cow::Borrowed(v) => visitor.visit_borrowed_bytes(v),
+			Cow::Owned(v) => visitor.visit_byte_buf(v),
+		}
+	}
+	fn deserialize_enum<V: Visitor<'de>>(
+		self,
+		_: &'static str,
+		_: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, DeError> {
+		// Same as the avro deserializer does for `Bytes`: the value is the name of
+		// a unit variant (e.g. `avro.codec`)
+		let variant = std::str::from_utf8(&self.0).map_err(|e| {
+			<DeError as serde::de::Error>::custom(format_args!("String is not valid utf-8: {e}"))
+		})?;
+		visitor.visit_enum(IntoDeserializer::<DeError>::into_deserializer(variant))
+	}
+	serde::forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+		bytes byte_buf option unit unit_struct newtype_struct seq tuple
+		tuple_struct map struct identifier ignored_any
+	}
+}
+
+impl<'de> IntoDeserializer<'de, DeError> for MaybeBorrowed<'de, str> {
+	type Deserializer = Self;
+	fn into_deserializer(self) -> Self {
+		self
+	}
+}
+
+impl<'de> IntoDeserializer<'de, DeError> for MaybeBorrowed<'de, [u8]> {
+	type Deserializer = Self;
+	fn into_deserializer(self) -> Self {
+		self
+	}
+}
@@ -5,18 +5,22 @@
 //!
 //! See [`Reader`] and [`Writer`] documentations for their respective examples.
 
+mod codec;
+mod concat;
+mod metadata;
 mod reader;
+mod recompress;
 mod writer;
 
-pub use {reader::*, writer::*};
+pub use {codec::*, concat::*, metadata::*, reader::*, recompress::*, writer::*};
 
-use std::num::NonZeroU8;
+use std::{borrow::Cow, num::NonZeroU8};
 
 /// Specifies the compression codec and level to be used for the data blocks
 /// in the file.
 ///
 /// Variants may be made available by enabling features.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Compression {
 	/// The `Null` codec simply passes through data uncompressed.
 	Null,
@@ -57,7 +61,86 @@ pub enum Compression {
 		/// Zstandard compression level to use (1-22 or
 		/// [`CompressionLevel::default()`])
 		level: CompressionLevel,
+		/// Dictionary to compress blocks with, if any
+		///
+		/// See [`ZstandardDictionary`].
+		dictionary: Option<ZstandardDictionary>,
 	},
+	#[cfg(feature = "lz4")]
+	/// The `lz4` codec uses the [LZ4](https://lz4.org/) compression
+	/// algorithm. Each block is compressed as an LZ4 frame.
+	///
+	/// This codec is not part of the Avro specification.
+	Lz4,
+	/// A codec that is not built into this crate
+	///
+	/// See [`Codec`].
+	Custom(CustomCodec),
+}
+
+/// Key of the header metadata entry the [`ZstandardDictionary`] is stored
+/// under
+#[cfg(feature = "zstandard")]
+pub const ZSTANDARD_DICTIONARY_METADATA_KEY: &str = "zstandard.dictionary";
+
+/// Dictionary to compress blocks with when using [`Compression::Zstandard`],
+/// typically trained on a sample of the records (e.g. with `zstd --train`)
+///
+/// This significantly improves compression ratios when blocks are small. It
+/// is stored in the header metadata of the file (under the
+/// [`ZSTANDARD_DICTIONARY_METADATA_KEY`] key), from where the [`Reader`]
+/// picks it up to decompress blocks.
+///
+/// This is cheap to clone.
+#[cfg(feature = "zstandard")]
+#[derive(Clone)]
+pub struct ZstandardDictionary {
+	bytes: std::sync::Arc<[u8]>,
+	/// Digested form of the dictionary for decompression, prepared the first
+	/// time a block is decompressed then shared by all the clones
+	decoder_dictionary: std::sync::Arc<std::sync::OnceLock<zstd::dict::DecoderDictionary<'static>>>,
+}
+#[cfg(feature = "zstandard")]
+impl ZstandardDictionary {
+	/// Use the given bytes as dictionary, e.g. as output by `zstd --train`
+	pub fn new(bytes: impl Into<std::sync::Arc<[u8]>>) -> Self {
+		Self {
+			bytes: bytes.into(),
+			decoder_dictionary: Default::default(),
+		}
+	}
+
+	/// The bytes of the dictionary
+	pub fn as_bytes(&self) -> &[u8] {
+		&self.bytes
+	}
+
+	pub(crate) fn decoder_dictionary(&self) -> &zstd::dict::DecoderDictionary<'static> {
+		self.decoder_dictionary
+			.get_or_init(|| zstd::dict::DecoderDictionary::copy(&self.bytes))
+	}
+}
+#[cfg(feature = "zstandard")]
+impl PartialEq for ZstandardDictionary {
+	fn eq(&self, other: &Self) -> bool {
+		self.bytes == other.bytes
+	}
+}
+#[cfg(feature = "zstandard")]
+impl Eq for ZstandardDictionary {}
+#[cfg(feature = "zstandard")]
+impl std::hash::Hash for ZstandardDictionary {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		self.bytes.hash(state)
+	}
+}
+#[cfg(feature = "zstandard")]
+impl std::fmt::Debug for ZstandardDictionary {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("ZstandardDictionary")
+			.field("len", &self.bytes.len())
+			.finish_non_exhaustive()
+	}
 }
 
 /// Compression level to use for the compression algorithm
@@ -160,7 +243,8 @@ impl std::fmt::Debug for CompressionLevel {
 }
 
 impl Compression {
-	fn codec(&self) -> CompressionCodec {
+	/// The codec that blocks compressed this way are tagged with in the header
+	pub fn codec(&self) -> CompressionCodec {
 		match self {
 			Compression::Null => CompressionCodec::Null,
 			#[cfg(feature = "deflate")]
@@ -173,15 +257,18 @@ impl Compression {
 			Compression::Xz { .. } => CompressionCodec::Xz,
 			#[cfg(feature = "zstandard")]
 			Compression::Zstandard { .. } => CompressionCodec::Zstandard,
+			#[cfg(feature = "lz4")]
+			Compression::Lz4 => CompressionCodec::Lz4,
+			Compression::Custom(codec) => CompressionCodec::Custom(codec.name()),
 		}
 	}
 }
 
 /// The compression codec used to compress blocks.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, serde_derive::Deserialize, serde_derive::Serialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde_derive::Deserialize)]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
-enum CompressionCodec {
+pub enum CompressionCodec {
 	/// The `Null` codec simply passes through data uncompressed.
 	Null,
 	#[cfg(feature = "deflate")]
@@ -206,19 +293,176 @@ enum CompressionCodec {
 	#[cfg(feature = "zstandard")]
 	/// The `zstandard` codec uses Facebook’s [Zstandard](https://facebook.github.io/zstd/) compression library
 	Zstandard,
+	#[cfg(feature = "lz4")]
+	/// The `lz4` codec uses the [LZ4](https://lz4.org/) compression
+	/// algorithm, each block being an LZ4 frame. This codec is not part of the
+	/// Avro specification.
+	Lz4,
+	/// A [`Codec`] that is not built into this crate, identified by its
+	/// [`name`](Codec::name)
+	#[serde(skip)]
+	Custom(&'static str),
+}
+
+impl CompressionCodec {
+	/// The name of the codec, as written in the `avro.codec` entry of the
+	/// header
+	pub fn name(self) -> &'static str {
+		match self {
+			CompressionCodec::Null => "null",
+			#[cfg(feature = "deflate")]
+			CompressionCodec::Deflate => "deflate",
+			#[cfg(feature = "bzip2")]
+			CompressionCodec::Bzip2 => "bzip2",
+			#[cfg(feature = "snappy")]
+			CompressionCodec::Snappy => "snappy",
+			#[cfg(feature = "xz")]
+			CompressionCodec::Xz => "xz",
+			#[cfg(feature = "zstandard")]
+			CompressionCodec::Zstandard => "zstandard",
+			#[cfg(feature = "lz4")]
+			CompressionCodec::Lz4 => "lz4",
+			CompressionCodec::Custom(name) => name,
+		}
+	}
+}
+
+/// The [`Compression`] of each codec that is built into this crate (with the
+/// enabled features), with the default compression level
+const BUILTIN_COMPRESSIONS: &[Compression] = &[
+	Compression::Null,
+	#[cfg(feature = "deflate")]
+	Compression::Deflate {
+		level: CompressionLevel::default(),
+	},
+	#[cfg(feature = "bzip2")]
+	Compression::Bzip2 {
+		level: CompressionLevel::default(),
+	},
+	#[cfg(feature = "snappy")]
+	Compression::Snappy,
+	#[cfg(feature = "xz")]
+	Compression::Xz {
+		level: CompressionLevel::default(),
+	},
+	#[cfg(feature = "zstandard")]
+	Compression::Zstandard {
+		level: CompressionLevel::default(),
+		dictionary: None,
+	},
+	#[cfg(feature = "lz4")]
+	Compression::Lz4,
+];
+
+impl serde::Serialize for CompressionCodec {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(self.name())
+	}
+}
+
+/// A block of an object container file, as it is stored in the file
+///
+/// This allows to work at the block level without deserializing the records,
+/// via [`Reader::next_raw_block`] and [`Writer::push_raw_block`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawBlock<'a> {
+	/// The number of objects serialized in this block
+	pub n_objects: u64,
+	/// The serialized objects, compressed with `codec`
+	pub compressed_bytes: Cow<'a, [u8]>,
+	/// The codec `compressed_bytes` is compressed with
+	pub codec: CompressionCodec,
+	/// The dictionary `compressed_bytes` is compressed with, if `codec` is
+	/// [`Zstandard`](CompressionCodec::Zstandard) and the file uses one
+	#[cfg(feature = "zstandard")]
+	pub zstandard_dictionary: Option<ZstandardDictionary>,
 }
 
 const HEADER_CONST: [u8; 4] = [b'O', b'b', b'j', 1u8];
 
 #[derive(serde_derive::Deserialize, serde_derive::Serialize)]
-struct Metadata<S, M> {
+struct HeaderMetadata<S, M> {
 	#[serde(rename = "avro.schema")]
 	schema: S,
 	#[serde(rename = "avro.codec")]
-	codec: CompressionCodec,
+	codec: S,
+	#[serde(
+		rename = "zstandard.dictionary",
+		default,
+		deserialize_with = "deserialize_some",
+		skip_serializing_if = "Option::is_none"
+	)]
+	zstandard_dictionary: Option<RawBytes>,
 	#[serde(flatten)]
 	user_metadata: M,
 }
+/// User metadata entries of a header, kept as the raw bytes they are encoded
+/// as, so that they can be written again as-is
+struct RawUserMetadata(Vec<(String, RawBytes)>);
+
+impl<'de> serde::Deserialize<'de> for RawUserMetadata {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct RawUserMetadataVisitor;
+		impl<'de> serde::de::Visitor<'de> for RawUserMetadataVisitor {
+			type Value = RawUserMetadata;
+			fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				formatter.write_str("a map of bytes")
+			}
+			fn visit_map<A: serde::de::MapAccess<'de>>(
+				self,
+				mut map: A,
+			) -> Result<Self::Value, A::Error> {
+				let mut entries = Vec::new();
+				while let Some(entry) = map.next_entry()? {
+					entries.push(entry);
+				}
+				Ok(RawUserMetadata(entries))
+			}
+		}
+		deserializer.deserialize_map(RawUserMetadataVisitor)
+	}
+}
+
+impl serde::Serialize for RawUserMetadata {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_map(self.0.iter().map(|(key, value)| (key, value)))
+	}
+}
+
+/// `Vec<u8>` that (de)serializes as bytes rather than as a sequence
+struct RawBytes(Vec<u8>);
+
+impl<'de> serde::Deserialize<'de> for RawBytes {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct RawBytesVisitor;
+		impl<'de> serde::de::Visitor<'de> for RawBytesVisitor {
+			type Value = RawBytes;
+			fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				formatter.write_str("bytes")
+			}
+			fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+				Ok(RawBytes(v.to_owned()))
+			}
+			fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+				Ok(RawBytes(v))
+			}
+		}
+		deserializer.deserialize_byte_buf(RawBytesVisitor)
+	}
+}
+
+impl serde::Serialize for RawBytes {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_bytes(&self.0)
+	}
+}
+
+fn deserialize_some<'de, D: serde::Deserializer<'de>>(
+	deserializer: D,
+) -> Result<Option<RawBytes>, D::Error> {
+	serde::Deserialize::deserialize(deserializer).map(Some)
+}
+
 const METADATA_SCHEMA: crate::schema::self_referential::NodeRef<'static> =
 	crate::schema::self_referential::NodeRef::from_static(
 		&crate::schema::self_referential::SchemaNode::Map(
@@ -268,4 +512,15 @@ fn compression_codec_serializes_properly() {
 		let serialized = serde_json::to_string(&codec).unwrap();
 		assert_eq!(serialized, "\"zstandard\"");
 	}
+
+	#[cfg(feature = "lz4")]
+	{
+		let codec = CompressionCodec::Lz4;
+		let serialized = serde_json::to_string(&codec).unwrap();
+		assert_eq!(serialized, "\"lz4\"");
+	}
+
+	let codec = CompressionCodec::Custom("custom");
+	let serialized = serde_json::to_string(&codec).unwrap();
+	assert_eq!(serialized, "\"custom\"");
 }
@@ -0,0 +1,356 @@
+use super::*;
+
+use {
+	integer_encoding::VarInt,
+	tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt},
+};
+
+/// Asynchronous reader for [object container files](https://avro.apache.org/docs/current/specification/#object-container-files)
+///
+/// Each block is read asynchronously from the underlying
+/// [`AsyncBufRead`] and decompressed in memory, then records are deserialized
+/// synchronously from that in-memory block. This means that a whole block
+/// (compressed and decompressed) is held in memory at once, which should be
+/// fine as avro blocks are typically of a reasonable size.
+///
+/// Deserializing records does not involve any IO, so it's not worth offloading
+/// it to a blocking thread.
+///
+/// # Example
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use futures_util::TryStreamExt;
+///
+/// let avro_object_container_file_encoded: &[u8] = &[
+/// 	79, 98, 106, 1, 4, 22, 97, 118, 114, 111, 46, 115, 99, 104, 101, 109, 97, 222, 1, 123, 34,
+/// 	116, 121, 112, 101, 34, 58, 34, 114, 101, 99, 111, 114, 100, 34, 44, 34, 110, 97, 109, 101,
+/// 	34, 58, 34, 116, 101, 115, 116, 34, 44, 34, 102, 105, 101, 108, 100, 115, 34, 58, 91, 123,
+/// 	34, 110, 97, 109, 101, 34, 58, 34, 97, 34, 44, 34, 116, 121, 112, 101, 34, 58, 34, 108,
+/// 	111, 110, 103, 34, 44, 34, 100, 101, 102, 97, 117, 108, 116, 34, 58, 52, 50, 125, 44, 123,
+/// 	34, 110, 97, 109, 101, 34, 58, 34, 98, 34, 44, 34, 116, 121, 112, 101, 34, 58, 34, 115,
+/// 	116, 114, 105, 110, 103, 34, 125, 93, 125, 20, 97, 118, 114, 111, 46, 99, 111, 100, 101,
+/// 	99, 8, 110, 117, 108, 108, 0, 94, 61, 54, 221, 190, 207, 108, 180, 158, 57, 114, 40, 173,
+/// 	199, 228, 239, 4, 20, 54, 6, 102, 111, 111, 84, 6, 98, 97, 114, 94, 61, 54, 221, 190, 207,
+/// 	108, 180, 158, 57, 114, 40, 173, 199, 228, 239,
+/// ];
+///
+/// #[derive(serde_derive::Deserialize, Debug, PartialEq, Eq)]
+/// struct SchemaRecord {
+/// 	a: i64,
+/// 	b: String,
+/// }
+///
+/// let mut reader = serde_avro_fast::object_container_file_encoding::AsyncReader::new(
+/// 	avro_object_container_file_encoded,
+/// )
+/// .await
+/// .expect("Failed to initialize reader");
+///
+/// let expected = vec![
+/// 	SchemaRecord {
+/// 		a: 27,
+/// 		b: "foo".to_owned(),
+/// 	},
+/// 	SchemaRecord {
+/// 		a: 42,
+/// 		b: "bar".to_owned(),
+/// 	},
+/// ];
+/// let res: Vec<SchemaRecord> = reader
+/// 	.deserialize::<SchemaRecord>()
+/// 	.try_collect()
+/// 	.await
+/// 	.expect("Failed to deserialize a record");
+///
+/// assert_eq!(expected, res);
+/// # }
+/// ```
+pub struct AsyncReader<R> {
+	reader: R,
+	compression: Compression,
+	sync_marker: [u8; 16],
+	/// The block as read from `reader`, before decompression
+	block_buffer: Vec<u8>,
+	/// The decompressed current block, that records are deserialized from
+	/// (unused with the `Null` codec, in which case we deserialize directly
+	/// from `block_buffer`)
+	decompression_buffer: Vec<u8>,
+	/// How far into the decompressed block we have deserialized
+	position_in_block: usize,
+	n_objects_left_in_block: usize,
+	/// If we fail to read a block, we can't know where the next one starts,
+	/// so we yield the error once, then pretend that we reached EOF (see
+	/// [`Reader`])
+	pretend_eof_because_yielded_unrecoverable_error: bool,
+	/// See [`set_max_decompressed_block_size`](AsyncReader::set_max_decompressed_block_size)
+	max_decompressed_block_size: usize,
+	metadata: Metadata,
+	schema: Arc<Schema>,
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncReader<R> {
+	/// Initialize an `AsyncReader` from any `impl AsyncBufRead`
+	///
+	/// Note that if your reader has [`AsyncRead`](tokio::io::AsyncRead) but
+	/// not [`AsyncBufRead`], you may simply wrap it into a
+	/// [`tokio::io::BufReader`].
+	///
+	/// This reads the header of the object container file.
+	pub async fn new(reader: R) -> Result<Self, FailedToInitializeReader> {
+		Self::new_and_metadata::<()>(reader)
+			.await
+			.map(|(reader, ())| reader)
+	}
+
+	/// Build an `AsyncReader`, also extracting custom metadata in addition to
+	/// the avro-reserved metadata
+	pub async fn new_and_metadata<M: DeserializeOwned>(
+		reader: R,
+	) -> Result<(Self, M), FailedToInitializeReader> {
+		Self::new_and_metadata_with_codecs(reader, &CodecRegistry::new()).await
+	}
+
+	/// Build an `AsyncReader`, resolving codecs that are not built into this
+	/// crate against `codecs`
+	///
+	/// See [`Reader::new_with_codecs`].
+	pub async fn new_with_codecs(
+		reader: R,
+		codecs: &CodecRegistry,
+	) -> Result<Self, FailedToInitializeReader> {
+		Self::new_and_metadata_with_codecs::<()>(reader, codecs)
+			.await
+			.map(|(reader, ())| reader)
+	}
+
+	/// Build an `AsyncReader`, also extracting custom metadata, and resolving
+	/// codecs that are not built into this crate against `codecs`
+	///
+	/// See [`Reader::new_and_metadata_with_codecs`].
+	pub async fn new_and_metadata_with_codecs<M: DeserializeOwned>(
+		mut reader: R,
+		codecs: &CodecRegistry,
+	) -> Result<(Self, M), FailedToInitializeReader> {
+		let raw_header = read_raw_header(&mut reader)
+			.await
+			.map_err(FailedToInitializeReader::FailedToDeserializeHeader)?;
+		let (_, header) = read_header(de::read::SliceRead::new(&raw_header), codecs)?;
+
+		Ok((
+			Self {
+				reader,
+				compression: header.compression,
+				sync_marker: header.sync_marker,
+				block_buffer: Vec::new(),
+				decompression_buffer: Vec::new(),
+				position_in_block: 0,
+				n_objects_left_in_block: 0,
+				pretend_eof_because_yielded_unrecoverable_error: false,
+				max_decompressed_block_size: decompression::DEFAULT_MAX_DECOMPRESSED_BLOCK_SIZE,
+				metadata: header.metadata,
+				schema: header.schema,
+			},
+			header.user_metadata,
+		))
+	}
+
+	/// Stream of the deserialized values
+	pub fn deserialize<'r, T: DeserializeOwned + 'r>(
+		&'r mut self,
+	) -> impl futures_util::Stream<Item = Result<T, DeError>> + 'r {
+		futures_util::stream::unfold(self, |reader| async move {
+			reader
+				.deserialize_next()
+				.await
+				.transpose()
+				.map(|res| (res, reader))
+		})
+	}
+
+	/// Attempt to deserialize the next value
+	///
+	/// # Cancel safety
+	///
+	/// This method is not cancel-safe: if the returned future is dropped while
+	/// it is reading a block, the part of the block that was already read is
+	/// lost, and the reader can't find where the next block starts anymore.
+	pub async fn deserialize_next<T: DeserializeOwned>(&mut self) -> Result<Option<T>, DeError> {
+		if self.pretend_eof_because_yielded_unrecoverable_error {
+			return Ok(None);
+		}
+		while self.n_objects_left_in_block == 0 {
+			match self.read_next_block().await {
+				Ok(true) => {}
+				Ok(false) => return Ok(None),
+				Err(de_error) => {
+					self.pretend_eof_because_yielded_unrecoverable_error = true;
+					return Err(de_error);
+				}
+			}
+		}
+		self.n_objects_left_in_block -= 1;
+		let block = match self.compression {
+			Compression::Null => &self.block_buffer,
+			_ => &self.decompression_buffer,
+		};
+		let mut deserializer_state = de::DeserializerState::from_schema_node(
+			de::read::SliceRead::new(&block[self.position_in_block..]),
+			self.schema.root(),
+		);
+		let res = T::deserialize(deserializer_state.deserializer());
+		let left_in_block = deserializer_state.into_reader().remaining().len();
+		self.position_in_block = block.len() - left_in_block;
+		if res.is_ok() && self.n_objects_left_in_block == 0 && left_in_block != 0 {
+			return Err(DeError::new(
+				"There's data left in the block after deserializing it entirely",
+			));
+		}
+		res.map(Some)
+	}
+
+	/// Read the next block into `block_buffer`, decompressing it into
+	/// `decompression_buffer` if there is a compression codec
+	///
+	/// Returns `false` if we reached EOF.
+	async fn read_next_block(&mut self) -> Result<bool, DeError> {
+		if self
+			.reader
+			.fill_buf()
+			.await
+			.map_err(DeError::io)?
+			.is_empty()
+		{
+			return Ok(false);
+		}
+		let n_objects_in_block: i64 = read_varint(&mut self.reader, &mut Vec::new()).await?;
+		let n_objects_in_block: usize = n_objects_in_block
+			.try_into()
+			.map_err(|_| DeError::new("Invalid container file block object count"))?;
+		let block_size: i64 = read_varint(&mut self.reader, &mut Vec::new()).await?;
+		let block_size: usize = block_size
+			.try_into()
+			.map_err(|_| DeError::new("Invalid container file block size in bytes"))?;
+		self.block_buffer.clear();
+		read_exact_into(&mut self.reader, block_size, &mut self.block_buffer).await?;
+		let mut sync_marker = [0; 16];
+		self.reader
+			.read_exact(&mut sync_marker)
+			.await
+			.map_err(DeError::io)?;
+		if sync_marker != self.sync_marker {
+			return Err(DeError::new("Incorrect sync marker at end of block"));
+		}
+		self.compression.decompress_block(
+			&self.block_buffer,
+			&mut self.decompression_buffer,
+			self.max_decompressed_block_size,
+		)?;
+		self.position_in_block = 0;
+		self.n_objects_left_in_block = n_objects_in_block;
+		Ok(true)
+	}
+
+	/// Set the maximum size that a block is allowed to decompress to
+	///
+	/// Since blocks are always decompressed whole by the `AsyncReader`, this
+	/// applies to all codecs. See [`Reader::set_max_decompressed_block_size`].
+	pub fn set_max_decompressed_block_size(&mut self, max_decompressed_block_size: usize) {
+		self.max_decompressed_block_size = max_decompressed_block_size;
+	}
+
+	/// Get the schema used for deserialization
+	///
+	/// It was read from the header of the object container file.
+	pub fn schema(&self) -> &Arc<Schema> {
+		&self.schema
+	}
+
+	/// Get all the entries of the header metadata, including the
+	/// avro-reserved ones
+	///
+	/// See [`Metadata`].
+	pub fn metadata(&self) -> &Metadata {
+		&self.metadata
+	}
+}
+
+/// Read the header (magic, metadata map and sync marker) without interpreting
+/// it, so that it can then be parsed synchronously by [`read_header`]
+async fn read_raw_header<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Vec<u8>, DeError> {
+	let mut raw_header = Vec::new();
+	read_exact_into(reader, HEADER_CONST.len(), &mut raw_header).await?;
+	if raw_header != HEADER_CONST {
+		// Let `read_header` generate the appropriate error
+		return Ok(raw_header);
+	}
+	let mut n_entries: usize = 0;
+	loop {
+		let block_len: i64 = read_varint(reader, &mut raw_header).await?;
+		if block_len == 0 {
+			break;
+		}
+		if block_len < 0 {
+			// Negative block length is followed by the size of the block in bytes
+			let _: i64 = read_varint(reader, &mut raw_header).await?;
+		}
+		n_entries = usize::try_from(block_len.unsigned_abs())
+			.ok()
+			.and_then(|block_len| n_entries.checked_add(block_len))
+			.filter(|&n_entries| n_entries <= MAX_METADATA_ENTRIES)
+			.ok_or_else(|| DeError::new("Exceeded max sequence size while deserializing"))?;
+		for _ in 0..block_len.unsigned_abs() {
+			// key, then value
+			for _ in 0..2 {
+				let len: i64 = read_varint(reader, &mut raw_header).await?;
+				let len = usize::try_from(len)
+					.map_err(|_| DeError::new("Invalid length in object container file header"))?;
+				read_exact_into(reader, len, &mut raw_header).await?;
+			}
+		}
+	}
+	read_exact_into(reader, 16, &mut raw_header).await?;
+	Ok(raw_header)
+}
+
+/// Read a varint, also appending its raw bytes to `raw`
+async fn read_varint<R: AsyncBufRead + Unpin, I: VarInt>(
+	reader: &mut R,
+	raw: &mut Vec<u8>,
+) -> Result<I, DeError> {
+	let start = raw.len();
+	loop {
+		let byte = reader.read_u8().await.map_err(DeError::io)?;
+		raw.push(byte);
+		if byte & 0x80 == 0 {
+			break;
+		}
+		if raw.len() - start >= 10 {
+			return Err(DeError::new("Varint is too long"));
+		}
+	}
+	I::decode_var(&raw[start..])
+		.map(|(val, _)| val)
+		.ok_or_else(|| DeError::new("Failed to decode varint"))
+}
+
+/// Append exactly `len` bytes from `reader` to `buf`
+///
+/// The buffer only grows as data is actually read, so that a corrupted length
+/// can't make us allocate huge amounts of memory upfront.
+async fn read_exact_into<R: AsyncBufRead + Unpin>(
+	reader: &mut R,
+	len: usize,
+	buf: &mut Vec<u8>,
+) -> Result<(), DeError> {
+	let expected_len = buf.len() + len;
+	(&mut *reader)
+		.take(len as u64)
+		.read_to_end(buf)
+		.await
+		.map_err(DeError::io)?;
+	if buf.len() != expected_len {
+		return Err(DeError::io(std::io::ErrorKind::UnexpectedEof.into()));
+	}
+	Ok(())
+}
@@ -1,22 +1,30 @@
 use crate::{
 	de::{self, read::take::IntoLeftAfterTake, DeserializerConfig, DeserializerState},
-	object_container_file_encoding::CompressionCodec,
+	object_container_file_encoding::{Compression, CustomCodec},
 };
 
-impl CompressionCodec {
+/// Default for the size that a block is allowed to decompress to when it is
+/// decompressed whole (see
+/// [`Reader::set_max_decompressed_block_size`](super::Reader::set_max_decompressed_block_size))
+pub(in super::super) const DEFAULT_MAX_DECOMPRESSED_BLOCK_SIZE: usize = 512 * 1024 * 1024;
+
+impl Compression {
+	/// `max_decompressed_block_size` only applies to codecs that don't support
+	/// streaming decompression (Snappy and custom codecs)
 	pub(super) fn state<'de, 's, R>(
-		self,
+		&self,
 		reader: R,
 		config: DeserializerConfig<'s>,
 		decompression_buffer: Vec<u8>,
 		block_size: usize,
+		max_decompressed_block_size: usize,
 	) -> Result<DecompressionState<'s, R>, de::DeError>
 	where
 		R: de::read::take::Take + de::read::ReadSlice<'de>,
 		<R as de::read::take::Take>::Take: de::read::ReadSlice<'de> + std::io::BufRead,
 	{
 		Ok(match self {
-			CompressionCodec::Null => DecompressionState::Null {
+			Compression::Null => DecompressionState::Null {
 				deserializer_state: de::DeserializerState::with_config(
 					de::read::take::Take::take(reader, block_size)?,
 					config,
@@ -24,7 +32,7 @@ impl CompressionCodec {
 				decompression_buffer,
 			},
 			#[cfg(feature = "deflate")]
-			CompressionCodec::Deflate => DecompressionState::BufReader {
+			Compression::Deflate { .. } => DecompressionState::BufReader {
 				deserializer_state: de::DeserializerState::with_config(
 					de::read::ReaderRead::new(std::io::BufReader::new(
 						DecompressionReaderForBufReader::Deflate(
@@ -38,7 +46,7 @@ impl CompressionCodec {
 				decompression_buffer,
 			},
 			#[cfg(feature = "bzip2")]
-			CompressionCodec::Bzip2 => DecompressionState::BufReader {
+			Compression::Bzip2 { .. } => DecompressionState::BufReader {
 				deserializer_state: de::DeserializerState::with_config(
 					de::read::ReaderRead::new(std::io::BufReader::new(
 						DecompressionReaderForBufReader::Bzip2(bzip2::bufread::BzDecoder::new(
@@ -50,7 +58,7 @@ impl CompressionCodec {
 				decompression_buffer,
 			},
 			#[cfg(feature = "snappy")]
-			CompressionCodec::Snappy => {
+			Compression::Snappy => {
 				// Snappy does not support block decompression in the format used by Avro.
 				// This should be fine because avro blocks themselves should typically be of a
 				// reasonable size
@@ -61,46 +69,21 @@ impl CompressionCodec {
 				})?;
 				let mut reader = reader;
 				let mut decompression_buffer = decompression_buffer;
-				/// Workaround a rust type inference limitation
-				fn fix_closure_late_bound_lifetime_inference<F, T>(f: F) -> F
-				where
-					F: FnOnce(&[u8]) -> T,
-				{
-					f
-				}
 				de::read::ReadSlice::read_slice(
 					&mut reader,
 					block_raw_size,
 					fix_closure_late_bound_lifetime_inference(|compressed_slice| {
-						fn snappy_to_de_error(snappy_error: snap::Error) -> de::DeError {
-							<de::DeError as serde::de::Error>::custom(format_args!(
-								"Snappy decompression error: {snappy_error}"
-							))
-						}
-						decompression_buffer.resize(
-							snap::raw::decompress_len(compressed_slice)
-								.map_err(snappy_to_de_error)?,
-							0,
-						);
-						let written = snap::raw::Decoder::new()
-							.decompress(compressed_slice, &mut decompression_buffer)
-							.map_err(snappy_to_de_error)?;
-						if written != decompression_buffer.len() {
-							return Err(de::DeError::new(
-								"Snappy decompression error: incorrect decompressed size",
-							));
-						}
-						Ok(())
+						snappy_decompress(
+							compressed_slice,
+							&mut decompression_buffer,
+							max_decompressed_block_size,
+						)
 					}),
 				)?;
-				let actual_crc32 = crc32fast::hash(&decompression_buffer);
-				let expected_crc32 =
-					u32::from_be_bytes(de::read::Read::read_const_size_buf(&mut reader)?);
-				if actual_crc32 != expected_crc32 {
-					return Err(de::DeError::new(
-						"Incorrect extra CRC32 of decompressed data when using Snappy compression codec",
-					));
-				}
+				snappy_check_crc32(
+					&decompression_buffer,
+					de::read::Read::read_const_size_buf(&mut reader)?,
+				)?;
 				DecompressionState::DecompressedOnConstruction {
 					deserializer_state: de::DeserializerState::with_config(
 						de::read::ReaderRead::new(std::io::Cursor::new(decompression_buffer)),
@@ -110,7 +93,7 @@ impl CompressionCodec {
 				}
 			}
 			#[cfg(feature = "xz")]
-			CompressionCodec::Xz => DecompressionState::BufReader {
+			Compression::Xz { .. } => DecompressionState::BufReader {
 				deserializer_state: de::DeserializerState::with_config(
 					de::read::ReaderRead::new(std::io::BufReader::new(
 						DecompressionReaderForBufReader::Xz(xz2::bufread::XzDecoder::new(
@@ -122,24 +105,275 @@ impl CompressionCodec {
 				decompression_buffer,
 			},
 			#[cfg(feature = "zstandard")]
-			CompressionCodec::Zstandard => DecompressionState::BufReader {
+			Compression::Zstandard { dictionary, .. } => DecompressionState::BufReader {
 				deserializer_state: de::DeserializerState::with_config(
 					de::read::ReaderRead::new(std::io::BufReader::new(
 						DecompressionReaderForBufReader::Zstandard(
-							zstd::stream::read::Decoder::with_buffer(de::read::take::Take::take(
-								reader, block_size,
-							)?)
-							.map_err(de::DeError::io)?,
+							zstandard_decoder(
+								de::read::take::Take::take(reader, block_size)?,
+								dictionary,
+							)?,
+							dictionary.clone(),
 						),
 					)),
 					config,
 				),
 				decompression_buffer,
 			},
+			Compression::Custom(codec) => {
+				// Custom codecs only support decompressing whole blocks
+				let mut reader = reader;
+				let mut decompression_buffer = decompression_buffer;
+				de::read::ReadSlice::read_slice(
+					&mut reader,
+					block_size,
+					fix_closure_late_bound_lifetime_inference(|compressed_slice| {
+						custom_decompress(
+							codec,
+							compressed_slice,
+							&mut decompression_buffer,
+							max_decompressed_block_size,
+						)
+					}),
+				)?;
+				DecompressionState::DecompressedOnConstruction {
+					deserializer_state: de::DeserializerState::with_config(
+						de::read::ReaderRead::new(std::io::Cursor::new(decompression_buffer)),
+						config,
+					),
+					source_reader: reader,
+				}
+			}
+			#[cfg(feature = "lz4")]
+			Compression::Lz4 => DecompressionState::BufReader {
+				deserializer_state: de::DeserializerState::with_config(
+					de::read::ReaderRead::new(std::io::BufReader::new(
+						DecompressionReaderForBufReader::Lz4(lz4_flex::frame::FrameDecoder::new(
+							de::read::take::Take::take(reader, block_size)?,
+						)),
+					)),
+					config,
+				),
+				decompression_buffer,
+			},
 		})
 	}
 }
 
+/// Workaround a rust type inference limitation
+fn fix_closure_late_bound_lifetime_inference<F, T>(f: F) -> F
+where
+	F: FnOnce(&[u8]) -> T,
+{
+	f
+}
+
+impl Compression {
+	/// Decompress a whole block that has already been loaded in memory
+	///
+	/// Returns the decompressed block: `block` itself with the `Null` codec,
+	/// otherwise `decompression_buffer`, which the block is decompressed into.
+	///
+	/// Errors if the block decompresses to more than
+	/// `max_decompressed_block_size` bytes.
+	pub(crate) fn decompress_block<'b>(
+		&self,
+		block: &'b [u8],
+		decompression_buffer: &'b mut Vec<u8>,
+		max_decompressed_block_size: usize,
+	) -> Result<&'b [u8], de::DeError> {
+		match self {
+			Compression::Null => Ok(block),
+			#[cfg(feature = "deflate")]
+			Compression::Deflate { .. } => decompress_whole_block(
+				flate2::bufread::DeflateDecoder::new(block),
+				decompression_buffer,
+				max_decompressed_block_size,
+				|decoder| decoder.into_inner(),
+			),
+			#[cfg(feature = "bzip2")]
+			Compression::Bzip2 { .. } => decompress_whole_block(
+				bzip2::bufread::BzDecoder::new(block),
+				decompression_buffer,
+				max_decompressed_block_size,
+				|decoder| decoder.into_inner(),
+			),
+			#[cfg(feature = "snappy")]
+			Compression::Snappy => {
+				let (compressed_slice, crc32) = block
+					.len()
+					.checked_sub(4)
+					.map(|block_raw_size| block.split_at(block_raw_size))
+					.ok_or_else(|| {
+						de::DeError::new(
+							"Incorrect block size for Snappy compression: \
+								should be at least 4 for CRC",
+						)
+					})?;
+				snappy_decompress(
+					compressed_slice,
+					decompression_buffer,
+					max_decompressed_block_size,
+				)?;
+				snappy_check_crc32(
+					decompression_buffer,
+					crc32.try_into().expect("split at len - 4"),
+				)?;
+				Ok(decompression_buffer)
+			}
+			#[cfg(feature = "xz")]
+			Compression::Xz { .. } => decompress_whole_block(
+				xz2::bufread::XzDecoder::new(block),
+				decompression_buffer,
+				max_decompressed_block_size,
+				|decoder| decoder.into_inner(),
+			),
+			#[cfg(feature = "zstandard")]
+			Compression::Zstandard { dictionary, .. } => decompress_whole_block(
+				zstandard_decoder(block, dictionary)?,
+				decompression_buffer,
+				max_decompressed_block_size,
+				|decoder| decoder.finish(),
+			),
+			#[cfg(feature = "lz4")]
+			Compression::Lz4 => decompress_whole_block(
+				lz4_flex::frame::FrameDecoder::new(block),
+				decompression_buffer,
+				max_decompressed_block_size,
+				|decoder| decoder.into_inner(),
+			),
+			Compression::Custom(codec) => {
+				custom_decompress(
+					codec,
+					block,
+					decompression_buffer,
+					max_decompressed_block_size,
+				)?;
+				Ok(decompression_buffer)
+			}
+		}
+	}
+}
+
+/// Custom codecs decompress whole blocks on their own, so we can only check the
+/// size of the decompressed block afterwards
+fn custom_decompress(
+	codec: &CustomCodec,
+	compressed_slice: &[u8],
+	decompression_buffer: &mut Vec<u8>,
+	max_decompressed_block_size: usize,
+) -> Result<(), de::DeError> {
+	decompression_buffer.clear();
+	codec
+		.codec()
+		.decompress(compressed_slice, decompression_buffer)
+		.map_err(|e| de::DeError::custom_io("Failed to decompress block", e))?;
+	if decompression_buffer.len() > max_decompressed_block_size {
+		return Err(decompressed_block_too_large(max_decompressed_block_size));
+	}
+	Ok(())
+}
+
+fn decompressed_block_too_large(max_decompressed_block_size: usize) -> de::DeError {
+	<de::DeError as serde::de::Error>::custom(format_args!(
+		"Decompressed block is larger than allowed for this reader \
+			({max_decompressed_block_size}) - this is probably due to malformed data"
+	))
+}
+
+#[cfg(any(
+	feature = "deflate",
+	feature = "bzip2",
+	feature = "xz",
+	feature = "zstandard",
+	feature = "lz4"
+))]
+fn decompress_whole_block<'b, D: std::io::Read>(
+	mut decoder: D,
+	decompression_buffer: &'b mut Vec<u8>,
+	max_decompressed_block_size: usize,
+	left_in_block: impl FnOnce(D) -> &'b [u8],
+) -> Result<&'b [u8], de::DeError> {
+	decompression_buffer.clear();
+	// Read one more byte than allowed so that we can tell whether the limit was
+	// exceeded, without ever decompressing more than that
+	std::io::Read::read_to_end(
+		&mut std::io::Read::take(
+			&mut decoder,
+			u64::try_from(max_decompressed_block_size)
+				.unwrap_or(u64::MAX)
+				.saturating_add(1),
+		),
+		decompression_buffer,
+	)
+	.map_err(|e| de::DeError::custom_io("Failed to decompress block", e))?;
+	if decompression_buffer.len() > max_decompressed_block_size {
+		return Err(decompressed_block_too_large(max_decompressed_block_size));
+	}
+	if !left_in_block(decoder).is_empty() {
+		return Err(de::DeError::new(
+			"There's data left in the block after decompressing it entirely",
+		));
+	}
+	Ok(decompression_buffer)
+}
+
+/// The returned decoder refers to the prepared `dictionary` without
+/// borrowing it: the caller has to keep `dictionary` alive for as long as the
+/// decoder is used.
+#[cfg(feature = "zstandard")]
+fn zstandard_decoder<R: std::io::BufRead>(
+	reader: R,
+	dictionary: &Option<crate::object_container_file_encoding::ZstandardDictionary>,
+) -> Result<zstd::stream::read::Decoder<'static, R>, de::DeError> {
+	match dictionary {
+		None => zstd::stream::read::Decoder::with_buffer(reader),
+		Some(dictionary) => zstd::stream::read::Decoder::with_prepared_dictionary(
+			reader,
+			dictionary.decoder_dictionary(),
+		),
+	}
+	.map_err(de::DeError::io)
+}
+
+#[cfg(feature = "snappy")]
+fn snappy_decompress(
+	compressed_slice: &[u8],
+	decompression_buffer: &mut Vec<u8>,
+	max_decompressed_block_size: usize,
+) -> Result<(), de::DeError> {
+	fn snappy_to_de_error(snappy_error: snap::Error) -> de::DeError {
+		<de::DeError as serde::de::Error>::custom(format_args!(
+			"Snappy decompression error: {snappy_error}"
+		))
+	}
+	let decompressed_len =
+		snap::raw::decompress_len(compressed_slice).map_err(snappy_to_de_error)?;
+	if decompressed_len > max_decompressed_block_size {
+		return Err(decompressed_block_too_large(max_decompressed_block_size));
+	}
+	decompression_buffer.resize(decompressed_len, 0);
+	let written = snap::raw::Decoder::new()
+		.decompress(compressed_slice, decompression_buffer)
+		.map_err(snappy_to_de_error)?;
+	if written != decompression_buffer.len() {
+		return Err(de::DeError::new(
+			"Snappy decompression error: incorrect decompressed size",
+		));
+	}
+	Ok(())
+}
+
+#[cfg(feature = "snappy")]
+fn snappy_check_crc32(decompressed: &[u8], expected_crc32: [u8; 4]) -> Result<(), de::DeError> {
+	if crc32fast::hash(decompressed) != u32::from_be_bytes(expected_crc32) {
+		return Err(de::DeError::new(
+			"Incorrect extra CRC32 of decompressed data when using Snappy compression codec",
+		));
+	}
+	Ok(())
+}
+
 pub(super) enum DecompressionState<'s, R: de::read::take::Take> {
 	Null {
 		deserializer_state: DeserializerState<'s, R::Take>,
@@ -154,7 +388,8 @@ pub(super) enum DecompressionState<'s, R: de::read::take::Take> {
 		feature = "deflate",
 		feature = "bzip2",
 		feature = "xz",
-		feature = "zstandard"
+		feature = "zstandard",
+		feature = "lz4"
 	))]
 	BufReader {
 		deserializer_state: DeserializerState<
@@ -166,7 +401,6 @@ pub(super) enum DecompressionState<'s, R: de::read::take::Take> {
 	/// Any decompression protocol that is decompressed as we build the
 	/// `DecompressionState` It is decompressed in a `Vec<u8>` and then we use a
 	/// `Cursor` to plug it in a `Deserializer`
-	#[cfg(feature = "snappy")]
 	DecompressedOnConstruction {
 		deserializer_state: DeserializerState<'s, de::read::ReaderRead<std::io::Cursor<Vec<u8>>>>,
 		source_reader: R,
@@ -180,8 +414,15 @@ pub(super) enum DecompressionReaderForBufReader<R: std::io::BufRead> {
 	Bzip2(bzip2::bufread::BzDecoder<R>),
 	#[cfg(feature = "xz")]
 	Xz(xz2::bufread::XzDecoder<R>),
+	/// The dictionary is kept alongside the decoder (and dropped after it)
+	/// because the decoder refers to it
 	#[cfg(feature = "zstandard")]
-	Zstandard(zstd::stream::read::Decoder<'static, R>),
+	Zstandard(
+		zstd::stream::read::Decoder<'static, R>,
+		Option<crate::object_container_file_encoding::ZstandardDictionary>,
+	),
+	#[cfg(feature = "lz4")]
+	Lz4(lz4_flex::frame::FrameDecoder<R>),
 }
 
 impl<'s, R: de::read::take::Take> DecompressionState<'s, R> {
@@ -200,7 +441,8 @@ impl<'s, R: de::read::take::Take> DecompressionState<'s, R> {
 				feature = "deflate",
 				feature = "bzip2",
 				feature = "xz",
-				feature = "zstandard"
+				feature = "zstandard",
+				feature = "lz4"
 			))]
 			DecompressionState::BufReader {
 				deserializer_state,
@@ -216,7 +458,7 @@ impl<'s, R: de::read::take::Take> DecompressionState<'s, R> {
 						#[cfg(feature = "xz")]
 						DecompressionReaderForBufReader::Xz(reader) => reader.into_inner(),
 						#[cfg(feature = "zstandard")]
-						DecompressionReaderForBufReader::Zstandard(mut reader) => {
+						DecompressionReaderForBufReader::Zstandard(mut reader, _dictionary) => {
 							// With zstandard, we need to manually drive the reader to the end by
 							// asking to deserialize the rest of the data. If the serialized avro is
 							// correct, this should not yield anything, but if we don't, it won't
@@ -241,13 +483,34 @@ impl<'s, R: de::read::take::Take> DecompressionState<'s, R> {
 							}
 							reader.finish()
 						}
+						#[cfg(feature = "lz4")]
+						DecompressionReaderForBufReader::Lz4(mut reader) => {
+							// Same as zstandard: the end of the frame is only read if we ask
+							// for more data than there is
+							let mut drive_reader_to_end_buf = [0];
+							let read =
+								std::io::Read::read(&mut reader, &mut drive_reader_to_end_buf)
+									.map_err(|e| {
+										de::DeError::custom_io(
+											"LZ4 error when driving decompressor to end",
+											e,
+										)
+									})?;
+							if read != 0 {
+								return Err(de::DeError::new(
+									"LZ4 decompression error: There's \
+									decompressed data left in the \
+									block after reading the whole avro block out of it",
+								));
+							}
+							reader.into_inner()
+						}
 					})
 					.into_left_after_take()?,
 					config,
 					decompression_buffer,
 				)
 			}
-			#[cfg(feature = "snappy")]
 			DecompressionState::DecompressedOnConstruction {
 				deserializer_state,
 				source_reader,
@@ -269,7 +532,9 @@ macro_rules! dispatch {
 			#[cfg(feature = "xz")]
 			DecompressionReaderForBufReader::Xz(reader) => reader.$function($($arg)*),
 			#[cfg(feature = "zstandard")]
-			DecompressionReaderForBufReader::Zstandard(reader) => reader.$function($($arg)*),
+			DecompressionReaderForBufReader::Zstandard(reader, _) => reader.$function($($arg)*),
+			#[cfg(feature = "lz4")]
+			DecompressionReaderForBufReader::Lz4(reader) => reader.$function($($arg)*),
 		}
 	};
 }
@@ -0,0 +1,225 @@
+use super::*;
+
+impl Reader<de::read::SliceRead<'static>> {
+	/// Memory-map the file at `path` and initialize a [`MmapReader`] over it
+	///
+	/// Contrary to [`from_slice`](Reader::from_slice), the returned
+	/// [`MmapReader`] owns the mapping, so there is no buffer whose lifetime
+	/// the caller would need to manage. Records may still borrow from the
+	/// file (or from the current decompressed block if the file is
+	/// compressed) via [`for_each_in_block`](MmapReader::for_each_in_block).
+	///
+	/// # Safety
+	///
+	/// The file must not be modified (including truncated) by this or
+	/// another process while the returned [`MmapReader`] is alive: see
+	/// [`memmap2::Mmap::map`].
+	pub unsafe fn open_mmap(
+		path: impl AsRef<std::path::Path>,
+	) -> Result<MmapReader, OpenMmapError> {
+		let file = std::fs::File::open(path).map_err(OpenMmapError::Io)?;
+		// Safety: upheld by the caller as per the contract of this function
+		let mmap = unsafe { memmap2::Mmap::map(&file) }.map_err(OpenMmapError::Io)?;
+		// Safety: the mapping is stored in the `MmapReader` alongside the reader,
+		// and dropped after it. The fake 'static lifetime never escapes the
+		// `MmapReader`: everything that borrows from the mapping is bound to a
+		// borrow of the `MmapReader`.
+		let slice: &'static [u8] = unsafe { std::slice::from_raw_parts(mmap.as_ptr(), mmap.len()) };
+		let reader = Reader::from_slice(slice).map_err(OpenMmapError::FailedToInitializeReader)?;
+		Ok(MmapReader {
+			reader,
+			decompression_buffer: Vec::new(),
+			_mmap: mmap,
+		})
+	}
+}
+
+/// Reader for a memory-mapped [object container file](https://avro.apache.org/docs/current/specification/#object-container-files)
+///
+/// To be constructed via [`Reader::open_mmap`].
+///
+/// # Example
+/// ```
+/// use serde_avro_fast::object_container_file_encoding::{self, Compression, Reader};
+///
+/// #[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+/// struct SchemaRecord<'a> {
+/// 	a: i64,
+/// 	b: &'a str,
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let schema: serde_avro_fast::Schema = r#"{
+/// 	"type": "record",
+/// 	"name": "test",
+/// 	"fields": [{ "name": "a", "type": "long" }, { "name": "b", "type": "string" }]
+/// }"#
+/// .parse()?;
+/// let path = std::env::temp_dir().join("serde_avro_fast_open_mmap_doctest.avro");
+/// std::fs::write(
+/// 	&path,
+/// 	object_container_file_encoding::write_all(
+/// 		&schema,
+/// 		Compression::Deflate {
+/// 			level: object_container_file_encoding::CompressionLevel::default(),
+/// 		},
+/// 		Vec::new(),
+/// 		[SchemaRecord { a: 1, b: "foo" }, SchemaRecord { a: 2, b: "bar" }],
+/// 	)?,
+/// )?;
+///
+/// // Safety: the file is not modified while it is mapped
+/// let mut reader = unsafe { Reader::open_mmap(&path)? };
+/// let mut total_len = 0;
+/// reader.for_each_in_block(|block| {
+/// 	for record in block.deserialize::<SchemaRecord>() {
+/// 		// `record.b` borrows from the decompressed block
+/// 		total_len += record?.b.len();
+/// 	}
+/// 	Ok::<_, serde_avro_fast::de::DeError>(())
+/// })?;
+/// assert_eq!(total_len, 6);
+/// # std::fs::remove_file(&path)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct MmapReader {
+	// the 'static here is fake, it in fact is bound to `_mmap` not being dropped
+	// struct fields are dropped in order of declaration, so this is dropped before
+	// `_mmap`
+	reader: Reader<de::read::SliceRead<'static>>,
+	decompression_buffer: Vec<u8>,
+	_mmap: memmap2::Mmap,
+}
+
+/// Errors that may happen when attempting to [`open_mmap`](Reader::open_mmap)
+#[derive(Debug, thiserror::Error)]
+pub enum OpenMmapError {
+	/// Failed to open or to memory-map the file
+	#[error("Failed to memory-map object container file: {}", _0)]
+	Io(std::io::Error),
+	/// Failed to read the header of the file
+	#[error("Failed to initialize reader: {}", _0)]
+	FailedToInitializeReader(FailedToInitializeReader),
+}
+
+impl MmapReader {
+	/// Iterator over the deserialized values
+	///
+	/// See [`Reader::deserialize`].
+	pub fn deserialize<T: DeserializeOwned>(
+		&mut self,
+	) -> impl Iterator<Item = Result<T, DeError>> + '_ {
+		self.reader.deserialize()
+	}
+
+	/// Call `f` with each of the remaining blocks of the file, allowing
+	/// records to borrow from the block
+	///
+	/// If the file is not compressed, records borrow directly from the
+	/// mapping. Otherwise, each block is decompressed into a buffer that is
+	/// reused across blocks, and records borrow from that buffer: they can
+	/// hence not outlive the call to `f`.
+	///
+	/// Records of a block that `f` does not deserialize are skipped.
+	///
+	/// This can only be called between blocks: if objects of the current
+	/// block were deserialized via [`deserialize`](MmapReader::deserialize),
+	/// all the objects of that block need to be deserialized before calling
+	/// this.
+	pub fn for_each_in_block<F, E>(&mut self, mut f: F) -> Result<(), E>
+	where
+		F: FnMut(&mut BlockDeserializer<'_, '_>) -> Result<(), E>,
+		E: From<DeError>,
+	{
+		let compression = self.reader.compression.clone();
+		while let Some(raw_block) = self.reader.next_raw_block()? {
+			let decompressed = compression.decompress_block(
+				&raw_block.compressed_bytes,
+				&mut self.decompression_buffer,
+				self.reader.max_decompressed_block_size,
+			)?;
+			// Deserialize with the same config as the reader
+			let config = match self.reader.reader_state {
+				ReaderState::NotInBlock { ref config, .. } => config.clone(),
+				ReaderState::InBlock { .. } | ReaderState::Broken => {
+					unreachable!("Reading a raw block leaves the reader between blocks")
+				}
+			};
+			f(&mut BlockDeserializer {
+				deserializer_state: de::DeserializerState::with_config(
+					de::read::SliceRead::new(decompressed),
+					config,
+				),
+				n_objects_remaining: raw_block.n_objects,
+			})?;
+		}
+		Ok(())
+	}
+
+	/// Set the maximum size that a block is allowed to decompress to
+	///
+	/// See [`Reader::set_max_decompressed_block_size`].
+	pub fn set_max_decompressed_block_size(&mut self, max_decompressed_block_size: usize) {
+		self.reader
+			.set_max_decompressed_block_size(max_decompressed_block_size);
+	}
+
+	/// Get the schema used for deserialization
+	///
+	/// It was read from the header of the object container file.
+	pub fn schema(&self) -> &Arc<Schema> {
+		self.reader.schema()
+	}
+
+	/// Get the codec the blocks of this file are compressed with
+	///
+	/// It was read from the header of the object container file.
+	pub fn compression_codec(&self) -> CompressionCodec {
+		self.reader.compression_codec()
+	}
+
+	/// Get all the entries of the header metadata, including the
+	/// avro-reserved ones
+	///
+	/// See [`Metadata`].
+	pub fn metadata(&self) -> &Metadata {
+		self.reader.metadata()
+	}
+}
+
+/// Deserializer for the records of a single block, provided by
+/// [`MmapReader::for_each_in_block`]
+///
+/// Records may borrow from the (decompressed) block for `'b`.
+pub struct BlockDeserializer<'s, 'b> {
+	deserializer_state: de::DeserializerState<'s, de::read::SliceRead<'b>>,
+	n_objects_remaining: u64,
+}
+
+impl<'s, 'b> BlockDeserializer<'s, 'b> {
+	/// Attempt to deserialize the next record of the block
+	///
+	/// Returns `None` once all the records of the block were deserialized.
+	pub fn deserialize_next<T: Deserialize<'b>>(&mut self) -> Result<Option<T>, DeError> {
+		match self.n_objects_remaining.checked_sub(1) {
+			None => Ok(None),
+			Some(n_objects_remaining) => {
+				self.n_objects_remaining = n_objects_remaining;
+				T::deserialize(self.deserializer_state.deserializer()).map(Some)
+			}
+		}
+	}
+
+	/// Iterator over the remaining records of the block
+	pub fn deserialize<'r, T: Deserialize<'b>>(
+		&'r mut self,
+	) -> impl Iterator<Item = Result<T, DeError>> + use<'r, 's, 'b, T> {
+		std::iter::from_fn(|| self.deserialize_next().transpose())
+	}
+
+	/// Number of records of the block that were not deserialized yet
+	pub fn n_objects_remaining(&self) -> u64 {
+		self.n_objects_remaining
+	}
+}
@@ -1,11 +1,28 @@
+#[cfg(feature = "tokio")]
+mod async_reader;
 mod decompression;
+#[cfg(feature = "memmap2")]
+mod mmap;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod range;
+mod resync;
+
+#[cfg(feature = "tokio")]
+pub use async_reader::AsyncReader;
+#[cfg(feature = "memmap2")]
+pub use mmap::{BlockDeserializer, MmapReader, OpenMmapError};
+pub use resync::{CorruptBlock, ResyncReader};
 
 use crate::{
 	de::{
 		read::{Read, ReadSlice},
 		DeError,
 	},
-	object_container_file_encoding::{CompressionCodec, Metadata, HEADER_CONST, METADATA_SCHEMA},
+	object_container_file_encoding::{
+		CodecRegistry, Compression, CompressionCodec, Metadata, RawBlock, HEADER_CONST,
+		METADATA_SCHEMA,
+	},
 	*,
 };
 
@@ -71,7 +88,9 @@ pub struct Reader<R: de::read::take::Take> {
 	// the 'static here is fake, it in fact is bound to `Schema` not being dropped
 	// struct fields are dropped in order of declaration, so this is dropped before schema
 	reader_state: ReaderState<'static, R>,
-	compression_codec: CompressionCodec,
+	/// The compression blocks are decompressed with: this holds everything
+	/// the header tells about it (e.g. zstandard dictionary)
+	compression: Compression,
 	sync_marker: [u8; 16],
 	/// If we hit an IO error, we yield it once, then for following calls to
 	/// `deserialize_next` we pretend that we reached EOF. This is because IO
@@ -79,6 +98,11 @@ pub struct Reader<R: de::read::take::Take> {
 	/// yielding the same error over and over again if the caller happens to try
 	/// to recover from deserialization errors.
 	pretend_eof_because_yielded_unrecoverable_error: bool,
+	metadata: Metadata,
+	/// Set if built with [`from_range`](Reader::from_range)
+	range_end: Option<range::RangeEnd<R>>,
+	/// See [`set_max_decompressed_block_size`](Reader::set_max_decompressed_block_size)
+	pub(super) max_decompressed_block_size: usize,
 	/// This has to be stored in here and be the last field because the Reader
 	/// is self referential: it stores references to inside Schema.
 	schema: Arc<Schema>,
@@ -149,38 +173,42 @@ where
 	/// Note that if your reader is not a slice reader, you should provide a
 	/// type `M` that implements [`serde::de::DeserializeOwned`], otherwise
 	/// deserialization may fail.
-	pub fn new_and_metadata<'de, M>(mut reader: R) -> Result<(Self, M), FailedToInitializeReader>
+	pub fn new_and_metadata<'de, M>(reader: R) -> Result<(Self, M), FailedToInitializeReader>
 	where
 		R: ReadSlice<'de>,
 		M: Deserialize<'de>,
 	{
-		if reader
-			.read_const_size_buf::<4>()
-			.map_err(FailedToInitializeReader::FailedToDeserializeHeader)?
-			!= HEADER_CONST
-		{
-			return Err(FailedToInitializeReader::NotAvroObjectContainerFile);
-		}
+		Self::new_and_metadata_with_codecs(reader, &CodecRegistry::new())
+	}
 
-		let mut metadata_deserializer_config =
-			de::DeserializerConfig::from_schema_node(METADATA_SCHEMA);
-		metadata_deserializer_config.max_seq_size = 1_000;
-		let mut metadata_deserializer_state =
-			de::DeserializerState::with_config(reader, metadata_deserializer_config);
-		let metadata: Metadata<String, M> =
-			serde::Deserialize::deserialize(metadata_deserializer_state.deserializer())
-				.map_err(FailedToInitializeReader::FailedToDeserializeHeader)?;
-		reader = metadata_deserializer_state.into_reader();
-		let schema: Arc<Schema> = Arc::new(
-			metadata
-				.schema
-				.parse()
-				.map_err(FailedToInitializeReader::FailedToParseSchema)?,
-		);
-
-		let sync_marker = reader
-			.read_const_size_buf::<16>()
-			.map_err(FailedToInitializeReader::FailedToDeserializeHeader)?;
+	/// Build a `Reader`, resolving codecs that are not built into this crate
+	/// against `codecs`
+	///
+	/// See [`Codec`](super::Codec).
+	pub fn new_with_codecs<'de>(
+		reader: R,
+		codecs: &CodecRegistry,
+	) -> Result<Self, FailedToInitializeReader>
+	where
+		R: ReadSlice<'de>,
+	{
+		Self::new_and_metadata_with_codecs::<()>(reader, codecs).map(|(reader, ())| reader)
+	}
+
+	/// Build a `Reader`, also extracting custom metadata (as
+	/// [`new_and_metadata`](Self::new_and_metadata)), and resolving codecs
+	/// that are not built into this crate against `codecs` (as
+	/// [`new_with_codecs`](Self::new_with_codecs))
+	pub fn new_and_metadata_with_codecs<'de, M>(
+		reader: R,
+		codecs: &CodecRegistry,
+	) -> Result<(Self, M), FailedToInitializeReader>
+	where
+		R: ReadSlice<'de>,
+		M: Deserialize<'de>,
+	{
+		let (reader, header) = read_header(reader, codecs)?;
+		let schema = header.schema;
 
 		// Safety: we don't drop the schema until this is dropped
 		// This is useful to be able to store a DeserializerState directly in here,
@@ -195,12 +223,15 @@ where
 					config: de::DeserializerConfig::from_schema_node(schema_root),
 					decompression_buffer: Vec::new(),
 				},
-				compression_codec: metadata.codec,
-				sync_marker,
+				compression: header.compression,
+				sync_marker: header.sync_marker,
 				pretend_eof_because_yielded_unrecoverable_error: false,
+				metadata: header.metadata,
+				range_end: None,
+				max_decompressed_block_size: decompression::DEFAULT_MAX_DECOMPRESSED_BLOCK_SIZE,
 				schema,
 			},
-			metadata.user_metadata,
+			header.user_metadata,
 		))
 	}
 
@@ -323,6 +354,11 @@ where
 		<R as de::read::take::Take>::Take: ReadSlice<'de>,
 	{
 		loop {
+			if let ReaderState::NotInBlock { .. } = self.reader_state {
+				if self.past_range_end()? {
+					break Ok(None);
+				}
+			}
 			match &mut self.reader_state {
 				ReaderState::Broken => {
 					return Err(DeError::new(
@@ -355,11 +391,12 @@ where
 					let block_size: usize = block_size
 						.try_into()
 						.map_err(|_| DeError::new("Invalid container file block size in bytes"))?;
-					let codec_data = self.compression_codec.state(
+					let codec_data = self.compression.state(
 						reader,
 						config,
 						decompression_buffer,
 						block_size,
+						self.max_decompressed_block_size,
 					)?;
 					self.reader_state = ReaderState::InBlock {
 						codec_data,
@@ -370,25 +407,7 @@ where
 					codec_data,
 					n_objects_in_block,
 				} => match n_objects_in_block.checked_sub(1) {
-					None => match std::mem::replace(&mut self.reader_state, ReaderState::Broken) {
-						ReaderState::InBlock {
-							codec_data,
-							n_objects_in_block: _,
-						} => {
-							let (mut reader, config, decompression_buffer) =
-								codec_data.into_source_reader_and_config()?;
-							let sync_marker = reader.read_const_size_buf::<16>()?;
-							if sync_marker != self.sync_marker {
-								return Err(DeError::new("Incorrect sync marker at end of block"));
-							}
-							self.reader_state = ReaderState::NotInBlock {
-								reader,
-								config,
-								decompression_buffer,
-							}
-						}
-						_ => unreachable!(),
-					},
+					None => self.leave_finished_block()?,
 					Some(next_n_in_block) => {
 						*n_objects_in_block = next_n_in_block;
 						break match codec_data {
@@ -399,12 +418,12 @@ where
 								feature = "deflate",
 								feature = "bzip2",
 								feature = "xz",
-								feature = "zstandard"
+								feature = "zstandard",
+								feature = "lz4"
 							))]
 							DecompressionState::BufReader {
 								deserializer_state, ..
 							} => deserialize_seed.deserialize(deserializer_state.deserializer()),
-							#[cfg(feature = "snappy")]
 							DecompressionState::DecompressedOnConstruction {
 								deserializer_state,
 								..
@@ -417,12 +436,306 @@ where
 		}
 	}
 
+	/// Read the next block without decompressing or deserializing it
+	///
+	/// This can only be called between blocks: if objects of the current block
+	/// were deserialized, all the objects of that block need to be
+	/// deserialized before calling this.
+	///
+	/// The returned block borrows from the input if reading from a slice.
+	pub fn next_raw_block<'de>(&mut self) -> Result<Option<RawBlock<'de>>, DeError>
+	where
+		R: ReadSlice<'de>,
+		<R as de::read::take::Take>::Take: ReadSlice<'de>,
+	{
+		if self.pretend_eof_because_yielded_unrecoverable_error {
+			return Ok(None);
+		}
+		let res = self.next_raw_block_inner();
+		if let Err(ref de_error) = res {
+			if de_error.io_error().is_some() || matches!(self.reader_state, ReaderState::Broken) {
+				// Same as in `deserialize_seed_next`
+				self.pretend_eof_because_yielded_unrecoverable_error = true;
+			}
+		}
+		res
+	}
+
+	fn next_raw_block_inner<'de>(&mut self) -> Result<Option<RawBlock<'de>>, DeError>
+	where
+		R: ReadSlice<'de>,
+		<R as de::read::take::Take>::Take: ReadSlice<'de>,
+	{
+		match self.reader_state {
+			ReaderState::Broken => {
+				return Err(DeError::new(
+					"Object container file reader is broken after error",
+				))
+			}
+			ReaderState::InBlock {
+				n_objects_in_block: 0,
+				..
+			} => self.leave_finished_block()?,
+			ReaderState::InBlock { .. } => {
+				return Err(DeError::new(
+					"Can not read a raw block while in the middle of a block: \
+						all the objects of the current block should be deserialized first",
+				))
+			}
+			ReaderState::NotInBlock { .. } => {}
+		}
+		if self.past_range_end()? {
+			return Ok(None);
+		}
+		match &mut self.reader_state {
+			ReaderState::NotInBlock { reader, .. } => {
+				if reader
+					.fill_buf()
+					.map(|b| b.is_empty())
+					.map_err(DeError::io)?
+				{
+					// Reader is empty, we're done reading
+					return Ok(None);
+				}
+			}
+			_ => unreachable!(),
+		}
+		let (mut reader, config, decompression_buffer) =
+			match std::mem::replace(&mut self.reader_state, ReaderState::Broken) {
+				ReaderState::NotInBlock {
+					reader,
+					config,
+					decompression_buffer,
+				} => (reader, config, decompression_buffer),
+				_ => unreachable!(),
+			};
+		let n_objects: i64 = reader.read_varint()?;
+		let n_objects: u64 = n_objects
+			.try_into()
+			.map_err(|_| DeError::new("Invalid container file block object count"))?;
+		let block_size: i64 = reader.read_varint()?;
+		let block_size: usize = block_size
+			.try_into()
+			.map_err(|_| DeError::new("Invalid container file block size in bytes"))?;
+		let compressed_bytes = reader.read_slice(block_size, CowVisitor)?;
+		let sync_marker = reader.read_const_size_buf::<16>()?;
+		if sync_marker != self.sync_marker {
+			return Err(DeError::new("Incorrect sync marker at end of block"));
+		}
+		self.reader_state = ReaderState::NotInBlock {
+			reader,
+			config,
+			decompression_buffer,
+		};
+		Ok(Some(RawBlock {
+			n_objects,
+			compressed_bytes,
+			codec: self.compression.codec(),
+			#[cfg(feature = "zstandard")]
+			zstandard_dictionary: match &self.compression {
+				Compression::Zstandard { dictionary, .. } => dictionary.clone(),
+				_ => None,
+			},
+		}))
+	}
+
+	/// Once all the objects of the current block have been deserialized, check
+	/// the sync marker and get ready to read the next block
+	fn leave_finished_block<'de>(&mut self) -> Result<(), DeError>
+	where
+		R: ReadSlice<'de>,
+		<R as de::read::take::Take>::Take: ReadSlice<'de>,
+	{
+		match std::mem::replace(&mut self.reader_state, ReaderState::Broken) {
+			ReaderState::InBlock {
+				codec_data,
+				n_objects_in_block: 0,
+			} => {
+				let (mut reader, config, decompression_buffer) =
+					codec_data.into_source_reader_and_config()?;
+				let sync_marker = reader.read_const_size_buf::<16>()?;
+				if sync_marker != self.sync_marker {
+					return Err(DeError::new("Incorrect sync marker at end of block"));
+				}
+				self.reader_state = ReaderState::NotInBlock {
+					reader,
+					config,
+					decompression_buffer,
+				};
+				Ok(())
+			}
+			_ => unreachable!(),
+		}
+	}
+
+	/// Set the maximum size that a block is allowed to decompress to when it
+	/// needs to be decompressed whole
+	///
+	/// If a block turns out to decompress to more than this, we will throw an
+	/// error instead. This is to avoid running out of memory on malformed (or
+	/// malicious) files, as a small compressed block may decompress to a
+	/// huge one.
+	///
+	/// This applies to the Snappy and custom codecs (which don't support
+	/// decompressing a block as it gets deserialized), as well as to all
+	/// codecs when blocks are decompressed whole (e.g. by
+	/// [`par_deserialize`](Reader::par_deserialize) or
+	/// [`concat`](super::concat())).
+	///
+	/// Default for this is 512MiB.
+	pub fn set_max_decompressed_block_size(&mut self, max_decompressed_block_size: usize) {
+		self.max_decompressed_block_size = max_decompressed_block_size;
+	}
+
 	/// Get the schema used for deserialization
 	///
 	/// It was read from the header of the object container file.
 	pub fn schema(&self) -> &Arc<Schema> {
 		&self.schema
 	}
+
+	/// Get the codec the blocks of this file are compressed with
+	///
+	/// It was read from the header of the object container file.
+	pub fn compression_codec(&self) -> CompressionCodec {
+		self.compression.codec()
+	}
+
+	/// Get the [`Compression`] the blocks of this file are compressed with,
+	/// with the default compression level
+	///
+	/// As opposed to [`compression_codec`](Self::compression_codec), this
+	/// also carries the parameters that were read from the header of the
+	/// file and that are required to decompress blocks, such as the
+	/// zstandard dictionary. This is typically useful to write another file
+	/// whose blocks can be copied as-is from this one.
+	pub fn compression(&self) -> &Compression {
+		&self.compression
+	}
+
+	/// Get all the entries of the header metadata, including the
+	/// avro-reserved ones
+	///
+	/// See [`Metadata`].
+	pub fn metadata(&self) -> &Metadata {
+		&self.metadata
+	}
+}
+
+/// What the header of an object container file tells us about the rest of
+/// the file
+pub(super) struct Header<M> {
+	pub(super) schema: Arc<Schema>,
+	pub(super) compression: Compression,
+	pub(super) sync_marker: [u8; 16],
+	pub(super) metadata: Metadata,
+	pub(super) user_metadata: M,
+}
+
+/// Read the header of an object container file, leaving the reader at the
+/// start of the first block
+pub(super) fn read_header<'de, R, M>(
+	mut reader: R,
+	codecs: &CodecRegistry,
+) -> Result<(R, Header<M>), FailedToInitializeReader>
+where
+	R: ReadSlice<'de>,
+	M: Deserialize<'de>,
+{
+	if reader
+		.read_const_size_buf::<4>()
+		.map_err(FailedToInitializeReader::FailedToDeserializeHeader)?
+		!= HEADER_CONST
+	{
+		return Err(FailedToInitializeReader::NotAvroObjectContainerFile);
+	}
+
+	let mut metadata_deserializer_config =
+		de::DeserializerConfig::from_schema_node(METADATA_SCHEMA);
+	metadata_deserializer_config.max_seq_size = MAX_METADATA_ENTRIES;
+	let mut metadata_deserializer_state =
+		de::DeserializerState::with_config(reader, metadata_deserializer_config);
+	let (metadata, header_metadata) =
+		super::read_metadata::<M, _>(metadata_deserializer_state.deserializer())
+			.map_err(FailedToInitializeReader::FailedToDeserializeHeader)?;
+	reader = metadata_deserializer_state.into_reader();
+	let schema: Arc<Schema> = Arc::new(
+		header_metadata
+			.schema
+			.parse()
+			.map_err(FailedToInitializeReader::FailedToParseSchema)?,
+	);
+
+	#[cfg_attr(not(feature = "zstandard"), allow(unused_mut))]
+	let mut compression = codecs
+		.resolve(&header_metadata.codec)
+		.map_err(FailedToInitializeReader::FailedToDeserializeHeader)?;
+	#[cfg(feature = "zstandard")]
+	if let Compression::Zstandard {
+		ref mut dictionary, ..
+	} = compression
+	{
+		*dictionary = header_metadata
+			.zstandard_dictionary
+			.map(|super::RawBytes(bytes)| super::ZstandardDictionary::new(bytes));
+	}
+
+	let sync_marker = reader
+		.read_const_size_buf::<16>()
+		.map_err(FailedToInitializeReader::FailedToDeserializeHeader)?;
+
+	Ok((
+		reader,
+		Header {
+			schema,
+			compression,
+			sync_marker,
+			metadata,
+			user_metadata: header_metadata.user_metadata,
+		},
+	))
+}
+
+/// Read the header, data and sync marker of a block from a slice without
+/// decompressing it
+///
+/// Returns the number of objects in the block and its (compressed) data.
+fn split_raw_block<'a>(
+	slice: &mut de::read::SliceRead<'a>,
+	expected_sync_marker: &[u8; 16],
+) -> Result<(usize, &'a [u8]), DeError> {
+	let n_objects: i64 = slice.read_varint()?;
+	let n_objects: usize = n_objects
+		.try_into()
+		.map_err(|_| DeError::new("Invalid container file block object count"))?;
+	let block_size: i64 = slice.read_varint()?;
+	let block_size: usize = block_size
+		.try_into()
+		.map_err(|_| DeError::new("Invalid container file block size in bytes"))?;
+	let data = slice
+		.remaining()
+		.get(..block_size)
+		.ok_or_else(|| DeError::new("Read block size larger than original slice"))?;
+	slice.skip_bytes(block_size as u64)?;
+	if slice.read_const_size_buf::<16>()? != *expected_sync_marker {
+		return Err(DeError::new("Incorrect sync marker at end of block"));
+	}
+	Ok((n_objects, data))
+}
+
+/// Maximum number of entries in the metadata map of the header
+const MAX_METADATA_ENTRIES: usize = 1_000;
+
+/// Borrows if reading from a slice, copies otherwise
+struct CowVisitor;
+impl<'de> de::read::ReadVisitor<'de> for CowVisitor {
+	type Value = std::borrow::Cow<'de, [u8]>;
+	fn visit(self, bytes: &[u8]) -> Result<Self::Value, DeError> {
+		Ok(std::borrow::Cow::Owned(bytes.to_owned()))
+	}
+	fn visit_borrowed(self, bytes: &'de [u8]) -> Result<Self::Value, DeError> {
+		Ok(std::borrow::Cow::Borrowed(bytes))
+	}
 }
 
 enum ReaderState<'s, R: de::read::take::Take> {
@@ -0,0 +1,203 @@
+use super::*;
+
+use rayon::iter::{Either, IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+impl<'a> Reader<de::read::SliceRead<'a>> {
+	/// Deserialize the remaining blocks in parallel on the
+	/// [rayon](https://docs.rs/rayon) thread pool, yielding all the records of
+	/// each block at once
+	///
+	/// The file is first split into blocks using their headers, without
+	/// decompressing them. Each block is then decompressed and deserialized on
+	/// the thread pool. Blocks are yielded in order.
+	///
+	/// If some records of the current block were already deserialized via
+	/// [`deserialize_next`](Reader::deserialize_next), the rest of that block
+	/// is deserialized sequentially and yielded first.
+	///
+	/// This consumes the whole `Reader`: subsequent calls to
+	/// `deserialize_next` will return `None`. If the file is found to be
+	/// corrupted while splitting it into blocks, the corresponding error is
+	/// yielded after all the valid blocks that precede it.
+	///
+	/// # Example
+	/// ```
+	/// use rayon::iter::ParallelIterator;
+	///
+	/// # let avro_object_container_file_encoded: &[u8] = &[
+	/// # 	79, 98, 106, 1, 4, 22, 97, 118, 114, 111, 46, 115, 99, 104, 101, 109, 97, 222, 1, 123, 34,
+	/// # 	116, 121, 112, 101, 34, 58, 34, 114, 101, 99, 111, 114, 100, 34, 44, 34, 110, 97, 109, 101,
+	/// # 	34, 58, 34, 116, 101, 115, 116, 34, 44, 34, 102, 105, 101, 108, 100, 115, 34, 58, 91, 123,
+	/// # 	34, 110, 97, 109, 101, 34, 58, 34, 97, 34, 44, 34, 116, 121, 112, 101, 34, 58, 34, 108,
+	/// # 	111, 110, 103, 34, 44, 34, 100, 101, 102, 97, 117, 108, 116, 34, 58, 52, 50, 125, 44, 123,
+	/// # 	34, 110, 97, 109, 101, 34, 58, 34, 98, 34, 44, 34, 116, 121, 112, 101, 34, 58, 34, 115,
+	/// # 	116, 114, 105, 110, 103, 34, 125, 93, 125, 20, 97, 118, 114, 111, 46, 99, 111, 100, 101,
+	/// # 	99, 8, 110, 117, 108, 108, 0, 94, 61, 54, 221, 190, 207, 108, 180, 158, 57, 114, 40, 173,
+	/// # 	199, 228, 239, 4, 20, 54, 6, 102, 111, 111, 84, 6, 98, 97, 114, 94, 61, 54, 221, 190, 207,
+	/// # 	108, 180, 158, 57, 114, 40, 173, 199, 228, 239,
+	/// # ];
+	/// #[derive(serde_derive::Deserialize, Debug, PartialEq, Eq)]
+	/// struct SchemaRecord {
+	/// 	a: i64,
+	/// 	b: String,
+	/// }
+	///
+	/// let mut reader = serde_avro_fast::object_container_file_encoding::Reader::from_slice(
+	/// 	avro_object_container_file_encoded,
+	/// )
+	/// .expect("Failed to initialize reader");
+	///
+	/// let blocks: Vec<Vec<SchemaRecord>> = reader
+	/// 	.par_deserialize_blocks::<SchemaRecord>()
+	/// 	.collect::<Result<_, _>>()
+	/// 	.expect("Failed to deserialize a block");
+	///
+	/// assert_eq!(
+	/// 	blocks,
+	/// 	[[
+	/// 		SchemaRecord {
+	/// 			a: 27,
+	/// 			b: "foo".to_owned()
+	/// 		},
+	/// 		SchemaRecord {
+	/// 			a: 42,
+	/// 			b: "bar".to_owned()
+	/// 		},
+	/// 	]]
+	/// );
+	/// ```
+	pub fn par_deserialize_blocks<T: DeserializeOwned + Send + 'a>(
+		&mut self,
+	) -> impl IndexedParallelIterator<Item = Result<Vec<T>, DeError>> + 'a {
+		let mut blocks = Vec::new();
+		if let Some(current_block_rest) = self.deserialize_current_block_rest() {
+			blocks.push(Block::Deserialized(current_block_rest));
+		}
+		let config = match self.pretend_eof_because_yielded_unrecoverable_error {
+			false => self.split_remaining_blocks(&mut blocks),
+			true => None,
+		};
+		// The config references the schema, so the schema has to be kept alive
+		// alongside it
+		let config_and_schema = config.map(|config| (config, self.schema.clone()));
+
+		let compression = self.compression.clone();
+		let max_decompressed_block_size = self.max_decompressed_block_size;
+		blocks
+			.into_par_iter()
+			.map_init(Vec::new, move |decompression_buffer, block| match block {
+				Block::Deserialized(res) => res,
+				Block::Raw { n_objects, data } => {
+					let (config, _schema) = config_and_schema
+						.as_ref()
+						.expect("Raw blocks are only split from a reader that has a config");
+					deserialize_block(
+						config,
+						n_objects,
+						compression.decompress_block(
+							data,
+							decompression_buffer,
+							max_decompressed_block_size,
+						)?,
+					)
+				}
+			})
+	}
+
+	/// Deserialize the remaining records in parallel on the
+	/// [rayon](https://docs.rs/rayon) thread pool
+	///
+	/// This is [`par_deserialize_blocks`](Reader::par_deserialize_blocks),
+	/// flattened. Records keep their order if they are `collect`ed.
+	pub fn par_deserialize<T: DeserializeOwned + Send + 'a>(
+		&mut self,
+	) -> impl ParallelIterator<Item = Result<T, DeError>> + 'a {
+		self.par_deserialize_blocks()
+			.flat_map_iter(|block_res| match block_res {
+				Ok(block) => Either::Left(block.into_iter().map(Ok)),
+				Err(de_error) => Either::Right(std::iter::once(Err(de_error))),
+			})
+	}
+
+	/// If we are in the middle of a block, deserialize the rest of it
+	fn deserialize_current_block_rest<T: DeserializeOwned>(
+		&mut self,
+	) -> Option<Result<Vec<T>, DeError>> {
+		let mut current_block_rest = Vec::new();
+		let res = loop {
+			match self.reader_state {
+				ReaderState::InBlock {
+					n_objects_in_block: 0,
+					..
+				} => break self.leave_finished_block(),
+				ReaderState::InBlock { .. } => match self.deserialize_next() {
+					Ok(Some(value)) => current_block_rest.push(value),
+					Ok(None) => unreachable!("There are objects left in this block"),
+					Err(de_error) => break Err(de_error),
+				},
+				ReaderState::NotInBlock { .. } | ReaderState::Broken => {
+					return None;
+				}
+			}
+		};
+		if res.is_err() {
+			self.pretend_eof_because_yielded_unrecoverable_error = true;
+		}
+		Some(res.map(|()| current_block_rest))
+	}
+
+	/// Split the rest of the file into blocks, leaving the reader at EOF
+	///
+	/// Returns the configuration that blocks should be deserialized with, if
+	/// there were remaining blocks to split
+	fn split_remaining_blocks<T>(
+		&mut self,
+		blocks: &mut Vec<Block<'a, T>>,
+	) -> Option<de::DeserializerConfig<'static>> {
+		let (mut slice, config) = match self.reader_state {
+			ReaderState::NotInBlock {
+				ref mut reader,
+				ref config,
+				..
+			} => (
+				std::mem::replace(reader, de::read::SliceRead::new(&[])),
+				config.clone(),
+			),
+			ReaderState::InBlock { .. } | ReaderState::Broken => return None,
+		};
+		while !slice.remaining().is_empty() {
+			match split_raw_block(&mut slice, &self.sync_marker) {
+				Ok((n_objects, data)) => blocks.push(Block::Raw { n_objects, data }),
+				Err(de_error) => {
+					self.pretend_eof_because_yielded_unrecoverable_error = true;
+					blocks.push(Block::Deserialized(Err(de_error)));
+					break;
+				}
+			}
+		}
+		Some(config)
+	}
+}
+
+enum Block<'a, T> {
+	Deserialized(Result<Vec<T>, DeError>),
+	Raw { n_objects: usize, data: &'a [u8] },
+}
+
+fn deserialize_block<T: DeserializeOwned>(
+	config: &de::DeserializerConfig<'_>,
+	n_objects: usize,
+	decompressed: &[u8],
+) -> Result<Vec<T>, DeError> {
+	let mut deserializer_state =
+		de::DeserializerState::with_config(de::read::SliceRead::new(decompressed), config.clone());
+	let values = (0..n_objects)
+		.map(|_| T::deserialize(deserializer_state.deserializer()))
+		.collect::<Result<Vec<T>, DeError>>()?;
+	if !deserializer_state.into_reader().remaining().is_empty() {
+		return Err(DeError::new(
+			"There's data left in the block after deserializing it entirely",
+		));
+	}
+	Ok(values)
+}
@@ -0,0 +1,150 @@
+use super::*;
+
+use std::io::{BufRead, Seek, SeekFrom};
+
+/// Where a [`Reader`] built with [`Reader::from_range`] should stop
+pub(super) struct RangeEnd<R> {
+	/// Blocks whose preceding sync marker starts at or after this offset are
+	/// not part of the range
+	end: u64,
+	/// Current offset of the reader in the file
+	position: fn(&mut R) -> std::io::Result<u64>,
+}
+
+impl<R: BufRead + Seek> Reader<de::read::ReaderRead<R>> {
+	/// Initialize a `Reader` that only reads the blocks of a byte range of an
+	/// object container file
+	///
+	/// This is useful to distribute the reading of a large file across
+	/// workers, each of which is assigned a byte range of the file. It follows
+	/// the semantics of Hadoop's `AvroInputFormat` splits: a block is part of
+	/// the range `start..end` if the sync marker that precedes it starts
+	/// within that range. That means that:
+	/// - The `Reader` starts reading after the first sync marker found at or
+	///   after `start`.
+	/// - The `Reader` keeps reading blocks until the one that crosses `end`,
+	///   included, even if that block ends past `end`.
+	///
+	/// Consequently, when ranges that cover a file without overlapping are
+	/// read this way, every block of the file is read exactly once.
+	///
+	/// The schema, compression and sync marker are taken from
+	/// `header_source`, which is typically a `Reader` over the same file that
+	/// was built only once, so that the header is not parsed again for every
+	/// range. `data_source` should be a fresh handle to the same file: this
+	/// will seek it to `start`.
+	///
+	/// # Example
+	/// ```
+	/// use serde_avro_fast::object_container_file_encoding::{self, Compression, Reader};
+	///
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// let schema: serde_avro_fast::Schema = r#""long""#.parse()?;
+	/// let file = object_container_file_encoding::write_all(
+	/// 	&schema,
+	/// 	Compression::Null,
+	/// 	Vec::new(),
+	/// 	0..1000,
+	/// )?;
+	///
+	/// let header_source = Reader::from_slice(&file)?;
+	/// let mid = file.len() as u64 / 2;
+	/// let mut decoded = Vec::new();
+	/// for (start, end) in [(0, mid), (mid, file.len() as u64)] {
+	/// 	let mut reader =
+	/// 		Reader::from_range(&header_source, std::io::Cursor::new(&file), start, end)?;
+	/// 	for value in reader.deserialize::<i64>() {
+	/// 		decoded.push(value?);
+	/// 	}
+	/// }
+	/// assert_eq!(decoded, (0..1000).collect::<Vec<i64>>());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn from_range<H: de::read::take::Take>(
+		header_source: &Reader<H>,
+		mut data_source: R,
+		start: u64,
+		end: u64,
+	) -> Result<Self, DeError> {
+		data_source
+			.seek(SeekFrom::Start(start))
+			.map_err(DeError::io)?;
+		skip_past_sync_marker(&mut data_source, &header_source.sync_marker).map_err(DeError::io)?;
+
+		let schema = header_source.schema.clone();
+		// Safety: we don't drop the schema until this is dropped
+		// (same as in `new_and_metadata`)
+		let schema_root = unsafe { schema.root_with_fake_static_lifetime() };
+
+		Ok(Self {
+			reader_state: ReaderState::NotInBlock {
+				reader: de::read::ReaderRead::new(data_source),
+				config: de::DeserializerConfig::from_schema_node(schema_root),
+				decompression_buffer: Vec::new(),
+			},
+			compression: header_source.compression.clone(),
+			sync_marker: header_source.sync_marker,
+			pretend_eof_because_yielded_unrecoverable_error: false,
+			metadata: header_source.metadata.clone(),
+			range_end: Some(RangeEnd {
+				end,
+				position: |reader| reader.inner_mut().stream_position(),
+			}),
+			max_decompressed_block_size: header_source.max_decompressed_block_size,
+			schema,
+		})
+	}
+}
+
+impl<R: de::read::take::Take> Reader<R> {
+	/// To be called when the reader is between blocks: whether the next block
+	/// is past the end of the range this reader was built for, if any
+	pub(super) fn past_range_end(&mut self) -> Result<bool, DeError> {
+		match (&self.range_end, &mut self.reader_state) {
+			(Some(range_end), ReaderState::NotInBlock { reader, .. }) => {
+				let block_start = (range_end.position)(reader).map_err(DeError::io)?;
+				// The sync marker that precedes the block starts 16 bytes before it
+				Ok(block_start >= range_end.end.saturating_add(16))
+			}
+			_ => Ok(false),
+		}
+	}
+}
+
+/// Advance `reader` right after the next occurrence of `sync_marker`, or to
+/// EOF if there is none
+fn skip_past_sync_marker(reader: &mut impl BufRead, sync_marker: &[u8; 16]) -> std::io::Result<()> {
+	// Last bytes of what was consumed so far, to find sync markers that span
+	// across two buffers
+	let mut carry: Vec<u8> = Vec::with_capacity(2 * sync_marker.len());
+	loop {
+		let buf = reader.fill_buf()?;
+		if buf.is_empty() {
+			return Ok(());
+		}
+		if !carry.is_empty() {
+			let carry_len = carry.len();
+			carry.extend_from_slice(&buf[..buf.len().min(sync_marker.len() - 1)]);
+			if let Some(pos) = carry
+				.windows(sync_marker.len())
+				.position(|window| window == &sync_marker[..])
+			{
+				reader.consume(pos + sync_marker.len() - carry_len);
+				return Ok(());
+			}
+			carry.truncate(carry_len);
+		}
+		if let Some(pos) = buf
+			.windows(sync_marker.len())
+			.position(|window| window == &sync_marker[..])
+		{
+			reader.consume(pos + sync_marker.len());
+			return Ok(());
+		}
+		let buf_len = buf.len();
+		carry.extend_from_slice(&buf[buf_len.saturating_sub(sync_marker.len() - 1)..]);
+		carry.drain(..carry.len().saturating_sub(sync_marker.len() - 1));
+		reader.consume(buf_len);
+	}
+}
@@ -0,0 +1,299 @@
+use super::*;
+
+/// A part of an object container file that was skipped by a [`ResyncReader`]
+#[derive(Debug)]
+pub struct CorruptBlock {
+	/// The range of bytes that was skipped, as offsets in the slice the
+	/// [`Reader`] was built from
+	pub skipped_range: std::ops::Range<usize>,
+	/// How many records were lost
+	///
+	/// If the block could be split from the rest of the file but some of its
+	/// records failed to be read, this is the number of records of the block
+	/// that were not yielded.
+	///
+	/// Otherwise, this is extrapolated from the average size of records in
+	/// the blocks that were read before, or `None` if no block was read
+	/// before.
+	pub estimated_lost_records: Option<u64>,
+	/// Why this part of the file was skipped
+	pub error: DeError,
+}
+
+impl<'a> Reader<de::read::SliceRead<'a>> {
+	/// Skip corrupt parts of the file instead of stopping, reporting them to
+	/// `on_corrupt_block`
+	///
+	/// By default, when the `Reader` encounters a corrupt block, it yields
+	/// the corresponding error then behaves as if it had reached the end of
+	/// the file, because it can't know where the next block starts.
+	///
+	/// The returned [`ResyncReader`] instead checks, before reading each
+	/// block, that its header is valid and that it ends with the sync marker
+	/// of the file. If not, it scans forward for the next occurrence of the
+	/// sync marker, skips everything up to it, and resumes reading the blocks
+	/// that follow. If a block looks valid but one of its records then fails
+	/// to deserialize, the error is yielded as usual, then the rest of the
+	/// block is skipped. In both cases, the skipped part of the file is
+	/// reported to `on_corrupt_block`.
+	///
+	/// This means that all the intact blocks of a file are read, including
+	/// those before a truncated last block (e.g. if the writer crashed).
+	///
+	/// Note that deserialization errors that are not due to corruption (e.g.
+	/// a type that does not match the schema) will also make the rest of the
+	/// corresponding block be skipped.
+	///
+	/// This is only available when reading from slices: checking a block
+	/// before reading it requires looking ahead up to its end, and skipping
+	/// the rest of a block after an error requires going back to where it
+	/// started, neither of which an `impl BufRead` allows without buffering
+	/// whole blocks.
+	pub fn resync_on_corrupt_blocks<F>(self, on_corrupt_block: F) -> ResyncReader<'a, F>
+	where
+		F: FnMut(CorruptBlock),
+	{
+		ResyncReader {
+			current_block: None,
+			n_objects_yielded_in_block: 0,
+			config: None,
+			n_bytes_in_valid_blocks: 0,
+			n_objects_in_valid_blocks: 0,
+			on_corrupt_block,
+			reader: self,
+		}
+	}
+}
+
+/// [`Reader`] that skips the corrupt parts of the file
+///
+/// See [`Reader::resync_on_corrupt_blocks`].
+pub struct ResyncReader<'a, F> {
+	/// The block we are currently reading, as checked by `check_next_block`
+	current_block: Option<CurrentBlock<'a>>,
+	n_objects_yielded_in_block: usize,
+	/// Used to recover the `NotInBlock` state if the current block fails
+	///
+	/// Same as in the [`Reader`], the 'static is fake: it is bound to the
+	/// schema of the reader, which is declared after this so that it is
+	/// dropped after this.
+	config: Option<de::DeserializerConfig<'static>>,
+	/// Used to estimate how many records were lost in corrupt parts of the
+	/// file
+	n_bytes_in_valid_blocks: usize,
+	n_objects_in_valid_blocks: usize,
+	on_corrupt_block: F,
+	reader: Reader<de::read::SliceRead<'a>>,
+}
+
+struct CurrentBlock<'a> {
+	start: usize,
+	/// Reader positioned right after the sync marker at the end of the block
+	end: de::read::SliceRead<'a>,
+	n_objects: usize,
+}
+
+impl<'a, F> ResyncReader<'a, F>
+where
+	F: FnMut(CorruptBlock),
+{
+	/// Iterator over the deserialized values
+	pub fn deserialize<'r, T: DeserializeOwned>(
+		&'r mut self,
+	) -> impl Iterator<Item = Result<T, DeError>> + use<'r, 'a, F, T> {
+		std::iter::from_fn(|| self.deserialize_next().transpose())
+	}
+
+	/// Iterator over the deserialized values
+	///
+	/// Note that this may fail if the provided `T` requires to borrow from the
+	/// input and the blocks are compressed. (`deserialize` typechecks that
+	/// we have `DeserializeOwned` to make sure that is never the case).
+	pub fn deserialize_borrowed<'r, T: Deserialize<'a>>(
+		&'r mut self,
+	) -> impl Iterator<Item = Result<T, DeError>> + use<'r, 'a, F, T> {
+		std::iter::from_fn(|| self.deserialize_next_borrowed().transpose())
+	}
+
+	/// Attempt to deserialize the next value
+	pub fn deserialize_next<T: DeserializeOwned>(&mut self) -> Result<Option<T>, DeError> {
+		self.deserialize_seed_next(PhantomData::<T>)
+	}
+
+	/// Attempt to deserialize the next value
+	///
+	/// Note that this may fail if the provided `T` requires to borrow from the
+	/// input and the blocks are compressed. (`deserialize_next` typechecks that
+	/// we have `DeserializeOwned` to make sure that is never the case).
+	pub fn deserialize_next_borrowed<T: Deserialize<'a>>(&mut self) -> Result<Option<T>, DeError> {
+		self.deserialize_seed_next(PhantomData::<T>)
+	}
+
+	/// Attempt to deserialize the next value via the advanced
+	/// [`DeserializeSeed`] serde API
+	///
+	/// See [`Reader::deserialize_seed_next`].
+	pub fn deserialize_seed_next<S: DeserializeSeed<'a>>(
+		&mut self,
+		deserialize_seed: S,
+	) -> Result<Option<S::Value>, DeError> {
+		self.prepare_next();
+		let res = self.reader.deserialize_seed_next(deserialize_seed);
+		match res {
+			Ok(Some(_)) => self.n_objects_yielded_in_block += 1,
+			Ok(None) => {}
+			Err(ref de_error) => self.skip_current_block(de_error),
+		}
+		res
+	}
+
+	/// The underlying [`Reader`], e.g. to access the schema or metadata of the
+	/// file
+	pub fn reader(&self) -> &Reader<de::read::SliceRead<'a>> {
+		&self.reader
+	}
+
+	/// Stop resynchronizing after corrupt blocks, getting back the underlying
+	/// [`Reader`]
+	pub fn into_inner(self) -> Reader<de::read::SliceRead<'a>> {
+		self.reader
+	}
+
+	/// Make sure the underlying reader is either inside a block that was
+	/// checked by `check_next_block`, or at the end of the file
+	///
+	/// This is needed because the underlying reader would otherwise move on
+	/// to the next block by itself once it is done with the current one.
+	fn prepare_next(&mut self) {
+		loop {
+			match self.reader.reader_state {
+				ReaderState::InBlock {
+					n_objects_in_block: 0,
+					..
+				} => {
+					if let Err(de_error) = self.reader.leave_finished_block() {
+						self.skip_current_block(&de_error);
+					}
+				}
+				ReaderState::NotInBlock { .. } => {
+					self.check_next_block();
+					match self.current_block {
+						Some(ref current_block) if current_block.n_objects == 0 => {
+							// Nothing to read in this block, so the underlying reader would
+							// move on to the next one by itself
+							let end = current_block.end.clone();
+							if let ReaderState::NotInBlock { ref mut reader, .. } =
+								self.reader.reader_state
+							{
+								*reader = end;
+							}
+						}
+						_ => return,
+					}
+				}
+				ReaderState::InBlock { .. } | ReaderState::Broken => return,
+			}
+		}
+	}
+
+	/// To be called when the reader is between blocks: check that the next
+	/// block is valid, skipping (and reporting) everything up to the next
+	/// valid one otherwise
+	fn check_next_block(&mut self) {
+		let Reader {
+			reader_state: ReaderState::NotInBlock {
+				reader: slice_read,
+				config,
+				..
+			},
+			sync_marker,
+			..
+		} = &mut self.reader
+		else {
+			return;
+		};
+		self.config = Some(config.clone());
+		self.n_objects_yielded_in_block = 0;
+		self.current_block = None;
+		loop {
+			let start = slice_read.position();
+			if slice_read.remaining().is_empty() {
+				return;
+			}
+			let mut end = slice_read.clone();
+			match split_raw_block(&mut end, sync_marker) {
+				Ok((n_objects, _)) => {
+					self.n_bytes_in_valid_blocks += end.position() - start;
+					self.n_objects_in_valid_blocks += n_objects;
+					self.current_block = Some(CurrentBlock {
+						start,
+						end,
+						n_objects,
+					});
+					return;
+				}
+				Err(de_error) => {
+					let remaining = slice_read.remaining();
+					let n_skipped = remaining
+						.windows(sync_marker.len())
+						.position(|window| window == &sync_marker[..])
+						.map_or(remaining.len(), |pos| pos + sync_marker.len());
+					slice_read
+						.skip_bytes(n_skipped as u64)
+						.expect("We checked that these bytes are available");
+					(self.on_corrupt_block)(CorruptBlock {
+						skipped_range: start..slice_read.position(),
+						estimated_lost_records: estimate_lost_records(
+							n_skipped,
+							self.n_bytes_in_valid_blocks,
+							self.n_objects_in_valid_blocks,
+						),
+						error: de_error,
+					});
+				}
+			}
+		}
+	}
+
+	/// To be called when the reader failed to read the current block: skip
+	/// (and report) the rest of it
+	fn skip_current_block(&mut self, de_error: &DeError) {
+		let (Some(current_block), Some(config)) = (self.current_block.take(), self.config.clone())
+		else {
+			return;
+		};
+		(self.on_corrupt_block)(CorruptBlock {
+			skipped_range: current_block.start..current_block.end.position(),
+			estimated_lost_records: Some(
+				current_block
+					.n_objects
+					.saturating_sub(self.n_objects_yielded_in_block) as u64,
+			),
+			error: <DeError as serde::de::Error>::custom(format_args!(
+				"Failed to read block: {de_error}"
+			)),
+		});
+		self.reader.reader_state = ReaderState::NotInBlock {
+			reader: current_block.end,
+			config,
+			decompression_buffer: Vec::new(),
+		};
+		self.reader.pretend_eof_because_yielded_unrecoverable_error = false;
+	}
+}
+
+fn estimate_lost_records(
+	n_bytes: usize,
+	n_bytes_in_valid_blocks: usize,
+	n_objects_in_valid_blocks: usize,
+) -> Option<u64> {
+	match n_objects_in_valid_blocks {
+		0 => None,
+		n_objects => Some(
+			u64::try_from(
+				n_bytes as u128 * n_objects as u128 / n_bytes_in_valid_blocks.max(1) as u128,
+			)
+			.unwrap_or(u64::MAX),
+		),
+	}
+}
@@ -0,0 +1,100 @@
+use crate::{
+	de::{self, DeError},
+	object_container_file_encoding::{
+		Compression, FailedToInitializeReader, RawUserMetadata, Reader, WriterBuilder,
+	},
+	ser::{SerError, SerializerConfig},
+};
+
+/// Compress the blocks of an [object container file](https://avro.apache.org/docs/current/specification/#object-container-files)
+/// with a different [`Compression`], without deserializing its records
+///
+/// Each block is decompressed then the serialized records it contains are
+/// compressed again into the output. The schema JSON (including any
+/// attribute this crate does not interpret, such as `doc`) and the user
+/// metadata of the input header are preserved. Only the codec and the sync
+/// marker of the file change.
+///
+/// Blocks that decompress to more than 512MiB are rejected (see
+/// [`Reader::set_max_decompressed_block_size`]).
+///
+/// Note that if your input is a slice, you may pass it directly as `&[u8]`
+/// implements [`BufRead`](std::io::BufRead).
+///
+/// # Example
+/// ```
+/// use serde_avro_fast::object_container_file_encoding::{self, Compression, Reader};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let schema: serde_avro_fast::Schema = r#""long""#.parse()?;
+///
+/// let file = object_container_file_encoding::write_all(
+/// 	&schema,
+/// 	Compression::Null,
+/// 	Vec::new(),
+/// 	[1, 2, 3],
+/// )?;
+///
+/// let recompressed = object_container_file_encoding::recompress(
+/// 	file.as_slice(),
+/// 	Compression::Deflate {
+/// 		level: object_container_file_encoding::CompressionLevel::new(9),
+/// 	},
+/// 	Vec::new(),
+/// )?;
+///
+/// let decoded = Reader::from_slice(&recompressed)?
+/// 	.deserialize()
+/// 	.collect::<Result<Vec<i64>, _>>()?;
+/// assert_eq!(decoded, [1, 2, 3]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn recompress<R, W>(input: R, compression: Compression, output: W) -> Result<W, RecompressError>
+where
+	R: std::io::BufRead,
+	W: std::io::Write,
+{
+	let (mut reader, user_metadata) =
+		Reader::new_and_metadata::<RawUserMetadata>(de::read::ReaderRead::new(input))
+			.map_err(RecompressError::FailedToInitializeReader)?;
+	let schema = reader.schema().clone();
+
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.compression(compression)
+		.build_with_user_metadata(output, user_metadata)
+		.map_err(RecompressError::Write)?;
+
+	let input_compression = reader.compression().clone();
+	let mut decompression_buffer = Vec::new();
+	while let Some(raw_block) = reader.next_raw_block().map_err(RecompressError::Read)? {
+		let decompressed = input_compression
+			.decompress_block(
+				&raw_block.compressed_bytes,
+				&mut decompression_buffer,
+				reader.max_decompressed_block_size,
+			)
+			.map_err(RecompressError::Read)?;
+		writer
+			.push_serialized(decompressed, raw_block.n_objects)
+			.map_err(RecompressError::Write)?;
+	}
+
+	writer.into_inner().map_err(RecompressError::Write)
+}
+
+/// Errors that may happen when attempting to [`recompress`] an object
+/// container file
+#[derive(Debug, thiserror::Error)]
+pub enum RecompressError {
+	/// Failed to read the header of the input
+	#[error("Failed to initialize reader: {}", _0)]
+	FailedToInitializeReader(FailedToInitializeReader),
+	/// Failed to read a block from the input
+	#[error("Failed to read input object container file: {}", _0)]
+	Read(DeError),
+	/// Failed to write to the output
+	#[error("Failed to write recompressed object container file: {}", _0)]
+	Write(SerError),
+}
@@ -0,0 +1,243 @@
+use super::*;
+
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+impl<'c, 's> WriterBuilder<'c, 's> {
+	/// Build an [`AsyncWriter`]
+	///
+	/// After this method is called, it is guaranteed that the full object
+	/// container file encoding header is already written to the `writer`.
+	pub async fn build_async<W: AsyncWrite + Unpin>(
+		self,
+		writer: W,
+	) -> Result<AsyncWriter<'c, 's, W>, SerError> {
+		self.build_async_with_user_metadata(writer, ()).await
+	}
+
+	/// Build an [`AsyncWriter`], also encoding [user-specified metadata](https://avro.apache.org/docs/current/specification/#object-container-files)
+	///
+	/// After this method is called, it is guaranteed that the full object
+	/// container file encoding header is already written to the `writer`.
+	pub async fn build_async_with_user_metadata<W: AsyncWrite + Unpin, M: Serialize>(
+		self,
+		mut writer: W,
+		metadata: M,
+	) -> Result<AsyncWriter<'c, 's, W>, SerError> {
+		if self.parallel_compression_threads > 0 {
+			return Err(SerError::new(
+				"Parallel compression is not supported by AsyncWriter",
+			));
+		}
+		let mut inner = self.build_inner(metadata)?;
+		writer
+			.write_all(inner.serializer_state.writer())
+			.await
+			.map_err(SerError::io)?;
+		inner.serializer_state.writer_mut().clear();
+		Ok(AsyncWriter { inner, writer })
+	}
+}
+
+/// Asynchronous writer for [object container files](https://avro.apache.org/docs/current/specification/#object-container-files)
+///
+/// To be constructed via [`WriterBuilder::build_async`].
+///
+/// Values are serialized into the in-memory block exactly like with
+/// [`Writer`]. When a block is finished, it is compressed then written to the
+/// underlying [`AsyncWrite`], so that backpressure from the writer is
+/// propagated to the functions of this `AsyncWriter`.
+///
+/// Contrary to [`Writer`], the last block can not be flushed on drop: you
+/// need to call [`into_inner`](AsyncWriter::into_inner) (or
+/// [`finish_block`](AsyncWriter::finish_block)) when done, otherwise the
+/// values serialized since the last block was written will be lost.
+///
+/// # Example
+///
+/// ```
+/// use {
+/// 	serde_avro_derive::BuildSchema,
+/// 	serde_avro_fast::{
+/// 		object_container_file_encoding::{Compression, Reader, WriterBuilder},
+/// 		ser::SerializerConfig,
+/// 	},
+/// };
+///
+/// #[derive(
+/// 	serde_derive::Serialize, serde_derive::Deserialize, BuildSchema, Debug, PartialEq, Eq,
+/// )]
+/// struct SchemaRecord<'a> {
+/// 	a: i64,
+/// 	b: &'a str,
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let schema: serde_avro_fast::Schema = SchemaRecord::schema()?;
+///
+/// let mut serializer_config = SerializerConfig::new(&schema);
+/// let mut writer = WriterBuilder::new(&mut serializer_config)
+/// 	.compression(Compression::Null)
+/// 	.build_async(Vec::new())
+/// 	.await?;
+///
+/// for i in 0..3 {
+/// 	writer.serialize(SchemaRecord { a: i, b: "hello" }).await?;
+/// }
+///
+/// let object_container_file_encoded: Vec<u8> = writer.into_inner().await?;
+///
+/// // Decode and make sure we obtain the same thing
+/// let decoded = Reader::from_slice(&object_container_file_encoded)?
+/// 	.deserialize_borrowed() // Only use `_borrowed` if data is not compressed
+/// 	.collect::<Result<Vec<SchemaRecord>, _>>()?;
+///
+/// assert_eq!(
+/// 	decoded,
+/// 	&[
+/// 		SchemaRecord { a: 0, b: "hello" },
+/// 		SchemaRecord { a: 1, b: "hello" },
+/// 		SchemaRecord { a: 2, b: "hello" }
+/// 	]
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncWriter<'c, 's, W: AsyncWrite + Unpin> {
+	inner: WriterInner<'c, 's>,
+	writer: W,
+}
+
+impl<'c, 's, W: AsyncWrite + Unpin> AsyncWriter<'c, 's, W> {
+	/// Serialize each value of the provided sequence in the object container
+	/// file
+	pub async fn serialize_all<IT: IntoIterator>(&mut self, iterator: IT) -> Result<(), SerError>
+	where
+		IT::Item: Serialize,
+	{
+		for value in iterator {
+			self.serialize(value).await?;
+		}
+		Ok(())
+	}
+
+	/// Serialize one value as an object in the object container file
+	///
+	/// If this finishes a block, this waits until the block is written to the
+	/// underlying writer.
+	pub async fn serialize<T: Serialize>(&mut self, value: T) -> Result<(), SerError> {
+		self.flush_finished_block().await?;
+		if self.inner.should_finish_block() {
+			self.finish_block().await?;
+		}
+		self.inner.serialize(value)?;
+		self.flush_finished_block().await?;
+		Ok(())
+	}
+
+	/// Copy one or multiple already-serialized values into the object container
+	/// file
+	///
+	/// See [`Writer::push_serialized`] for the expectations on the provided
+	/// slice.
+	pub async fn push_serialized(
+		&mut self,
+		serialized_objects: &[u8],
+		n_objects: u64,
+	) -> Result<(), SerError> {
+		self.flush_finished_block().await?;
+		if self.inner.should_finish_block() {
+			self.finish_block().await?;
+		}
+		self.inner.push_serialized(serialized_objects, n_objects)?;
+		self.flush_finished_block().await?;
+		Ok(())
+	}
+
+	/// Write a block that was already compressed
+	///
+	/// See [`Writer::push_raw_block`].
+	pub async fn push_raw_block(&mut self, raw_block: &RawBlock<'_>) -> Result<(), SerError> {
+		let raw_block_header = self.inner.raw_block_header(raw_block)?;
+		self.finish_block().await?;
+		if let Some((block_header_buffer, block_header_size)) = raw_block_header {
+			vectored_write_polyfill::write_all_vectored_async(
+				&mut self.writer,
+				[
+					&block_header_buffer[..block_header_size.get()],
+					&raw_block.compressed_bytes,
+					&self.inner.sync_marker,
+				],
+			)
+			.await
+			.map_err(SerError::io)?;
+			self.inner.raw_block_flushed(raw_block);
+		}
+		Ok(())
+	}
+
+	/// Flush the final block (if a block was started) and the underlying
+	/// writer, then return the underlying writer.
+	///
+	/// Note that this does not [`shutdown`](AsyncWriteExt::shutdown) the
+	/// underlying writer.
+	pub async fn into_inner(mut self) -> Result<W, SerError> {
+		self.finish_block().await?;
+		self.writer.flush().await.map_err(SerError::io)?;
+		Ok(self.writer)
+	}
+
+	/// Flush the current block (if a block was started)
+	///
+	/// After this function is called, if it returned no error, it is guaranteed
+	/// that the full block is written to the writer.
+	/// This implies that all bytes written so far amount to a valid object
+	/// container file.
+	pub async fn finish_block(&mut self) -> Result<(), SerError> {
+		self.inner.finish_block()?;
+		self.flush_finished_block().await?;
+		Ok(())
+	}
+
+	/// Flush the current block (if a block was started), then flush the
+	/// underlying writer
+	///
+	/// See [`Writer::flush`].
+	pub async fn flush(&mut self) -> Result<(), SerError> {
+		self.finish_block().await?;
+		self.writer.flush().await.map_err(SerError::io)
+	}
+
+	/// Statistics about the blocks written so far
+	///
+	/// See [`WriterStats`].
+	pub fn stats(&self) -> WriterStats {
+		self.inner.stats
+	}
+
+	async fn flush_finished_block(&mut self) -> Result<(), SerError> {
+		if let Some(finished_block) = self.inner.finished_block() {
+			vectored_write_polyfill::write_all_vectored_async(&mut self.writer, finished_block)
+				.await
+				.map_err(SerError::io)?;
+			self.inner.mark_finished_block_flushed();
+		}
+		Ok(())
+	}
+
+	/// Get a mutable reference to the inner writer
+	///
+	/// See [`Writer::inner_mut`] for what may be expected of what was written
+	/// to it so far.
+	pub fn inner_mut(&mut self) -> &mut W {
+		&mut self.writer
+	}
+
+	/// Get a reference to the inner writer
+	///
+	/// See [`Writer::inner`] for what may be expected of what was written to it
+	/// so far.
+	pub fn inner(&self) -> &W {
+		&self.writer
+	}
+}
@@ -6,7 +6,7 @@ pub(super) struct CompressionCodecState {
 }
 
 impl CompressionCodecState {
-	pub(super) fn new(compression_codec: Compression) -> Self {
+	pub(super) fn new(compression_codec: &Compression) -> Self {
 		Self {
 			output_vec: Vec::new(),
 			kind: match compression_codec {
@@ -33,8 +33,9 @@ impl CompressionCodecState {
 					level: level.clip(9),
 				},
 				#[cfg(feature = "zstandard")]
-				Compression::Zstandard { level } => Kind::Zstandard {
+				Compression::Zstandard { level, dictionary } => Kind::Zstandard {
 					compressor: None,
+					dictionary: dictionary.clone(),
 					level: level.clip(
 						(*zstd::compression_level_range().end())
 							.max(0)
@@ -42,6 +43,11 @@ impl CompressionCodecState {
 							.unwrap_or(u8::MAX - 1),
 					),
 				},
+				#[cfg(feature = "lz4")]
+				Compression::Lz4 => Kind::Lz4,
+				Compression::Custom(codec) => Kind::Custom {
+					codec: codec.clone(),
+				},
 			},
 		}
 	}
@@ -71,8 +77,14 @@ enum Kind {
 	#[cfg(feature = "zstandard")]
 	Zstandard {
 		compressor: Option<zstd::bulk::Compressor<'static>>,
+		dictionary: Option<crate::object_container_file_encoding::ZstandardDictionary>,
 		level: crate::object_container_file_encoding::CompressionLevel,
 	},
+	#[cfg(feature = "lz4")]
+	Lz4,
+	Custom {
+		codec: crate::object_container_file_encoding::CustomCodec,
+	},
 }
 
 impl CompressionCodecState {
@@ -92,6 +104,9 @@ impl CompressionCodecState {
 			Kind::Xz { len, .. } => Some(&self.output_vec[..len]),
 			#[cfg(feature = "zstandard")]
 			Kind::Zstandard { .. } => Some(&self.output_vec),
+			#[cfg(feature = "lz4")]
+			Kind::Lz4 => Some(&self.output_vec),
+			Kind::Custom { .. } => Some(&self.output_vec),
 		}
 	}
 
@@ -241,7 +256,11 @@ impl CompressionCodecState {
 				}
 			}
 			#[cfg(feature = "zstandard")]
-			Kind::Zstandard { compressor, level } => {
+			Kind::Zstandard {
+				compressor,
+				dictionary,
+				level,
+			} => {
 				self.output_vec.clear();
 				self.output_vec
 					.reserve(zstd::zstd_safe::compress_bound(input.len()));
@@ -249,9 +268,15 @@ impl CompressionCodecState {
 				let compressor = match compressor {
 					None => {
 						*compressor = Some(
-							zstd::bulk::Compressor::new(level.instantiate_nb(0)).map_err(
-								|err| error("zstandard", &format_args!("error on init: {err}")),
-							)?,
+							zstd::bulk::Compressor::with_dictionary(
+								level.instantiate_nb(0),
+								dictionary
+									.as_ref()
+									.map_or(&[], |dictionary| dictionary.as_bytes()),
+							)
+							.map_err(|err| {
+								error("zstandard", &format_args!("error on init: {err}"))
+							})?,
 						);
 						compressor.as_mut().unwrap()
 					}
@@ -262,6 +287,21 @@ impl CompressionCodecState {
 					.compress_to_buffer(input, &mut self.output_vec)
 					.map_err(|err| error("zstandard", &err))?;
 			}
+			#[cfg(feature = "lz4")]
+			Kind::Lz4 => {
+				self.output_vec.clear();
+				let mut encoder =
+					lz4_flex::frame::FrameEncoder::new(std::mem::take(&mut self.output_vec));
+				std::io::Write::write_all(&mut encoder, input).map_err(|err| error("lz4", &err))?;
+				self.output_vec = encoder.finish().map_err(|err| error("lz4", &err))?;
+			}
+			Kind::Custom { codec } => {
+				self.output_vec.clear();
+				codec
+					.codec()
+					.compress(input, &mut self.output_vec)
+					.map_err(|err| error(codec.name(), &err))?;
+			}
 		}
 		Ok(())
 	}
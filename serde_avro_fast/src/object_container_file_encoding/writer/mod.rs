@@ -1,19 +1,33 @@
+#[cfg(feature = "tokio")]
+mod async_writer;
 mod compression;
+mod parallel_compression;
 mod vectored_write_polyfill;
 
-use compression::CompressionCodecState;
+#[cfg(feature = "tokio")]
+pub use async_writer::AsyncWriter;
+
+use {
+	compression::CompressionCodecState,
+	parallel_compression::{CompressedBlock, ParallelCompression},
+};
 
 use crate::{
-	object_container_file_encoding::{Metadata, METADATA_SCHEMA},
+	de,
+	object_container_file_encoding::{CodecRegistry, HeaderMetadata, METADATA_SCHEMA},
 	ser::{SerError, SerializerConfig, SerializerConfigRef, SerializerState},
 	Schema,
 };
 
-use super::{Compression, HEADER_CONST};
+use super::{Compression, RawBlock, HEADER_CONST};
 
 use {
 	serde::Serialize,
-	std::{io::Write, num::NonZeroUsize},
+	std::{
+		io::{Read, Seek, SeekFrom, Write},
+		num::NonZeroUsize,
+		time::{Duration, Instant},
+	},
 };
 
 /// Write all the elements of the provided sequence in an [object container file](https://avro.apache.org/docs/current/specification/#object-container-files)
@@ -93,7 +107,7 @@ where
 	let mut writer = WriterBuilder::new(&mut serializer_config)
 		.compression(compression)
 		.build(writer)?;
-	writer.serialize_all(iterator.into_iter())?;
+	writer.serialize_all(iterator)?;
 	writer.into_inner()
 }
 
@@ -104,6 +118,9 @@ pub struct WriterBuilder<'c, 's> {
 	serializer_config: SerializerConfigRef<'c, 's>,
 	compression: Compression,
 	approx_block_size: u32,
+	max_records_per_block: Option<u64>,
+	max_block_duration: Option<Duration>,
+	parallel_compression_threads: usize,
 	/// Will otherwise be randomly generated
 	enforce_sync_marker_value: Option<[u8; 16]>,
 }
@@ -132,6 +149,9 @@ impl<'c, 's> WriterBuilder<'c, 's> {
 			serializer_config,
 			compression: Compression::Null,
 			approx_block_size: 64 * 1024,
+			max_records_per_block: None,
+			max_block_duration: None,
+			parallel_compression_threads: 0,
 			enforce_sync_marker_value: None,
 		}
 	}
@@ -161,6 +181,56 @@ impl<'c, 's> WriterBuilder<'c, 's> {
 		self
 	}
 
+	/// Maximum number of records in a block
+	///
+	/// Once a block contains this many records, it is compressed and flushed,
+	/// even if it is smaller than
+	/// [`approx_block_size`](Self::approx_block_size).
+	///
+	/// Note that this can't be enforced exactly with
+	/// [`push_serialized`](Writer::push_serialized), as whether to finish the
+	/// block is only considered after writing the full slice.
+	///
+	/// Default is no limit.
+	pub fn max_records_per_block(mut self, max_records_per_block: u64) -> Self {
+		self.max_records_per_block = Some(max_records_per_block);
+		self
+	}
+
+	/// Maximum time since the first record of a block was serialized
+	///
+	/// Once a block is older than this, it is compressed and flushed, so that
+	/// long-lived writers that receive few records still write them out
+	/// regularly.
+	///
+	/// Note that this is only checked when values are pushed to the writer:
+	/// if no values are pushed, the block is not flushed. If that may happen,
+	/// you may call [`Writer::flush`] periodically.
+	///
+	/// Default is no limit.
+	pub fn max_block_duration(mut self, max_block_duration: Duration) -> Self {
+		self.max_block_duration = Some(max_block_duration);
+		self
+	}
+
+	/// Compress blocks on `n_threads` worker threads
+	///
+	/// Serialization then continues into a new block while previous blocks are
+	/// being compressed. Blocks are still written in order.
+	///
+	/// This is mostly useful with CPU-intensive compression codecs, such as
+	/// `Xz` or high levels of `Zstandard`. Once `2 * n_threads` blocks are
+	/// waiting to be written, serialization waits for the workers to catch up.
+	///
+	/// Default value is `0`, which means blocks are compressed inline. This has
+	/// no effect with [`Compression::Null`].
+	///
+	/// Not supported by `AsyncWriter`.
+	pub fn parallel_compression(mut self, n_threads: usize) -> Self {
+		self.parallel_compression_threads = n_threads;
+		self
+	}
+
 	/// Enforce the 16-byte inter-block sync marker value
 	///
 	/// This is [the 16-byte value that is written at the end of each block to help detect corrupt blocks](https://avro.apache.org/docs/current/specification/#object-container-files).
@@ -193,6 +263,103 @@ impl<'c, 's> WriterBuilder<'c, 's> {
 		mut writer: W,
 		metadata: M,
 	) -> Result<Writer<'c, 's, W>, SerError> {
+		let mut inner = self.build_inner(metadata)?;
+		writer
+			.write_all(inner.serializer_state.writer())
+			.map_err(SerError::io)?;
+		inner.serializer_state.writer_mut().clear();
+		Ok(Writer {
+			inner,
+			writer: Some(writer),
+		})
+	}
+
+	/// Build a [`Writer`] that appends to an existing object container file
+	///
+	/// The header of the file is read to recover its compression codec and
+	/// sync marker, then new blocks are written at the end of the file, so
+	/// that the file remains valid. If the file is empty, a header is written
+	/// first, as with [`build`](Self::build).
+	///
+	/// This returns an error if the schema of the file does not have the same
+	/// [`rabin_fingerprint`](Schema::rabin_fingerprint) as the schema of the
+	/// [`SerializerConfig`].
+	///
+	/// If the codec of the file is not that of the
+	/// [`compression`](Self::compression) configured on this builder, the
+	/// codec of the file is used, with the default compression level. If the
+	/// file is compressed with a zstandard dictionary, that dictionary is
+	/// used. [`sync_marker`](Self::sync_marker) is ignored.
+	///
+	/// Note that the file is expected to be a valid object container file: if
+	/// its last block was only partially written (e.g. because of a crash),
+	/// the file needs to be truncated to the end of its last complete block
+	/// beforehand, otherwise the appended blocks won't be readable.
+	pub fn build_append<W: Read + Write + Seek>(
+		mut self,
+		mut writer: W,
+	) -> Result<Writer<'c, 's, W>, SerError> {
+		if writer.seek(SeekFrom::End(0)).map_err(SerError::io)? == 0 {
+			return self.build(writer);
+		}
+		writer.seek(SeekFrom::Start(0)).map_err(SerError::io)?;
+		// If the file uses the custom codec of this builder, it has to be resolved
+		let codecs = match &self.compression {
+			Compression::Custom(codec) => CodecRegistry::new().register_custom(codec.clone()),
+			_ => CodecRegistry::new(),
+		};
+		let (_, header) = super::read_header::<_, ()>(
+			de::read::ReaderRead::new(std::io::BufReader::new(&mut writer)),
+			&codecs,
+		)
+		.map_err(|e| {
+			<SerError as serde::ser::Error>::custom(format_args!(
+				"Failed to read the header of the object container file to append to: {e}"
+			))
+		})?;
+		if header.schema.rabin_fingerprint() != self.serializer_config.schema().rabin_fingerprint()
+		{
+			return Err(SerError::new(
+				"The schema of the object container file to append to is not the same as \
+					the schema of the SerializerConfig",
+			));
+		}
+		match (&mut self.compression, header.compression) {
+			#[cfg(feature = "zstandard")]
+			(
+				Compression::Zstandard { dictionary, .. },
+				Compression::Zstandard {
+					dictionary: file_dictionary,
+					..
+				},
+			) => *dictionary = file_dictionary,
+			(compression, file_compression) => {
+				if compression.codec() != file_compression.codec() {
+					*compression = file_compression;
+				}
+			}
+		}
+		self.enforce_sync_marker_value = Some(header.sync_marker);
+		writer.seek(SeekFrom::End(0)).map_err(SerError::io)?;
+
+		let mut inner = self.build_inner(())?;
+		// The file already has a header
+		inner.serializer_state.writer_mut().clear();
+		Ok(Writer {
+			inner,
+			writer: Some(writer),
+		})
+	}
+
+	/// Build the [`WriterInner`], with the header in its buffer
+	///
+	/// The caller is responsible for writing the header then clearing the
+	/// buffer before anything gets serialized.
+	fn build_inner<M: Serialize>(self, metadata: M) -> Result<WriterInner<'c, 's>, SerError> {
+		if let Compression::Custom(codec) = &self.compression {
+			codec.check_name()?;
+		}
+
 		let sync_marker = match self.enforce_sync_marker_value {
 			Some(enforced_sync_marker) => enforced_sync_marker,
 			None => {
@@ -216,9 +383,17 @@ impl<'c, 's> WriterBuilder<'c, 's> {
 			let mut header_serializer_config = SerializerConfig::new_with_optional_schema(None);
 			let mut header_serializer_state =
 				SerializerState::from_writer(buf, &mut header_serializer_config);
-			(Metadata::<&str, M> {
+			(HeaderMetadata::<&str, M> {
 				schema: self.serializer_config.schema().json(),
-				codec: self.compression.codec(),
+				codec: self.compression.codec().name(),
+				zstandard_dictionary: match &self.compression {
+					#[cfg(feature = "zstandard")]
+					Compression::Zstandard {
+						dictionary: Some(dictionary),
+						..
+					} => Some(super::RawBytes(dictionary.as_bytes().to_owned())),
+					_ => None,
+				},
 				user_metadata: metadata,
 			})
 			.serialize(
@@ -234,23 +409,27 @@ impl<'c, 's> WriterBuilder<'c, 's> {
 
 		buf.write_all(&sync_marker).map_err(SerError::io)?;
 
-		writer.write_all(&buf).map_err(SerError::io)?;
-		buf.clear();
+		let parallel_compression = match (&self.compression, self.parallel_compression_threads) {
+			(Compression::Null, _) | (_, 0) => None,
+			(compression, n_threads) => Some(ParallelCompression::new(compression, n_threads)?),
+		};
 
-		Ok(Writer {
-			inner: WriterInner {
-				serializer_state: SerializerState::with_opt_owned_config(
-					buf,
-					self.serializer_config,
-				),
-				sync_marker,
-				compression_codec_state: CompressionCodecState::new(self.compression),
-				n_elements_in_block: 0,
-				approx_block_size: self.approx_block_size,
-				block_header_buffer: [0; 20],
-				block_header_size: None,
-			},
-			writer: Some(writer),
+		Ok(WriterInner {
+			serializer_state: SerializerState::with_opt_owned_config(buf, self.serializer_config),
+			sync_marker,
+			compression_codec_state: CompressionCodecState::new(&self.compression),
+			n_elements_in_block: 0,
+			approx_block_size: self.approx_block_size,
+			max_records_per_block: self.max_records_per_block,
+			max_block_duration: self.max_block_duration,
+			block_opened_at: None,
+			block_header_buffer: [0; 20],
+			block_header_size: None,
+			compression: self.compression.clone(),
+			parallel_compression,
+			parallel_compressed_block: None,
+			finished_block_uncompressed: (0, 0),
+			stats: WriterStats::default(),
 		})
 	}
 }
@@ -344,12 +523,12 @@ impl<'c, 's, W: Write> Writer<'c, 's, W> {
 
 	/// Serialize one value as an object in the object container file
 	pub fn serialize<T: Serialize>(&mut self, value: T) -> Result<(), SerError> {
-		self.flush_finished_block()?;
-		if self.inner.serializer_state.writer().len() >= self.inner.approx_block_size as usize {
+		self.flush_finished_block(false)?;
+		if self.inner.should_finish_block() {
 			self.finish_block()?;
 		}
 		self.inner.serialize(value)?;
-		self.flush_finished_block()?;
+		self.flush_finished_block(false)?;
 		Ok(())
 	}
 
@@ -380,12 +559,46 @@ impl<'c, 's, W: Write> Writer<'c, 's, W> {
 		serialized_objects: &[u8],
 		n_objects: u64,
 	) -> Result<(), SerError> {
-		self.flush_finished_block()?;
-		if self.inner.serializer_state.writer().len() >= self.inner.approx_block_size as usize {
+		self.flush_finished_block(false)?;
+		if self.inner.should_finish_block() {
 			self.finish_block()?;
 		}
 		self.inner.push_serialized(serialized_objects, n_objects)?;
-		self.flush_finished_block()?;
+		self.flush_finished_block(false)?;
+		Ok(())
+	}
+
+	/// Write a block that was already compressed, e.g. obtained through
+	/// [`Reader::next_raw_block`](super::Reader::next_raw_block)
+	///
+	/// The current block is finished first, so that blocks stay in order.
+	///
+	/// The block has to be compressed with the same codec (and zstandard
+	/// dictionary, if any) as the one this writer was built with, otherwise
+	/// this returns an error. It is also
+	/// expected that the objects it contains were serialized with the same
+	/// schema as the one provided to the [`WriterBuilder`] that constructed
+	/// this [`Writer`]. If that is not the case, the generated object
+	/// container file will be invalid.
+	pub fn push_raw_block(&mut self, raw_block: &RawBlock<'_>) -> Result<(), SerError> {
+		let raw_block_header = self.inner.raw_block_header(raw_block)?;
+		self.finish_block()?;
+		if let Some((block_header_buffer, block_header_size)) = raw_block_header {
+			let writer = self.writer.as_mut().expect(
+				"This is only unset by into_inner, which guarantees we \
+					couldn't call this function after",
+			);
+			vectored_write_polyfill::write_all_vectored(
+				writer,
+				[
+					&block_header_buffer[..block_header_size.get()],
+					&raw_block.compressed_bytes,
+					&self.inner.sync_marker,
+				],
+			)
+			.map_err(SerError::io)?;
+			self.inner.raw_block_flushed(raw_block);
+		}
 		Ok(())
 	}
 
@@ -407,37 +620,50 @@ impl<'c, 's, W: Write> Writer<'c, 's, W> {
 	/// container file.
 	pub fn finish_block(&mut self) -> Result<(), SerError> {
 		self.inner.finish_block()?;
-		self.flush_finished_block()?;
+		self.flush_finished_block(true)?;
 		Ok(())
 	}
 
-	fn flush_finished_block(&mut self) -> Result<(), SerError> {
-		match self.inner.block_header_size {
-			None => {
-				// there's no block to flush
-			}
-			Some(block_header_size) => {
-				let writer = self.writer.as_mut().expect(
-					"This is only unset by into_inner, which guarantees that \
-						flush_finished_block is called, which guarantees that block_header_size \
-						is None",
-				);
-				// To be replaced with std's write_all_vectored once that is stabilized
-				// https://github.com/rust-lang/rust/issues/70436
-				vectored_write_polyfill::write_all_vectored(
-					writer,
-					[
-						&self.inner.block_header_buffer[..block_header_size.get()],
-						self.inner.compressed_block(),
-						&self.inner.sync_marker,
-					],
-				)
+	/// Flush the current block (if a block was started), then flush the
+	/// underlying writer
+	///
+	/// This is [`finish_block`](Self::finish_block) followed by
+	/// [`Write::flush`] on the underlying writer, so that everything that was
+	/// serialized so far reaches its destination (e.g. for long-lived writers
+	/// to a file or a socket).
+	pub fn flush(&mut self) -> Result<(), SerError> {
+		self.finish_block()?;
+		self.inner_mut().flush().map_err(SerError::io)
+	}
+
+	/// Statistics about the blocks written so far
+	///
+	/// See [`WriterStats`].
+	pub fn stats(&self) -> WriterStats {
+		self.inner.stats
+	}
+
+	/// Write the blocks that are finished (and compressed)
+	///
+	/// With parallel compression, if `wait` is `true`, this waits for all
+	/// blocks to be compressed and written.
+	fn flush_finished_block(&mut self, wait: bool) -> Result<(), SerError> {
+		loop {
+			self.inner.prepare_finished_block(wait)?;
+			let Some(finished_block) = self.inner.finished_block() else {
+				break;
+			};
+			let writer = self.writer.as_mut().expect(
+				"This is only unset by into_inner, which guarantees that \
+					flush_finished_block is called, which guarantees that block_header_size \
+					is None",
+			);
+			// To be replaced with std's write_all_vectored once that is stabilized
+			// https://github.com/rust-lang/rust/issues/70436
+			vectored_write_polyfill::write_all_vectored(writer, finished_block)
 				.map_err(SerError::io)?;
-				self.inner.block_header_size = None; // Mark that we have flushed
-				self.inner.serializer_state.writer_mut().clear();
-			}
+			self.inner.mark_finished_block_flushed();
 		}
-
 		Ok(())
 	}
 
@@ -517,9 +743,21 @@ struct WriterInner<'c, 's> {
 	serializer_state: SerializerState<'c, 's, Vec<u8>>,
 	n_elements_in_block: u64,
 	approx_block_size: u32,
+	max_records_per_block: Option<u64>,
+	max_block_duration: Option<Duration>,
+	/// When the first element of the current block was serialized
+	block_opened_at: Option<Instant>,
 	sync_marker: [u8; 16],
 	block_header_buffer: [u8; 20],
 	block_header_size: Option<NonZeroUsize>,
+	compression: Compression,
+	parallel_compression: Option<ParallelCompression>,
+	/// With parallel compression, the block that is currently being flushed
+	parallel_compressed_block: Option<CompressedBlock>,
+	/// Number of elements and uncompressed size of the block that is
+	/// currently being flushed, for the stats
+	finished_block_uncompressed: (u64, usize),
+	stats: WriterStats,
 	/// This type goes at the end because it's potentially large
 	compression_codec_state: CompressionCodecState,
 }
@@ -535,8 +773,8 @@ impl<'c, 's> WriterInner<'c, 's> {
 					.writer_mut()
 					.truncate(buf_len_before_attempt);
 			})?;
-		self.n_elements_in_block += 1;
-		if self.serializer_state.writer().len() >= self.approx_block_size as usize {
+		self.elements_added(1)?;
+		if self.should_finish_block() {
 			self.finish_block()?;
 		}
 		Ok(())
@@ -558,20 +796,54 @@ impl<'c, 's> WriterInner<'c, 's> {
 					.truncate(buf_len_before_attempt);
 				SerError::io(e)
 			})?;
-		self.n_elements_in_block =
-			self.n_elements_in_block
-				.checked_add(n_objects)
-				.ok_or_else(|| {
-					SerError::new("Provided incorrect n_elements to write_serialized (too big)")
-				})?;
-		if self.serializer_state.writer().len() >= self.approx_block_size as usize {
+		self.elements_added(n_objects)?;
+		if self.should_finish_block() {
 			self.finish_block()?;
 		}
 		Ok(())
 	}
 
+	/// To be called once `n_elements` were written to the current block
+	///
+	/// If they are the first ones of the block, this is when the block is
+	/// considered opened for [`max_block_duration`](WriterBuilder::max_block_duration).
+	fn elements_added(&mut self, n_elements: u64) -> Result<(), SerError> {
+		if self.n_elements_in_block == 0 && self.max_block_duration.is_some() {
+			self.block_opened_at = Some(Instant::now());
+		}
+		self.n_elements_in_block = self
+			.n_elements_in_block
+			.checked_add(n_elements)
+			.ok_or_else(|| {
+				SerError::new("Provided incorrect n_elements to write_serialized (too big)")
+			})?;
+		Ok(())
+	}
+
+	/// Whether the current block has reached one of the limits configured on
+	/// the [`WriterBuilder`]
+	fn should_finish_block(&self) -> bool {
+		self.n_elements_in_block > 0
+			&& (self.serializer_state.writer().len() >= self.approx_block_size as usize
+				|| self
+					.max_records_per_block
+					.is_some_and(|max| self.n_elements_in_block >= max)
+				|| self
+					.max_block_duration
+					.zip(self.block_opened_at)
+					.is_some_and(|(max, opened_at)| opened_at.elapsed() >= max))
+	}
+
 	fn finish_block(&mut self) -> Result<(), SerError> {
 		if self.n_elements_in_block > 0 {
+			self.block_opened_at = None;
+			if let Some(ref mut parallel_compression) = self.parallel_compression {
+				parallel_compression
+					.submit(self.serializer_state.writer_mut(), self.n_elements_in_block)?;
+				self.n_elements_in_block = 0;
+				return Ok(());
+			}
+
 			assert!(
 				self.block_header_size.is_none(),
 				"Previous block should always be flushed before starting to serialize a new one"
@@ -580,23 +852,10 @@ impl<'c, 's> WriterInner<'c, 's> {
 			self.compression_codec_state
 				.encode(self.serializer_state.writer().as_slice())?;
 
-			let n_elements_in_block: i64 = self.n_elements_in_block.try_into().map_err(|_| {
-				SerError::new(
-					"n_elements_in_block exceeds i64::MAX \
-						(probably provided incorrect n_elements to write_serialized)",
-				)
-			})?;
-			let n = <i64 as integer_encoding::VarInt>::encode_var(
-				n_elements_in_block,
-				&mut self.block_header_buffer,
-			);
-			#[allow(clippy::cast_possible_wrap)]
-			let n2 = <i64 as integer_encoding::VarInt>::encode_var(
-				self.compressed_block().len() as i64,
-				&mut self.block_header_buffer[n..],
-			);
-			self.block_header_size = Some(
-				NonZeroUsize::new(n + n2).expect("Encoding VarInts should never write zero bytes"),
+			self.set_block_header(self.n_elements_in_block, self.compressed_block().len())?;
+			self.finished_block_uncompressed = (
+				self.n_elements_in_block,
+				self.serializer_state.writer().len(),
 			);
 			self.n_elements_in_block = 0;
 		}
@@ -604,7 +863,101 @@ impl<'c, 's> WriterInner<'c, 's> {
 		Ok(())
 	}
 
+	fn set_block_header(&mut self, n_elements: u64, compressed_len: usize) -> Result<(), SerError> {
+		self.block_header_size = Some(encode_block_header(
+			n_elements,
+			compressed_len,
+			&mut self.block_header_buffer,
+		)?);
+		Ok(())
+	}
+
+	/// Check that a raw block can be written by this writer, and encode its
+	/// header
+	fn raw_block_header(
+		&self,
+		raw_block: &RawBlock<'_>,
+	) -> Result<Option<([u8; 20], NonZeroUsize)>, SerError> {
+		let compression_codec = self.compression.codec();
+		if raw_block.codec != compression_codec {
+			return Err(<SerError as serde::ser::Error>::custom(format_args!(
+				"Raw block is compressed with codec {:?}, but this writer uses {:?}",
+				raw_block.codec, compression_codec
+			)));
+		}
+		#[cfg(feature = "zstandard")]
+		if let Compression::Zstandard { dictionary, .. } = &self.compression {
+			if raw_block.zstandard_dictionary != *dictionary {
+				return Err(SerError::new(
+					"Raw block is not compressed with the same zstandard dictionary as this writer",
+				));
+			}
+		}
+		if raw_block.n_objects == 0 {
+			return Ok(None);
+		}
+		let mut block_header_buffer = [0; 20];
+		let block_header_size = encode_block_header(
+			raw_block.n_objects,
+			raw_block.compressed_bytes.len(),
+			&mut block_header_buffer,
+		)?;
+		Ok(Some((block_header_buffer, block_header_size)))
+	}
+
+	/// With parallel compression, pick the next block that was compressed by the
+	/// workers (if any) so that it can be flushed
+	///
+	/// If `wait` is `true`, this waits for the workers to compress it.
+	fn prepare_finished_block(&mut self, wait: bool) -> Result<(), SerError> {
+		if self.block_header_size.is_none() {
+			if let Some(ref mut parallel_compression) = self.parallel_compression {
+				if let Some(block) = parallel_compression.next_compressed_block(wait)? {
+					self.set_block_header(block.n_elements, block.compressed.len())?;
+					self.finished_block_uncompressed = (block.n_elements, block.uncompressed_len());
+					self.parallel_compressed_block = Some(block);
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// If a block was finished but not flushed yet, the slices that should be
+	/// written for it
+	fn finished_block(&self) -> Option<[&[u8]; 3]> {
+		self.block_header_size.map(|block_header_size| {
+			[
+				&self.block_header_buffer[..block_header_size.get()],
+				self.compressed_block(),
+				&self.sync_marker,
+			]
+		})
+	}
+
+	fn mark_finished_block_flushed(&mut self) {
+		let (n_elements, uncompressed_len) = self.finished_block_uncompressed;
+		self.stats
+			.block_flushed(n_elements, uncompressed_len, self.compressed_block().len());
+		self.block_header_size = None;
+		match (
+			self.parallel_compressed_block.take(),
+			&mut self.parallel_compression,
+		) {
+			(Some(block), Some(parallel_compression)) => parallel_compression.recycle(block),
+			_ => self.serializer_state.writer_mut().clear(),
+		}
+	}
+
+	/// Account for a raw block in the stats once it was written
+	fn raw_block_flushed(&mut self, raw_block: &RawBlock<'_>) {
+		self.stats
+			.raw_block_flushed(raw_block.n_objects, raw_block.compressed_bytes.len());
+	}
+
 	fn compressed_block(&self) -> &[u8] {
+		if let Some(ref block) = self.parallel_compressed_block {
+			return &block.compressed;
+		}
 		self.compression_codec_state
 			.compressed_buffer()
 			.unwrap_or_else(|| {
@@ -613,3 +966,77 @@ impl<'c, 's> WriterInner<'c, 's> {
 			})
 	}
 }
+
+fn encode_block_header(
+	n_elements: u64,
+	compressed_len: usize,
+	block_header_buffer: &mut [u8; 20],
+) -> Result<NonZeroUsize, SerError> {
+	let n_elements: i64 = n_elements.try_into().map_err(|_| {
+		SerError::new(
+			"n_elements_in_block exceeds i64::MAX \
+				(probably provided incorrect n_elements to write_serialized)",
+		)
+	})?;
+	let n = <i64 as integer_encoding::VarInt>::encode_var(n_elements, block_header_buffer);
+	#[allow(clippy::cast_possible_wrap)]
+	let n2 = <i64 as integer_encoding::VarInt>::encode_var(
+		compressed_len as i64,
+		&mut block_header_buffer[n..],
+	);
+	Ok(NonZeroUsize::new(n + n2).expect("Encoding VarInts should never write zero bytes"))
+}
+
+/// Statistics about the blocks written by a [`Writer`] (or
+/// [`AsyncWriter`](super::AsyncWriter)) so far
+///
+/// This only accounts for blocks that were written to the underlying writer:
+/// records of the block that is currently being built are not counted until
+/// that block is finished.
+///
+/// Byte counts only include the data of the blocks, not the header of the
+/// file nor the block headers and sync markers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WriterStats {
+	/// Number of records written
+	pub n_records: u64,
+	/// Number of blocks written
+	pub n_blocks: u64,
+	/// Total size of the blocks before compression
+	pub uncompressed_bytes: u64,
+	/// Total size of the blocks after compression
+	pub compressed_bytes: u64,
+	/// Total size of the blocks written via
+	/// [`push_raw_block`](Writer::push_raw_block)
+	///
+	/// These are already compressed so their uncompressed size is not known:
+	/// they are not counted in `uncompressed_bytes` and `compressed_bytes`
+	/// (but their records and blocks are counted in `n_records` and
+	/// `n_blocks`).
+	pub raw_bytes: u64,
+}
+
+impl WriterStats {
+	/// `uncompressed_bytes / compressed_bytes`
+	///
+	/// `None` if nothing was compressed by this writer yet.
+	pub fn compression_ratio(&self) -> Option<f64> {
+		#[allow(clippy::cast_precision_loss)]
+		(self.compressed_bytes > 0)
+			.then(|| self.uncompressed_bytes as f64 / self.compressed_bytes as f64)
+	}
+
+	fn block_flushed(&mut self, n_records: u64, uncompressed_len: usize, compressed_len: usize) {
+		self.n_records += n_records;
+		self.n_blocks += 1;
+		self.uncompressed_bytes += uncompressed_len as u64;
+		self.compressed_bytes += compressed_len as u64;
+	}
+
+	fn raw_block_flushed(&mut self, n_records: u64, len: usize) {
+		self.n_records += n_records;
+		self.n_blocks += 1;
+		self.raw_bytes += len as u64;
+	}
+}
@@ -0,0 +1,204 @@
+//! Compression of finished blocks on worker threads, while serialization
+//! continues into the next block
+
+use super::{compression::CompressionCodecState, Compression, SerError};
+
+use std::{
+	collections::VecDeque,
+	sync::{mpsc, Arc, Mutex},
+	thread::JoinHandle,
+};
+
+pub(super) struct ParallelCompression {
+	/// Set to `None` on drop so that workers stop
+	job_sender: Option<mpsc::Sender<Job>>,
+	workers: Vec<JoinHandle<()>>,
+	/// Blocks that were sent to workers, in the order they should be written
+	in_flight: VecDeque<InFlightBlock>,
+	/// Past this number of blocks in flight, we wait for workers before
+	/// serializing more
+	max_in_flight: usize,
+	/// Uncompressed buffers returned by workers, that can be reused for
+	/// serializing the next blocks
+	spare_buffers: Vec<Vec<u8>>,
+}
+
+struct Job {
+	uncompressed: Vec<u8>,
+	result_sender: mpsc::SyncSender<JobResult>,
+}
+
+struct JobResult {
+	compressed: Result<Vec<u8>, SerError>,
+	uncompressed: Vec<u8>,
+}
+
+struct InFlightBlock {
+	n_elements: u64,
+	result_receiver: mpsc::Receiver<JobResult>,
+}
+
+pub(super) struct CompressedBlock {
+	pub(super) n_elements: u64,
+	pub(super) compressed: Vec<u8>,
+	/// Given back so that the buffer can be reused
+	uncompressed: Vec<u8>,
+}
+
+impl CompressedBlock {
+	pub(super) fn uncompressed_len(&self) -> usize {
+		self.uncompressed.len()
+	}
+}
+
+impl ParallelCompression {
+	pub(super) fn new(compression: &Compression, n_threads: usize) -> Result<Self, SerError> {
+		let (job_sender, job_receiver) = mpsc::channel::<Job>();
+		let job_receiver = Arc::new(Mutex::new(job_receiver));
+		let workers = (0..n_threads)
+			.map(|i| {
+				let job_receiver = job_receiver.clone();
+				let compression = compression.clone();
+				std::thread::Builder::new()
+					.name(format!("avro-compression-{i}"))
+					.spawn(move || worker(&compression, &job_receiver))
+					.map_err(|e| {
+						<SerError as serde::ser::Error>::custom(format_args!(
+							"Failed to spawn compression worker thread: {e}"
+						))
+					})
+			})
+			.collect::<Result<_, _>>()?;
+		Ok(Self {
+			job_sender: Some(job_sender),
+			workers,
+			in_flight: VecDeque::new(),
+			max_in_flight: n_threads * 2,
+			spare_buffers: Vec::new(),
+		})
+	}
+
+	/// Send a block to the workers for compression
+	///
+	/// `uncompressed` is replaced with an empty buffer that may be used to
+	/// serialize the next block.
+	pub(super) fn submit(
+		&mut self,
+		uncompressed: &mut Vec<u8>,
+		n_elements: u64,
+	) -> Result<(), SerError> {
+		let replacement = match self.spare_buffers.pop() {
+			Some(mut spare_buffer) => {
+				spare_buffer.clear();
+				spare_buffer
+			}
+			// Likely to end up with the same size as the previous block
+			None => Vec::with_capacity(uncompressed.capacity()),
+		};
+		let uncompressed = std::mem::replace(uncompressed, replacement);
+		let (result_sender, result_receiver) = mpsc::sync_channel(1);
+		self.job_sender
+			.as_ref()
+			.expect("Only unset on drop")
+			.send(Job {
+				uncompressed,
+				result_sender,
+			})
+			// Workers only stop before the sender is dropped if they all panicked
+			.map_err(|_: mpsc::SendError<Job>| worker_panicked())?;
+		self.in_flight.push_back(InFlightBlock {
+			n_elements,
+			result_receiver,
+		});
+		Ok(())
+	}
+
+	/// Get the next compressed block to write, if any
+	///
+	/// If `wait` is `true` or if there are too many blocks in flight, this
+	/// blocks until the next block is compressed. Otherwise, this only returns
+	/// the next block if it's already compressed.
+	pub(super) fn next_compressed_block(
+		&mut self,
+		wait: bool,
+	) -> Result<Option<CompressedBlock>, SerError> {
+		let Some(next) = self.in_flight.front() else {
+			return Ok(None);
+		};
+		let job_result = if wait || self.in_flight.len() > self.max_in_flight {
+			next.result_receiver
+				.recv()
+				.map_err(|_: mpsc::RecvError| worker_panicked())?
+		} else {
+			match next.result_receiver.try_recv() {
+				Ok(job_result) => job_result,
+				Err(mpsc::TryRecvError::Empty) => return Ok(None),
+				Err(mpsc::TryRecvError::Disconnected) => return Err(worker_panicked()),
+			}
+		};
+		let n_elements = self
+			.in_flight
+			.pop_front()
+			.expect("We just looked at it")
+			.n_elements;
+		match job_result.compressed {
+			Ok(compressed) => Ok(Some(CompressedBlock {
+				n_elements,
+				compressed,
+				uncompressed: job_result.uncompressed,
+			})),
+			Err(ser_error) => {
+				self.spare_buffers.push(job_result.uncompressed);
+				Err(ser_error)
+			}
+		}
+	}
+
+	/// Give back a block after it was written, so that its buffers may be
+	/// reused
+	pub(super) fn recycle(&mut self, block: CompressedBlock) {
+		self.spare_buffers.push(block.uncompressed);
+	}
+}
+
+impl Drop for ParallelCompression {
+	fn drop(&mut self) {
+		self.job_sender = None;
+		for worker in self.workers.drain(..) {
+			// If a worker panicked, that was already reported through the
+			// corresponding block
+			let _ = worker.join();
+		}
+	}
+}
+
+fn worker(compression: &Compression, job_receiver: &Mutex<mpsc::Receiver<Job>>) {
+	let mut compression_codec_state = CompressionCodecState::new(compression);
+	loop {
+		let job = {
+			let Ok(job_receiver) = job_receiver.lock() else {
+				// Another worker panicked while holding the lock
+				return;
+			};
+			match job_receiver.recv() {
+				Ok(job) => job,
+				Err(mpsc::RecvError) => return, // Writer was dropped
+			}
+		};
+		let compressed = compression_codec_state.encode(&job.uncompressed).map(|()| {
+			compression_codec_state
+				.compressed_buffer()
+				.unwrap_or(&job.uncompressed)
+				.to_vec()
+		});
+		// If the writer was dropped, nobody is waiting for this block anymore
+		let _ = job.result_sender.send(JobResult {
+			compressed,
+			uncompressed: job.uncompressed,
+		});
+	}
+}
+
+fn worker_panicked() -> SerError {
+	SerError::new("Compression worker thread panicked")
+}
@@ -34,3 +34,31 @@ fn write_all_vectored_inner<'a, W: Write>(
 	}
 	Ok(())
 }
+
+/// Async counterpart of [`write_all_vectored`]
+#[cfg(feature = "tokio")]
+pub(super) async fn write_all_vectored_async<'a, W, const N: usize>(
+	writer: &mut W,
+	slices: [&'a [u8]; N],
+) -> Result<()>
+where
+	W: tokio::io::AsyncWrite + Unpin,
+{
+	let mut bufs = slices.map(IoSlice::new);
+	let mut bufs: &mut [IoSlice<'a>] = &mut bufs;
+	IoSlice::advance_slices(&mut bufs, 0);
+	while !bufs.is_empty() {
+		match tokio::io::AsyncWriteExt::write_vectored(writer, bufs).await {
+			Ok(0) => {
+				return Err(Error::new(
+					ErrorKind::WriteZero,
+					"failed to write whole buffer",
+				));
+			}
+			Ok(n) => IoSlice::advance_slices(&mut bufs, n),
+			Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+			Err(e) => return Err(e),
+		}
+	}
+	Ok(())
+}
@@ -0,0 +1,108 @@
+use super::{RegularType, SchemaError, SchemaKey, SchemaMut};
+
+impl SchemaMut {
+	/// Check that the `default`s of the record fields match the schema of
+	/// their field
+	///
+	/// As specified
+	/// [here](https://avro.apache.org/docs/current/specification/#schema-record),
+	/// the default of a union field should match the first variant of the
+	/// union.
+	///
+	/// This is not called by the parsing functions, as defaults are only used
+	/// by readers when the field is missing from the writer schema, and schemas
+	/// that have invalid defaults are common. It is however useful when
+	/// building a schema whose defaults are meant to be used.
+	pub fn check_defaults(&self) -> Result<(), SchemaError> {
+		for node in &self.nodes {
+			if let RegularType::Record(record) = &node.type_ {
+				for field in &record.fields {
+					if let Some(default) = &field.default {
+						if !self.default_matches(field.type_, default)? {
+							return Err(SchemaError::msg(format_args!(
+								"The default of field `{}` of record `{}` does not match the \
+									schema of the field: {}",
+								field.name,
+								record.name.fully_qualified_name(),
+								default
+							)));
+						}
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+
+	fn default_matches(
+		&self,
+		key: SchemaKey,
+		default: &serde_json::Value,
+	) -> Result<bool, SchemaError> {
+		use serde_json::Value;
+		let node = self
+			.get(key)
+			.ok_or_else(|| SchemaError::new("SchemaKey index is out of bounds"))?;
+		// Bytes and fixed defaults are strings whose code points are the bytes
+		let bytes_len = |s: &str| {
+			s.chars()
+				.try_fold(0, |len, c| (c <= '\u{ff}').then_some(len + 1))
+		};
+		Ok(match (&node.type_, default) {
+			(RegularType::Null, Value::Null) => true,
+			(RegularType::Boolean, Value::Bool(_)) => true,
+			(RegularType::Int, Value::Number(n)) => {
+				n.as_i64().is_some_and(|n| i32::try_from(n).is_ok())
+			}
+			(RegularType::Long, Value::Number(n)) => n.as_i64().is_some(),
+			(RegularType::Float | RegularType::Double, Value::Number(_)) => true,
+			(RegularType::String, Value::String(_)) => true,
+			(RegularType::Bytes, Value::String(s)) => bytes_len(s).is_some(),
+			(RegularType::Fixed(fixed), Value::String(s)) => bytes_len(s) == Some(fixed.size),
+			(RegularType::Enum(enum_), Value::String(s)) => enum_.symbols.contains(s),
+			(RegularType::Array(array), Value::Array(items)) => {
+				for item in items {
+					if !self.default_matches(array.items, item)? {
+						return Ok(false);
+					}
+				}
+				true
+			}
+			(RegularType::Map(map), Value::Object(values)) => {
+				for value in values.values() {
+					if !self.default_matches(map.values, value)? {
+						return Ok(false);
+					}
+				}
+				true
+			}
+			(RegularType::Union(union), _) => match union.variants.first() {
+				// Unions can't directly contain unions (which could also recurse
+				// indefinitely here)
+				Some(&first_variant)
+					if !matches!(
+						self.get(first_variant).map(|node| &node.type_),
+						Some(RegularType::Union(_))
+					) =>
+				{
+					self.default_matches(first_variant, default)?
+				}
+				_ => false,
+			},
+			(RegularType::Record(record), Value::Object(fields)) => {
+				for field in &record.fields {
+					let matches = match fields.get(&field.name) {
+						Some(value) => self.default_matches(field.type_, value)?,
+						// Fields that are not specified take their own default
+						None => field.default.is_some(),
+					};
+					if !matches {
+						return Ok(false);
+					}
+				}
+				true
+			}
+			_ => false,
+		})
+	}
+}
@@ -2,6 +2,7 @@
 //! used for its initialization
 
 mod canonical_form;
+mod check_defaults;
 mod check_for_cycles;
 mod parsing;
 mod rabin;
@@ -31,7 +32,7 @@ pub struct SchemaMut {
 	/// If the schema was parsed from a JSON string, this will contain the
 	/// original JSON string (whitespace removed). This is used to preserve the
 	/// same exact original JSON string when the schema is "serialized back to
-	/// JSON", to avoid losing attributes like `doc`, `default`, ...
+	/// JSON", to avoid losing attributes like `aliases`, `order`, ...
 	/// that this library does not care about.
 	///
 	/// If the schema was not parsed from a JSON string, or was modified later
@@ -54,7 +55,7 @@ impl SchemaMut {
 	///
 	/// This loses the original JSON. If obtaining it again (for e.g. object
 	/// container file encoding) it will be re-generated and will lose all
-	/// non-stored schema fields (`doc`, `aliases`, `default`, ...).
+	/// non-stored schema fields (`aliases`, `order`, ...).
 	///
 	/// The first node (index `0`) is the root of the schema.
 	///
@@ -361,12 +362,27 @@ pub struct Record {
 	pub fields: Vec<RecordField>,
 	/// The name of the record (including namespace)
 	pub name: Name,
+	doc: Option<String>,
 }
 impl Record {
 	/// `name` is the name of the record (including namespace), and `fields` is
 	/// the list of fields in this record.
 	pub fn new(name: Name, fields: Vec<RecordField>) -> Self {
-		Self { fields, name }
+		Self {
+			fields,
+			name,
+			doc: None,
+		}
+	}
+
+	/// Documentation of the record (`doc` attribute)
+	pub fn doc(&self) -> Option<&str> {
+		self.doc.as_deref()
+	}
+
+	/// Set the documentation of the record (`doc` attribute)
+	pub fn set_doc(&mut self, doc: Option<String>) {
+		self.doc = doc;
 	}
 }
 
@@ -378,6 +394,8 @@ pub struct RecordField {
 	pub name: String,
 	/// The key (in the [`SchemaMut`]) of the schema of the type of this field
 	pub type_: SchemaKey,
+	doc: Option<String>,
+	default: Option<serde_json::Value>,
 }
 impl RecordField {
 	/// `schema` is the key (in the [`SchemaMut`]) of the schema of the type of
@@ -386,8 +404,39 @@ impl RecordField {
 		Self {
 			name: name.into(),
 			type_: schema,
+			doc: None,
+			default: None,
 		}
 	}
+
+	/// Documentation of the field (`doc` attribute)
+	pub fn doc(&self) -> Option<&str> {
+		self.doc.as_deref()
+	}
+
+	/// Set the documentation of the field (`doc` attribute)
+	pub fn set_doc(&mut self, doc: Option<String>) {
+		self.doc = doc;
+	}
+
+	/// Default value of the field (`default` attribute), used by readers when
+	/// the field is missing from the writer schema
+	///
+	/// It is represented as JSON, as specified
+	/// [here](https://avro.apache.org/docs/current/specification/#schema-record):
+	/// e.g. `null` for a union whose first variant is `null`, a string for
+	/// `bytes` and `fixed`...
+	pub fn default(&self) -> Option<&serde_json::Value> {
+		self.default.as_ref()
+	}
+
+	/// Set the default value of the field (`default` attribute)
+	///
+	/// It is not checked against the schema of the field: see
+	/// [`SchemaMut::check_defaults`].
+	pub fn set_default(&mut self, default: Option<serde_json::Value>) {
+		self.default = default;
+	}
 }
 
 /// Component of a [`SchemaMut`]
@@ -401,12 +450,27 @@ pub struct Enum {
 	pub symbols: Vec<String>,
 	/// The name of the enum (including namespace)
 	pub name: Name,
+	doc: Option<String>,
 }
 impl Enum {
 	/// `name` is the name of the enum (including namespace), and `symbols` is
 	/// the list of variants of the enum.
 	pub fn new(name: Name, symbols: Vec<String>) -> Self {
-		Self { symbols, name }
+		Self {
+			symbols,
+			name,
+			doc: None,
+		}
+	}
+
+	/// Documentation of the enum (`doc` attribute)
+	pub fn doc(&self) -> Option<&str> {
+		self.doc.as_deref()
+	}
+
+	/// Set the documentation of the enum (`doc` attribute)
+	pub fn set_doc(&mut self, doc: Option<String>) {
+		self.doc = doc;
 	}
 }
 
@@ -422,29 +486,66 @@ pub enum LogicalType {
 	/// `scale` defaults to 0 and is an integer greater than or equal to 0 and
 	/// `precision` is an integer greater than 0.
 	///
+	/// This (de)serializes from/to strings (e.g. `rust_decimal::Decimal`,
+	/// `bigdecimal::BigDecimal`), floats, or integers. Numbers whose unscaled
+	/// integer fits in an `i128` (e.g. precision 38) are always represented
+	/// exactly. Larger ones require the `num-bigint` or `bigdecimal` feature.
+	///
+	/// Strings (and so `rust_decimal::Decimal`) that have more decimal places
+	/// than the schema's `scale` fail to serialize instead of being rounded.
+	/// Floats however are rounded to `scale`, as they are not exact to begin
+	/// with.
+	///
+	/// It may also be (de)serialized as its raw `(unscaled, scale)` tuple
+	/// representation, where `unscaled` is either an integer or its big-endian
+	/// two's complement representation as bytes
+	/// ([`serde_bytes`](https://docs.rs/serde_bytes/latest/serde_bytes/)).
+	///
 	/// <https://avro.apache.org/docs/current/specification/#decimal>
 	Decimal(Decimal),
-	/// A universally unique identifier, annotating a string.
+	/// A universally unique identifier, annotating a string or a fixed of size
+	/// 16.
+	///
+	/// With the `uuid` feature enabled, `uuid::Uuid` (which serializes as a
+	/// string) can be serialized to both representations, and the fixed
+	/// representation is deserialized as a string when hinted so.
 	Uuid,
 	/// Logical type which represents the number of days since the unix epoch.
 	/// Serialization format is `Schema::Int`.
+	///
+	/// With the `chrono`, `time` or `jiff` features enabled, this can also be
+	/// (de)serialized from/to `YYYY-MM-DD` strings (which is what e.g.
+	/// `chrono::NaiveDate` and `jiff::civil::Date` use).
 	Date,
 	/// The time of day in number of milliseconds after midnight with no
 	/// reference any calendar, time zone or date in particular.
 	///
 	/// Annotates an [`Int`](RegularType::Int).
+	///
+	/// With the `chrono`, `time` or `jiff` features enabled, this can also be
+	/// (de)serialized from/to `HH:MM:SS[.fraction]` strings (which is what e.g.
+	/// `chrono::NaiveTime` and `jiff::civil::Time` use).
 	TimeMillis,
 	/// The time of day in number of microseconds after midnight with no
 	/// reference any calendar, time zone or date in particular.
 	///
 	/// Annotates a [`Long`](RegularType::Long).
+	///
+	/// With the `chrono`, `time` or `jiff` features enabled, this can also be
+	/// (de)serialized from/to `HH:MM:SS[.fraction]` strings (which is what e.g.
+	/// `chrono::NaiveTime` and `jiff::civil::Time` use).
 	TimeMicros,
 	/// An instant in time represented as the number of milliseconds after the
 	/// UNIX epoch.
 	///
 	/// Annotates a [`Long`](RegularType::Long).
 	///
-	/// You probably want to use
+	/// With the `chrono`, `time` or `jiff` features enabled, this can also be
+	/// (de)serialized from/to RFC3339 strings, which is what e.g.
+	/// `chrono::DateTime<Utc>`, `jiff::Timestamp` and `time::OffsetDateTime`
+	/// (with `time::serde::rfc3339`) use, so these can be used directly.
+	///
+	/// Otherwise, you probably want to use
 	/// [`TimestampMilliSeconds`](https://docs.rs/serde_with/latest/serde_with/struct.TimestampMilliSeconds.html)
 	/// from [`serde_with`](https://docs.rs/serde_with/latest/serde_with/index.html#examples) when deserializing this.
 	TimestampMillis,
@@ -453,7 +554,12 @@ pub enum LogicalType {
 	///
 	/// Annotates a [`Long`](RegularType::Long).
 	///
-	/// You probably want to use
+	/// With the `chrono`, `time` or `jiff` features enabled, this can also be
+	/// (de)serialized from/to RFC3339 strings, which is what e.g.
+	/// `chrono::DateTime<Utc>`, `jiff::Timestamp` and `time::OffsetDateTime`
+	/// (with `time::serde::rfc3339`) use, so these can be used directly.
+	///
+	/// Otherwise, you probably want to use
 	/// [`TimestampMicroSeconds`](https://docs.rs/serde_with/latest/serde_with/struct.TimestampMicroSeconds.html)
 	/// from [`serde_with`](https://docs.rs/serde_with/latest/serde_with/index.html#examples) when deserializing this.
 	TimestampMicros,
@@ -466,6 +572,9 @@ pub enum LogicalType {
 	Duration,
 	/// Logical type which represents `Decimal` values without predefined scale.
 	/// The underlying type is serialized and deserialized as `Schema::Bytes`
+	///
+	/// This supports the same representations as
+	/// [`Decimal`](LogicalType::Decimal).
 	BigDecimal,
 	/// A logical type that is not known or not handled in any particular way
 	/// by this library.
@@ -94,6 +94,7 @@ impl SchemaMut {
 					| RegularType::Enum(Enum {
 						symbols: _,
 						name: _,
+						doc: _,
 					})
 					| RegularType::Fixed(Fixed { size: _, name: _ }) => {}
 				}
@@ -263,6 +264,7 @@ impl<'a> SchemaConstructionState<'a> {
 					raw::Type::Enum => RegularType::Enum(Enum {
 						name: name()?.0,
 						symbols: field!(symbols).iter().map(|e| (*e.0).to_owned()).collect(),
+						doc: doc(object),
 					}),
 					raw::Type::Fixed => RegularType::Fixed(Fixed {
 						name: name()?.0,
@@ -278,10 +280,13 @@ impl<'a> SchemaConstructionState<'a> {
 										name: (*field.name.0).to_owned(),
 										type_: self
 											.register_node(&field.type_, name_key.namespace)?,
+										doc: field.doc.as_ref().map(|doc| (*doc.0).to_owned()),
+										default: field.default.clone(),
 									})
 								})
 								.collect::<Result<_, SchemaError>>()?,
 							name,
+							doc: doc(object),
 						})
 					}
 					raw::Type::Null => RegularType::Null,
@@ -348,6 +353,12 @@ impl<'a> SchemaConstructionState<'a> {
 	}
 }
 
+fn doc(object: Option<&raw::SchemaNodeObject<'_>>) -> Option<String> {
+	object
+		.and_then(|object| object.doc.as_ref())
+		.map(|doc| (*doc.0).to_owned())
+}
+
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
 struct NameKey<'a> {
 	namespace: Option<&'a str>,
@@ -48,6 +48,8 @@ pub(super) struct SchemaNodeObject<'a> {
 	pub(super) name: Option<BorrowedCowIfPossible<'a>>,
 	/// For named types
 	pub(super) namespace: Option<BorrowedCowIfPossible<'a>>,
+	/// For record and enum types
+	pub(super) doc: Option<BorrowedCowIfPossible<'a>>,
 	/// For record type
 	pub(super) fields: Option<Vec<Field<'a>>>,
 	/// For enum type
@@ -71,6 +73,19 @@ pub(super) struct Field<'a> {
 	pub(super) name: BorrowedCowIfPossible<'a>,
 	#[serde(rename = "type")]
 	pub(super) type_: SchemaNode<'a>,
+	pub(super) doc: Option<BorrowedCowIfPossible<'a>>,
+	/// `"default": null` means that the default is `null`, not that there is
+	/// no default
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub(super) default: Option<serde_json::Value>,
+}
+
+fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+	D: Deserializer<'de>,
+	T: Deserialize<'de>,
+{
+	T::deserialize(deserializer).map(Some)
 }
 
 #[derive(serde_derive::Deserialize)]
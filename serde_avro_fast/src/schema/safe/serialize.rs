@@ -235,6 +235,7 @@ impl Serialize for SerializeSchema<'_, SchemaKey> {
 			RegularType::Record(Record {
 				ref name,
 				ref fields,
+				ref doc,
 			}) => {
 				if self.should_write_as_ref() {
 					serializer.serialize_str(&self.str_for_ref(name))
@@ -242,6 +243,9 @@ impl Serialize for SerializeSchema<'_, SchemaKey> {
 					let mut map = serializer.serialize_map(None)?;
 					serialize_type_and_logical_type("record", &mut map)?;
 					self.serialize_name(&mut map, name)?;
+					if let Some(doc) = doc {
+						map.serialize_entry("doc", doc)?;
+					}
 					map.serialize_entry(
 						"fields",
 						&self.serializable_with_namespace(fields.as_slice(), name.namespace()),
@@ -252,6 +256,7 @@ impl Serialize for SerializeSchema<'_, SchemaKey> {
 			RegularType::Enum(Enum {
 				ref name,
 				ref symbols,
+				ref doc,
 			}) => {
 				if self.should_write_as_ref() {
 					serializer.serialize_str(&self.str_for_ref(name))
@@ -259,6 +264,9 @@ impl Serialize for SerializeSchema<'_, SchemaKey> {
 					let mut map = serializer.serialize_map(None)?;
 					serialize_type_and_logical_type("enum", &mut map)?;
 					self.serialize_name(&mut map, name)?;
+					if let Some(doc) = doc {
+						map.serialize_entry("doc", doc)?;
+					}
 					map.serialize_entry("symbols", symbols)?;
 					map.end()
 				}
@@ -290,9 +298,15 @@ impl Serialize for SerializeSchema<'_, &[RecordField]> {
 
 impl Serialize for SerializeSchema<'_, &RecordField> {
 	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-		let mut map = serializer.serialize_map(Some(2))?;
+		let mut map = serializer.serialize_map(None)?;
 		map.serialize_entry("name", &self.key.name)?;
+		if let Some(doc) = &self.key.doc {
+			map.serialize_entry("doc", doc)?;
+		}
 		map.serialize_entry("type", &self.serializable(self.key.type_))?;
+		if let Some(default) = &self.key.default {
+			map.serialize_entry("default", default)?;
+		}
 		map.end()
 	}
 }
@@ -166,7 +166,7 @@ pub(crate) enum SchemaNode<'a> {
 	Fixed(Fixed),
 	Decimal(Decimal),
 	BigDecimal,
-	Uuid,
+	Uuid(UuidRepr),
 	Date,
 	TimeMillis,
 	TimeMicros,
@@ -247,6 +247,14 @@ pub(crate) enum DecimalRepr {
 	Fixed(Fixed),
 }
 
+/// Component of a [`SchemaNode`]
+#[derive(Clone, Debug)]
+pub(crate) enum UuidRepr {
+	String,
+	/// Always of size 16
+	Fixed(Fixed),
+}
+
 impl TryFrom<super::safe::SchemaMut> for Schema {
 	type Error = SchemaError;
 	fn try_from(mut safe: super::safe::SchemaMut) -> Result<Self, SchemaError> {
@@ -321,7 +329,11 @@ impl TryFrom<super::safe::SchemaMut> for Schema {
 				SafeSchemaNode {
 					logical_type: Some(LogicalType::Uuid),
 					type_: SafeSchemaType::String,
-				} => SchemaNode::Uuid,
+				} => SchemaNode::Uuid(UuidRepr::String),
+				SafeSchemaNode {
+					logical_type: Some(LogicalType::Uuid),
+					type_: SafeSchemaType::Fixed(fixed),
+				} if fixed.size == 16 => SchemaNode::Uuid(UuidRepr::Fixed(fixed)),
 				SafeSchemaNode {
 					logical_type: Some(LogicalType::Date),
 					type_: SafeSchemaType::Int,
@@ -537,7 +549,13 @@ impl<'a> std::fmt::Debug for SchemaNode<'a> {
 				d.finish()
 			}
 			SchemaNode::BigDecimal => f.debug_tuple("BigDecimal").finish(),
-			SchemaNode::Uuid => f.debug_tuple("Uuid").finish(),
+			SchemaNode::Uuid(ref inner) => {
+				let mut d = f.debug_tuple("Uuid");
+				if depth < MAX_DEPTH {
+					d.field(inner);
+				}
+				d.finish()
+			}
 			SchemaNode::Date => f.debug_tuple("Date").finish(),
 			SchemaNode::TimeMillis => f.debug_tuple("TimeMillis").finish(),
 			SchemaNode::TimeMicros => f.debug_tuple("TimeMicros").finish(),
@@ -243,44 +243,74 @@ impl<'a> PerTypeLookup<'a> {
 					register(UnionVariantLookupKey::Float8, 2);
 					register(UnionVariantLookupKey::Str, 20);
 				}
-				SchemaNode::Uuid => {
+				SchemaNode::Uuid(repr) => {
 					register_type_name("Uuid");
 					// A user may assume that uuid::Uuid will serialize to Uuid by default,
 					// but since it serializes as &str by default, we in fact can't distinguish
 					// between that and &str, so we'll error in case union has both Uuid and String
 					// to avoid unexpected behavior on the user side.
 					// They may specify using enums.
-					register(UnionVariantLookupKey::Str, 0);
+					match repr {
+						UuidRepr::String => {
+							register(UnionVariantLookupKey::Str, 0);
+						}
+						UuidRepr::Fixed(fixed) => {
+							// Can be serialized to as any other fixed
+							register_name(&fixed.name);
+							register(UnionVariantLookupKey::SliceU8, 0);
+							register(UnionVariantLookupKey::SeqOrTupleOrTupleStruct, 2);
+							// Parsing a str to a fixed uuid requires the uuid crate
+							#[cfg(feature = "uuid")]
+							register(UnionVariantLookupKey::Str, 0);
+							#[cfg(not(feature = "uuid"))]
+							register(UnionVariantLookupKey::Str, 15);
+						}
+					}
 				}
 				SchemaNode::Date => {
 					register_type_name("Date");
 					register(UnionVariantLookupKey::Integer, 0);
 					register(UnionVariantLookupKey::Integer4, 0);
 					register(UnionVariantLookupKey::Integer8, 1);
+					// chrono, time and jiff types serialize as str
+					#[cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+					register(UnionVariantLookupKey::Str, 12);
 				}
 				SchemaNode::TimeMillis => {
 					register_type_name("TimeMillis");
 					register(UnionVariantLookupKey::Integer, 0);
 					register(UnionVariantLookupKey::Integer4, 0);
 					register(UnionVariantLookupKey::Integer8, 1);
+					#[cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+					register(UnionVariantLookupKey::Str, 12);
 				}
 				SchemaNode::TimeMicros => {
 					register_type_name("TimeMicros");
 					register(UnionVariantLookupKey::Integer, 0);
 					register(UnionVariantLookupKey::Integer4, 1);
 					register(UnionVariantLookupKey::Integer8, 0);
+					#[cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+					register(UnionVariantLookupKey::Str, 12);
 				}
 				SchemaNode::TimestampMillis => {
 					register_type_name("TimestampMillis");
 					register(UnionVariantLookupKey::Integer, 0);
 					register(UnionVariantLookupKey::Integer4, 1);
 					register(UnionVariantLookupKey::Integer8, 0);
+					#[cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+					register(UnionVariantLookupKey::Str, 12);
+					// std::time::SystemTime serializes as a struct
+					register(UnionVariantLookupKey::StructOrMap, 12);
 				}
 				SchemaNode::TimestampMicros => {
 					register_type_name("TimestampMicros");
 					register(UnionVariantLookupKey::Integer, 0);
 					register(UnionVariantLookupKey::Integer4, 1);
 					register(UnionVariantLookupKey::Integer8, 0);
+					#[cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+					register(UnionVariantLookupKey::Str, 12);
+					// std::time::SystemTime serializes as a struct
+					register(UnionVariantLookupKey::StructOrMap, 12);
 				}
 				SchemaNode::Duration => {
 					register(UnionVariantLookupKey::StructOrMap, 5);
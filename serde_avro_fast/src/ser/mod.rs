@@ -108,6 +108,7 @@ pub struct SerializerState<'c, 's, W> {
 pub struct SerializerConfig<'s> {
 	buffers: Buffers,
 	allow_slow_sequence_to_bytes: bool,
+	allow_sub_unit_precision_truncation: bool,
 	/// This schema is the default when building a serializer (or otherwise
 	/// calling `.schema()`). It can only be set to `None` within this crate.
 	/// Allowing overriding of the SchemaNode when building the serializer is a
@@ -136,6 +137,7 @@ impl<'s> SerializerConfig<'s> {
 		Self {
 			schema,
 			allow_slow_sequence_to_bytes: false,
+			allow_sub_unit_precision_truncation: false,
 			buffers: Buffers::default(),
 		}
 	}
@@ -154,6 +156,21 @@ impl<'s> SerializerConfig<'s> {
 		self
 	}
 
+	/// Truncate timestamps, times of day and durations whose precision is finer
+	/// than that of the schema, instead of erroring.
+	///
+	/// By default, serializing e.g. a `std::time::SystemTime`, a
+	/// `chrono::DateTime` or a `jiff::Timestamp` that has sub-microsecond
+	/// precision to a `timestamp-micros` fails, as that precision would be
+	/// lost. Since the clocks of most platforms have nanosecond precision, you
+	/// may want to enable this when serializing e.g. the current time.
+	///
+	/// Values are truncated towards the past.
+	pub fn allow_sub_unit_precision_truncation(&mut self) -> &mut Self {
+		self.allow_sub_unit_precision_truncation = true;
+		self
+	}
+
 	/// Get the schema that was used when creating this `SerializerConfig`.
 	///
 	/// That is the one that will be used when building a serializer from this
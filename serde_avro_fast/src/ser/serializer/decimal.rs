@@ -1,10 +1,15 @@
 use super::*;
 
+#[derive(Clone, Copy)]
 pub(in super::super) enum DecimalMode<'a> {
 	Big,
 	Regular(&'a Decimal),
 }
 
+/// Serialize a `rust_decimal::Decimal`, rounding it to the scale of the schema
+///
+/// This is used for floats, which are approximations anyway. Strings are
+/// checked not to be rounded before calling this.
 pub(super) fn serialize<'r, 'c, 's, W>(
 	state: &'r mut SerializerState<'c, 's, W>,
 	decimal_mode: DecimalMode<'s>,
@@ -13,26 +18,112 @@ pub(super) fn serialize<'r, 'c, 's, W>(
 where
 	W: Write,
 {
-	let mut scale_buf = [0; 10];
-	let scale_to_write = match decimal_mode {
+	let scale = match decimal_mode {
 		DecimalMode::Regular(decimal) => {
 			// Try to scale it appropriately
 			rust_decimal.rescale(decimal.scale);
 			if rust_decimal.scale() != decimal.scale {
-				return Err(SerError::new(
-					"Decimal number cannot be scaled to fit in schema scale \
-						with a 96 bit mantissa (number or scale too large)",
-				));
+				// Number or scale too large for the 96 bit mantissa: we can still represent it
+				// exactly, just not through rust_decimal
+				return serialize_exact(state, decimal_mode, &rust_decimal.to_string());
+			}
+			decimal.scale
+		}
+		DecimalMode::Big => rust_decimal.scale(),
+	};
+	serialize_unscaled(
+		state,
+		decimal_mode,
+		&rust_decimal.mantissa().to_be_bytes(),
+		scale,
+	)
+}
+
+pub(super) fn serialize_str<'r, 'c, 's, W>(
+	state: &'r mut SerializerState<'c, 's, W>,
+	decimal_mode: DecimalMode<'s>,
+	v: &str,
+) -> Result<(), SerError>
+where
+	W: Write,
+{
+	// Favor rust_decimal when it can represent the number exactly, as it's faster
+	match rust_decimal::Decimal::from_str_exact(v) {
+		Ok(rust_decimal) => {
+			// Strings are never rounded (rescaling would round)
+			if let DecimalMode::Regular(decimal) = decimal_mode {
+				if rust_decimal.normalize().scale() > decimal.scale {
+					return Err(SerError::custom(format_args!(
+						"Decimal number {v:?} can not be represented with scale {} \
+							without losing precision",
+						decimal.scale
+					)));
+				}
+			}
+			serialize(state, decimal_mode, rust_decimal)
+		}
+		Err(_) => serialize_exact(state, decimal_mode, v),
+	}
+}
+
+/// Serialize a decimal without going through `rust_decimal`, which enables
+/// arbitrary precision
+fn serialize_exact<'r, 'c, 's, W>(
+	state: &'r mut SerializerState<'c, 's, W>,
+	decimal_mode: DecimalMode<'s>,
+	v: &str,
+) -> Result<(), SerError>
+where
+	W: Write,
+{
+	let target_scale = match decimal_mode {
+		DecimalMode::Regular(decimal) => Some(decimal.scale),
+		DecimalMode::Big => None,
+	};
+	let (unscaled, scale) = crate::decimal::parse(v, target_scale).map_err(|e| {
+		SerError::custom(format_args!(
+			"str cannot be converted to decimal for serialization as {}: {e}",
+			match decimal_mode {
+				DecimalMode::Regular(_) => "Decimal",
+				DecimalMode::Big => "BigDecimal",
+			}
+		))
+	})?;
+	unscaled.with_be_bytes(|buf| serialize_unscaled(state, decimal_mode, buf, scale))
+}
+
+/// Serialize a decimal from its unscaled integer, represented as big-endian
+/// two's complement, and its scale
+///
+/// For regular decimals, `scale` has to match the schema's.
+pub(super) fn serialize_unscaled<'r, 'c, 's, W>(
+	state: &'r mut SerializerState<'c, 's, W>,
+	decimal_mode: DecimalMode<'s>,
+	buf: &[u8],
+	scale: u32,
+) -> Result<(), SerError>
+where
+	W: Write,
+{
+	// Zero may be represented as empty bytes
+	let buf = if buf.is_empty() { &[0][..] } else { buf };
+	let mut scale_buf = [0; 10];
+	let scale_to_write = match decimal_mode {
+		DecimalMode::Regular(decimal) => {
+			if scale != decimal.scale {
+				return Err(SerError::custom(format_args!(
+					"Decimal scale ({scale}) does not match schema scale ({})",
+					decimal.scale
+				)));
 			}
 			&[]
 		}
 		DecimalMode::Big => {
-			let scale: i64 = rust_decimal.scale().into();
+			let scale: i64 = scale.into();
 			let n = <i64 as integer_encoding::VarInt>::encode_var(scale, &mut scale_buf);
 			&scale_buf[0..n]
 		}
 	};
-	let buf: [u8; 16] = rust_decimal.mantissa().to_be_bytes();
 	#[inline]
 	fn can_truncate_without_altering_number(buf: &[u8]) -> usize {
 		// If it's a negative number we can ignore all 0xff followed by MSB
@@ -68,28 +159,29 @@ where
 		}) => {
 			// If it's a negative number we can ignore all 0xff followed by MSB
 			// at 1 If it's a positive number we can ignore all 0x00 followed by MSB at 0
-			let start = can_truncate_without_altering_number(&buf);
-			// #[allow] is sound: buf is [u8; 16], so buf.len() - start is at most 16
-			#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
-			let len = (buf.len() - start) as i32;
+			let start = can_truncate_without_altering_number(buf);
+			let len: i64 = (buf.len() - start)
+				.try_into()
+				.map_err(|_| SerError::new("Decimal is too large to be serialized"))?;
 			match decimal_mode {
 				DecimalMode::Big => {
 					// We need to write the length of the full bytes, then write
 					// the length of the unscaled
 					assert!(!scale_to_write.is_empty());
 					let mut len_buf = [0; 10];
-					let len_len = <i32 as integer_encoding::VarInt>::encode_var(len, &mut len_buf);
+					let len_len = <i64 as integer_encoding::VarInt>::encode_var(len, &mut len_buf);
 					state
 						.writer
-						.write_varint::<i32>({
+						.write_varint::<i64>({
 							// #[allow] is sound because:
-							// len_len <= 5 (VarInt of i32)
-							// len <= 16
+							// len_len <= 10 (VarInt of i64)
 							// scale_to_write.len() <= 10 (VarInt of i64)
-							#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
-							{
-								len_len as i32 + len + scale_to_write.len() as i32
-							}
+							#[allow(clippy::cast_possible_wrap)]
+							{ (len_len + scale_to_write.len()) as i64 }
+								.checked_add(len)
+								.ok_or_else(|| {
+									SerError::new("Decimal is too large to be serialized")
+								})?
 						})
 						.map_err(SerError::io)?;
 					state
@@ -104,7 +196,7 @@ where
 					// We need to write the length of the bytes
 					state
 						.writer
-						.write_varint::<i32>(len)
+						.write_varint::<i64>(len)
 						.map_err(SerError::io)?;
 				}
 				DecimalMode::Regular(Decimal {
@@ -137,7 +229,7 @@ where
 						None => {
 							assert!(size == 0);
 							// We only know how to represent 0 in this case (empty bytes)
-							if !rust_decimal.is_zero() {
+							if buf.iter().any(|&b| b != 0) {
 								return Err(SerError::new(
 									"Non-zero decimal number can not be serialized \
 										as a fixed size decimal with size 0",
@@ -0,0 +1,135 @@
+use super::*;
+
+use crate::decimal::Unscaled;
+
+/// Extracts the unscaled integer of a decimal serialized as its raw
+/// `(unscaled, scale)` representation
+///
+/// It may be either an integer or the big-endian two's complement
+/// representation of the integer as bytes.
+pub(super) struct ExtractUnscaledForDecimal;
+impl serde::Serializer for ExtractUnscaledForDecimal {
+	type Ok = Unscaled;
+	type Error = SerError;
+
+	fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+		Ok(Unscaled::I128(v.into()))
+	}
+
+	fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+		Ok(Unscaled::I128(v.into()))
+	}
+
+	fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+		Ok(Unscaled::I128(v.into()))
+	}
+
+	fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+		Ok(Unscaled::I128(v.into()))
+	}
+
+	fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+		Ok(Unscaled::I128(v))
+	}
+
+	fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+		Ok(Unscaled::I128(v.into()))
+	}
+
+	fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+		Ok(Unscaled::I128(v.into()))
+	}
+
+	fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+		Ok(Unscaled::I128(v.into()))
+	}
+
+	fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+		Ok(Unscaled::I128(v.into()))
+	}
+
+	fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+		Ok(match i128::try_from(v) {
+			Ok(v) => Unscaled::I128(v),
+			Err(_) => {
+				// MSB is set so we need an additional byte for the sign
+				let mut buf = Vec::with_capacity(17);
+				buf.push(0);
+				buf.extend_from_slice(&v.to_be_bytes());
+				Unscaled::Big(buf)
+			}
+		})
+	}
+
+	fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+		Ok(Unscaled::Big(v.to_vec()))
+	}
+
+	serde_serializer_quick_unsupported::serializer_unsupported! {
+		err = (SerError::new(
+			"Unscaled integer should be an integer or bytes for serialization as Decimal"
+		));
+		bool f32 f64 char str none some unit unit_struct unit_variant newtype_struct
+		newtype_variant seq tuple tuple_struct tuple_variant map struct struct_variant
+	}
+}
+
+/// Extracts the scale of a decimal serialized as its raw `(unscaled, scale)`
+/// representation
+pub(super) struct ExtractScaleForDecimal;
+impl ExtractScaleForDecimal {
+	fn scale<N: TryInto<u32>>(v: N) -> Result<u32, SerError> {
+		v.try_into()
+			.map_err(|_| SerError::new("Decimal scale should fit in a u32"))
+	}
+}
+impl serde::Serializer for ExtractScaleForDecimal {
+	type Ok = u32;
+	type Error = SerError;
+
+	fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+		Self::scale(v)
+	}
+
+	fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+		Self::scale(v)
+	}
+
+	fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+		Self::scale(v)
+	}
+
+	fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+		Self::scale(v)
+	}
+
+	fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+		Self::scale(v)
+	}
+
+	fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+		Self::scale(v)
+	}
+
+	fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+		Self::scale(v)
+	}
+
+	fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+		Ok(v)
+	}
+
+	fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+		Self::scale(v)
+	}
+
+	fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+		Self::scale(v)
+	}
+
+	serde_serializer_quick_unsupported::serializer_unsupported! {
+		err = (SerError::new("Scale should be an integer for serialization as Decimal"));
+		bool f32 f64 char str bytes none some unit unit_struct unit_variant newtype_struct
+		newtype_variant seq tuple tuple_struct tuple_variant map struct struct_variant
+	}
+}
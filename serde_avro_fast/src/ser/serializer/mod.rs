@@ -1,8 +1,12 @@
 mod blocks;
 mod decimal;
+mod extract_for_decimal;
 mod extract_for_duration;
 mod seq_or_tuple;
+mod std_time;
 mod struct_or_map;
+#[cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+mod temporal;
 
 use super::*;
 
@@ -88,6 +92,23 @@ impl<'r, 'c, 's, W: Write> Serializer for DatumSerializer<'r, 'c, 's, W> {
 	}
 
 	fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+		if i128::try_from(v).is_err() {
+			// Decimals can still represent it, with an additional byte for the sign
+			let (decimal_mode, scale) = match self.schema_node {
+				SchemaNode::Decimal(decimal) => {
+					(decimal::DecimalMode::Regular(decimal), decimal.scale)
+				}
+				SchemaNode::BigDecimal => (decimal::DecimalMode::Big, 0),
+				_ => return self.serialize_integer(v),
+			};
+			return if scale == 0 {
+				let mut buf = [0; 17];
+				buf[1..].copy_from_slice(&v.to_be_bytes());
+				decimal::serialize_unscaled(self.state, decimal_mode, &buf, 0)
+			} else {
+				decimal::serialize_str(self.state, decimal_mode, &v.to_string())
+			};
+		}
 		self.serialize_integer(v)
 	}
 
@@ -169,9 +190,28 @@ impl<'r, 'c, 's, W: Write> Serializer for DatumSerializer<'r, 'c, 's, W> {
 
 	fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
 		match self.schema_node {
-			SchemaNode::String | SchemaNode::Bytes | SchemaNode::Uuid => {
+			SchemaNode::String | SchemaNode::Bytes | SchemaNode::Uuid(UuidRepr::String) => {
 				self.state.write_length_delimited(v.as_bytes())
 			}
+			// A str of the fixed's size is written as-is, as for any other fixed
+			SchemaNode::Uuid(UuidRepr::Fixed(fixed)) if fixed.size == v.len() => self
+				.state
+				.writer
+				.write_all(v.as_bytes())
+				.map_err(SerError::io),
+			#[cfg(feature = "uuid")]
+			SchemaNode::Uuid(UuidRepr::Fixed(_)) => {
+				let uuid = uuid::Uuid::try_parse(v).map_err(|parse_err| {
+					SerError::custom(format_args!(
+						"str cannot be converted to uuid for serialization as fixed Uuid: {}",
+						parse_err
+					))
+				})?;
+				self.state
+					.writer
+					.write_all(uuid.as_bytes())
+					.map_err(SerError::io)
+			}
 			SchemaNode::Enum(
 				e @ Enum {
 					per_name_lookup, ..
@@ -203,26 +243,23 @@ impl<'r, 'c, 's, W: Write> Serializer for DatumSerializer<'r, 'c, 's, W> {
 				}
 			}
 			SchemaNode::Decimal(decimal) => {
-				let rust_decimal: rust_decimal::Decimal = v.parse().map_err(|parse_err| {
-					SerError::custom(format_args!(
-						"str cannot be converted to decimal for serialization as Decimal: {}",
-						parse_err
-					))
-				})?;
-				decimal::serialize(
-					self.state,
-					decimal::DecimalMode::Regular(decimal),
-					rust_decimal,
-				)
+				decimal::serialize_str(self.state, decimal::DecimalMode::Regular(decimal), v)
 			}
 			SchemaNode::BigDecimal => {
-				let rust_decimal: rust_decimal::Decimal = v.parse().map_err(|parse_err| {
-					SerError::custom(format_args!(
-						"str cannot be converted to decimal for serialization as BigDecimal: {}",
-						parse_err
-					))
-				})?;
-				decimal::serialize(self.state, decimal::DecimalMode::Big, rust_decimal)
+				decimal::serialize_str(self.state, decimal::DecimalMode::Big, v)
+			}
+			#[cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+			SchemaNode::Date
+			| SchemaNode::TimeMillis
+			| SchemaNode::TimeMicros
+			| SchemaNode::TimestampMillis
+			| SchemaNode::TimestampMicros => {
+				let n = temporal::parse_str(
+					self.schema_node,
+					v,
+					self.state.config.allow_sub_unit_precision_truncation,
+				)?;
+				self.serialize_integer(n)
 			}
 			SchemaNode::Union(union) => {
 				self.serialize_union_unnamed(union, UnionVariantLookupKey::Str, |ser| {
@@ -239,7 +276,8 @@ impl<'r, 'c, 's, W: Write> Serializer for DatumSerializer<'r, 'c, 's, W> {
 	fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
 		match self.schema_node {
 			SchemaNode::Bytes | SchemaNode::String => self.state.write_length_delimited(v),
-			SchemaNode::Fixed(Fixed { size, .. }) => {
+			SchemaNode::Fixed(Fixed { size, .. })
+			| SchemaNode::Uuid(UuidRepr::Fixed(Fixed { size, .. })) => {
 				if *size != v.len() {
 					Err(SerError::new(
 						"Can't serialize &[u8] as Fixed: slice's len does not match Fixed's size",
@@ -248,6 +286,19 @@ impl<'r, 'c, 's, W: Write> Serializer for DatumSerializer<'r, 'c, 's, W> {
 					self.state.writer.write_all(v).map_err(SerError::io)
 				}
 			}
+			#[cfg(feature = "uuid")]
+			SchemaNode::Uuid(UuidRepr::String) => {
+				let uuid = uuid::Uuid::from_slice(v).map_err(|e| {
+					SerError::custom(format_args!(
+						"&[u8] cannot be converted to uuid for serialization as string Uuid: {e}"
+					))
+				})?;
+				self.state.write_length_delimited(
+					uuid.hyphenated()
+						.encode_lower(&mut uuid::Uuid::encode_buffer())
+						.as_bytes(),
+				)
+			}
 			SchemaNode::Duration => {
 				// In that case we assume that it's the raw value.
 				// This is the most efficient way to deserialize it then
@@ -390,6 +441,15 @@ impl<'r, 'c, 's, W: Write> Serializer for DatumSerializer<'r, 'c, 's, W> {
 					Ok(SerializeSeqOrTupleOrTupleStruct::duration(self.state))
 				}
 			}
+			SchemaNode::Decimal(decimal) if len == Some(2) => {
+				Ok(SerializeSeqOrTupleOrTupleStruct::decimal(
+					self.state,
+					decimal::DecimalMode::Regular(decimal),
+				))
+			}
+			SchemaNode::BigDecimal if len == Some(2) => Ok(
+				SerializeSeqOrTupleOrTupleStruct::decimal(self.state, decimal::DecimalMode::Big),
+			),
 			SchemaNode::Bytes => {
 				self.state.check_allowed_slow_sequence_to_bytes()?;
 				match len {
@@ -397,7 +457,7 @@ impl<'r, 'c, 's, W: Write> Serializer for DatumSerializer<'r, 'c, 's, W> {
 					Some(len) => SerializeSeqOrTupleOrTupleStruct::bytes(self.state, len),
 				}
 			}
-			SchemaNode::Fixed(fixed) => {
+			SchemaNode::Fixed(fixed) | SchemaNode::Uuid(UuidRepr::Fixed(fixed)) => {
 				self.state.check_allowed_slow_sequence_to_bytes()?;
 				if len.is_some_and(|l| l != fixed.size) {
 					Err(SerError::new(
@@ -410,6 +470,13 @@ impl<'r, 'c, 's, W: Write> Serializer for DatumSerializer<'r, 'c, 's, W> {
 					))
 				}
 			}
+			SchemaNode::Record(record) => {
+				if len != Some(record.fields.len()) {
+					Err(seq_or_tuple::record_seq_len_incorrect())
+				} else {
+					Ok(SerializeSeqOrTupleOrTupleStruct::record(self.state, record))
+				}
+			}
 			SchemaNode::Union(union) => self.serialize_union_unnamed(
 				union,
 				UnionVariantLookupKey::SeqOrTupleOrTupleStruct,
@@ -428,10 +495,14 @@ impl<'r, 'c, 's, W: Write> Serializer for DatumSerializer<'r, 'c, 's, W> {
 
 	fn serialize_tuple_struct(
 		self,
-		_name: &'static str,
+		name: &'static str,
 		len: usize,
 	) -> Result<Self::SerializeTupleStruct, Self::Error> {
-		self.serialize_seq(Some(len))
+		// Tuple structs may be represented as records, which we may find in unions by
+		// name
+		self.serialize_lookup_union_variant_by_name(name, |serializer| {
+			serializer.serialize_seq(Some(len))
+		})
 	}
 
 	fn serialize_tuple_variant(
@@ -573,44 +644,37 @@ impl<'r, 'c, 's, W: Write> DatumSerializer<'r, 'c, 's, W> {
 				Ok(())
 			}
 			SchemaNode::Decimal(decimal) => {
-				let mut n: i128 = num.try_into().map_err(|_| {
+				let n: i128 = num.try_into().map_err(|_| {
 					SerError::new("Number does not fit i128 for encoding as Decimal")
 				})?;
-				n = 10i128
+				match 10i128
 					.checked_pow(decimal.scale)
 					.and_then(|pow| n.checked_mul(pow))
-					.ok_or_else(|| {
-						SerError::new(
-							"Integer to be encoded as decimal, multiplied by 10^scale \
-								is too large to fit in an i128. This is unsupported.",
-						)
-					})?;
-				let bytes = n.to_be_bytes();
-				let buf = match decimal.repr {
-					DecimalRepr::Bytes => {
-						let mut start = 0;
-						while start < bytes.len() - 1 && bytes[start] == 0 {
-							start += 1;
-						}
-						let buf = &bytes[start..];
-						self.state
-							.writer
-							.write_varint::<i64>(buf.len().try_into().map_err(|_| {
-								SerError::new(
-									"Buffer len does not fit i64 for encoding as bytes size",
-								)
-							})?)
-							.map_err(SerError::io)?;
-						buf
-					}
-					DecimalRepr::Fixed(ref fixed) => {
-						let start = bytes.len().checked_sub(fixed.size).ok_or_else(|| {
-							SerError::custom("Decimals of size larger than 16 are not supported")
-						})?;
-						&bytes[start..]
-					}
-				};
-				self.state.writer.write_all(buf).map_err(SerError::io)
+				{
+					Some(unscaled) => decimal::serialize_unscaled(
+						self.state,
+						decimal::DecimalMode::Regular(decimal),
+						&unscaled.to_be_bytes(),
+						decimal.scale,
+					),
+					// Multiplied by 10^scale, this doesn't fit in an i128 anymore
+					None => decimal::serialize_str(
+						self.state,
+						decimal::DecimalMode::Regular(decimal),
+						&n.to_string(),
+					),
+				}
+			}
+			SchemaNode::BigDecimal => {
+				let n: i128 = num.try_into().map_err(|_| {
+					SerError::new("Number does not fit i128 for encoding as BigDecimal")
+				})?;
+				decimal::serialize_unscaled(
+					self.state,
+					decimal::DecimalMode::Big,
+					&n.to_be_bytes(),
+					0,
+				)
 			}
 			SchemaNode::Enum(_) => {
 				self.state
@@ -670,6 +734,14 @@ impl<'r, 'c, 's, W: Write> DatumSerializer<'r, 'c, 's, W> {
 		len: usize,
 	) -> Result<SerializeStructAsRecordOrMapOrDuration<'r, 'c, 's, W>, SerError> {
 		self.serialize_lookup_union_variant_by_name(variant_or_struct_name, |serializer| {
+			if let Some(target) =
+				std_time::StdTimeTarget::new(serializer.schema_node, variant_or_struct_name, len)
+			{
+				return Ok(SerializeStructAsRecordOrMapOrDuration::std_time(
+					serializer.state,
+					target,
+				));
+			}
 			match *serializer.schema_node {
 				SchemaNode::Record(ref record) => Ok(
 					SerializeStructAsRecordOrMapOrDuration::record(serializer.state, record),
@@ -685,11 +757,36 @@ impl<'r, 'c, 's, W: Write> DatumSerializer<'r, 'c, 's, W> {
 					}
 					SerializeStructAsRecordOrMapOrDuration::duration(serializer.state)
 				}
-				SchemaNode::Union(ref union) => serializer.serialize_union_unnamed(
-					union,
-					UnionVariantLookupKey::StructOrMap,
-					|ser| ser.serialize_struct_or_struct_variant(variant_or_struct_name, len),
-				),
+				SchemaNode::Union(ref union) => {
+					if union
+						.per_type_lookup
+						.unnamed(UnionVariantLookupKey::StructOrMap)
+						.is_none() && union
+						.variants
+						.iter()
+						.filter(|variant| matches!(variant.as_ref(), SchemaNode::Record(_)))
+						.nth(1)
+						.is_some()
+					{
+						// There are several records in the union and the name of the struct
+						// didn't allow picking one: this may be an internally tagged enum,
+						// whose first field is the name of the variant
+						Ok(
+							SerializeStructAsRecordOrMapOrDuration::union_record_from_tag(
+								serializer.state,
+								union,
+							),
+						)
+					} else {
+						serializer.serialize_union_unnamed(
+							union,
+							UnionVariantLookupKey::StructOrMap,
+							|ser| {
+								ser.serialize_struct_or_struct_variant(variant_or_struct_name, len)
+							},
+						)
+					}
+				}
 				_ => Err(SerError::custom(format_args!(
 					"Could not serialize struct `{variant_or_struct_name}` to {:?}",
 					serializer.schema_node
@@ -13,6 +13,12 @@ enum Kind<'r, 'c, 's, W> {
 		serializer_state: &'r mut SerializerState<'c, 's, W>,
 		n_values: u8,
 	},
+	Decimal {
+		serializer_state: &'r mut SerializerState<'c, 's, W>,
+		decimal_mode: super::decimal::DecimalMode<'s>,
+		unscaled: Option<crate::decimal::Unscaled>,
+		scale: Option<u32>,
+	},
 	BufferedBytes {
 		serializer_state: &'r mut SerializerState<'c, 's, W>,
 		buffer: Vec<u8>,
@@ -21,6 +27,10 @@ enum Kind<'r, 'c, 's, W> {
 		serializer_state: &'r mut SerializerState<'c, 's, W>,
 		expected_len: usize,
 	},
+	Record {
+		serializer_state: &'r mut SerializerState<'c, 's, W>,
+		remaining_fields: std::slice::Iter<'s, RecordField<'s>>,
+	},
 	Finished,
 }
 
@@ -46,6 +56,20 @@ impl<'r, 'c, 's, W: Write> SerializeSeqOrTupleOrTupleStruct<'r, 'c, 's, W> {
 		}
 	}
 
+	pub(super) fn decimal(
+		serializer_state: &'r mut SerializerState<'c, 's, W>,
+		decimal_mode: super::decimal::DecimalMode<'s>,
+	) -> Self {
+		Self {
+			kind: Kind::Decimal {
+				serializer_state,
+				decimal_mode,
+				unscaled: None,
+				scale: None,
+			},
+		}
+	}
+
 	pub(crate) fn buffered_bytes(state: &'r mut SerializerState<'c, 's, W>) -> Self {
 		Self {
 			kind: Kind::BufferedBytes {
@@ -85,6 +109,20 @@ impl<'r, 'c, 's, W: Write> SerializeSeqOrTupleOrTupleStruct<'r, 'c, 's, W> {
 		}
 	}
 
+	/// Fields are serialized by position, so that tuples (tuple structs, tuple
+	/// variants...) may be serialized as records
+	pub(super) fn record(
+		serializer_state: &'r mut SerializerState<'c, 's, W>,
+		record: &'s Record<'s>,
+	) -> Self {
+		Self {
+			kind: Kind::Record {
+				serializer_state,
+				remaining_fields: record.fields.iter(),
+			},
+		}
+	}
+
 	fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerError>
 	where
 		T: Serialize + ?Sized,
@@ -117,6 +155,23 @@ impl<'r, 'c, 's, W: Write> SerializeSeqOrTupleOrTupleStruct<'r, 'c, 's, W> {
 					Ok(())
 				}
 			}
+			Kind::Decimal {
+				ref mut unscaled,
+				ref mut scale,
+				..
+			} => {
+				if unscaled.is_none() {
+					*unscaled = Some(
+						value.serialize(super::extract_for_decimal::ExtractUnscaledForDecimal)?,
+					);
+				} else if scale.is_none() {
+					*scale =
+						Some(value.serialize(super::extract_for_decimal::ExtractScaleForDecimal)?);
+				} else {
+					return Err(decimal_seq_len_incorrect());
+				}
+				Ok(())
+			}
 			Kind::BufferedBytes { ref mut buffer, .. } => {
 				buffer.push(value.serialize(ExtractU8Serializer)?);
 				Ok(())
@@ -140,6 +195,16 @@ impl<'r, 'c, 's, W: Write> SerializeSeqOrTupleOrTupleStruct<'r, 'c, 's, W> {
 
 				Ok(())
 			}
+			Kind::Record {
+				ref mut serializer_state,
+				ref mut remaining_fields,
+			} => match remaining_fields.next() {
+				None => Err(record_seq_len_incorrect()),
+				Some(field) => value.serialize(DatumSerializer {
+					state: serializer_state,
+					schema_node: field.schema.as_ref(),
+				}),
+			},
 			Kind::Finished => Err(should_not_be_finished()),
 		}
 	}
@@ -157,6 +222,17 @@ impl<'r, 'c, 's, W: Write> SerializeSeqOrTupleOrTupleStruct<'r, 'c, 's, W> {
 					Ok(())
 				}
 			}
+			Kind::Decimal {
+				ref mut serializer_state,
+				ref decimal_mode,
+				ref unscaled,
+				scale,
+			} => match (unscaled, scale) {
+				(Some(unscaled), Some(scale)) => unscaled.with_be_bytes(|buf| {
+					super::decimal::serialize_unscaled(serializer_state, *decimal_mode, buf, scale)
+				}),
+				_ => Err(decimal_seq_len_incorrect()),
+			},
 			Kind::BufferedBytes {
 				ref mut serializer_state,
 				ref buffer,
@@ -170,6 +246,16 @@ impl<'r, 'c, 's, W: Write> SerializeSeqOrTupleOrTupleStruct<'r, 'c, 's, W> {
 					Ok(())
 				}
 			}
+			Kind::Record {
+				ref remaining_fields,
+				..
+			} => {
+				if remaining_fields.len() != 0 {
+					Err(record_seq_len_incorrect())
+				} else {
+					Ok(())
+				}
+			}
 			Kind::Finished => Err(should_not_be_finished()),
 		}
 	}
@@ -198,6 +284,20 @@ pub(super) fn duration_seq_len_incorrect() -> SerError {
 	SerError::new("seq/tuple can indeed be serialized as Duration, but only if it's of length 3")
 }
 
+fn decimal_seq_len_incorrect() -> SerError {
+	SerError::new(
+		"seq/tuple can indeed be serialized as Decimal, but only if it's of length 2 \
+			(unscaled, scale)",
+	)
+}
+
+pub(super) fn record_seq_len_incorrect() -> SerError {
+	SerError::new(
+		"seq/tuple can indeed be serialized as Record, but only if it has as many elements \
+			as the record has fields",
+	)
+}
+
 fn should_not_be_finished() -> SerError {
 	SerError::new(
 		"Internal serializer error: should not have state \
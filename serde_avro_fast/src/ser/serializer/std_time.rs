@@ -0,0 +1,170 @@
+//! Serialization of `std::time::Duration` and `std::time::SystemTime`, whose
+//! serde implementations serialize them as structs of seconds and
+//! nanoseconds, as avro `duration` and `timestamp-*` respectively
+
+use super::*;
+
+/// What the seconds and nanoseconds are serialized as
+#[derive(Clone, Copy)]
+pub(super) enum StdTimeTarget {
+	/// `std::time::Duration` (`secs`, `nanos`) as `duration`
+	Duration,
+	/// `std::time::SystemTime` (`secs_since_epoch`, `nanos_since_epoch`) as
+	/// `timestamp-*`, where this is the number of nanoseconds per unit
+	Timestamp { nanos_per_unit: u64 },
+}
+
+impl StdTimeTarget {
+	/// If a struct with this name and number of fields may be serialized to
+	/// this schema node as a `std::time` type, what to serialize it as
+	pub(super) fn new(schema_node: &SchemaNode<'_>, struct_name: &str, len: usize) -> Option<Self> {
+		match (schema_node, struct_name, len) {
+			(SchemaNode::Duration, "Duration", 2) => Some(Self::Duration),
+			(SchemaNode::TimestampMillis, "SystemTime", 2) => Some(Self::Timestamp {
+				nanos_per_unit: 1_000_000,
+			}),
+			(SchemaNode::TimestampMicros, "SystemTime", 2) => Some(Self::Timestamp {
+				nanos_per_unit: 1_000,
+			}),
+			_ => None,
+		}
+	}
+
+	fn field_names(self) -> [&'static str; 2] {
+		match self {
+			Self::Duration => ["secs", "nanos"],
+			Self::Timestamp { .. } => ["secs_since_epoch", "nanos_since_epoch"],
+		}
+	}
+}
+
+/// The seconds and nanoseconds gotten so far
+pub(super) struct StdTime {
+	target: StdTimeTarget,
+	secs: Option<u64>,
+	nanos: Option<u64>,
+}
+
+impl StdTime {
+	pub(super) fn new(target: StdTimeTarget) -> Self {
+		Self {
+			target,
+			secs: None,
+			nanos: None,
+		}
+	}
+
+	pub(super) fn serialize_field<T>(&mut self, key: &str, value: &T) -> Result<(), SerError>
+	where
+		T: Serialize + ?Sized,
+	{
+		let [secs_field, nanos_field] = self.target.field_names();
+		let slot = if key == secs_field {
+			&mut self.secs
+		} else if key == nanos_field {
+			&mut self.nanos
+		} else {
+			return Err(self.fields_incorrect());
+		};
+		if slot.is_some() {
+			return Err(SerError::custom(format_args!(
+				"{key} is getting serialized twice for serialization as {:?}",
+				self.target_name()
+			)));
+		}
+		*slot = Some(value.serialize(ExtractU64ForStdTime)?);
+		Ok(())
+	}
+
+	pub(super) fn end<W: Write>(
+		self,
+		state: &mut SerializerState<'_, '_, W>,
+	) -> Result<(), SerError> {
+		let (Some(secs), Some(nanos)) = (self.secs, self.nanos) else {
+			return Err(self.fields_incorrect());
+		};
+		let truncate = state.config.allow_sub_unit_precision_truncation;
+		match self.target {
+			StdTimeTarget::Duration => {
+				// Durations are always serialized with 0 months, as months don't
+				// have a fixed length
+				if !truncate && nanos % 1_000_000 != 0 {
+					return Err(SerError::new(
+						"Duration has sub-millisecond precision, which can't be represented \
+							as avro duration (see \
+							`SerializerConfig::allow_sub_unit_precision_truncation`)",
+					));
+				}
+				let days: u32 = (secs / 86_400).try_into().map_err(|_| {
+					SerError::new("Duration is too long to be serialized as avro duration")
+				})?;
+				let millis = u32::try_from((secs % 86_400) * 1_000 + nanos / 1_000_000)
+					.map_err(|_| SerError::new("Duration nanos should be less than a second"))?;
+				let mut values = [0u8; 12];
+				values[4..8].copy_from_slice(&days.to_le_bytes());
+				values[8..12].copy_from_slice(&millis.to_le_bytes());
+				state.writer.write_all(&values).map_err(SerError::io)
+			}
+			StdTimeTarget::Timestamp { nanos_per_unit } => {
+				if !truncate && nanos % nanos_per_unit != 0 {
+					return Err(SerError::custom(format_args!(
+						"SystemTime has sub-{} precision, which would be lost (see \
+							`SerializerConfig::allow_sub_unit_precision_truncation`)",
+						if nanos_per_unit == 1_000_000 {
+							"millisecond"
+						} else {
+							"microsecond"
+						}
+					)));
+				}
+				let timestamp = i128::from(secs) * i128::from(1_000_000_000 / nanos_per_unit)
+					+ i128::from(nanos / nanos_per_unit);
+				let timestamp: i64 =
+					timestamp.try_into().map_err(|_| {
+						SerError::new("SystemTime does not fit in an avro long for serialization as timestamp")
+					})?;
+				state.writer.write_varint(timestamp).map_err(SerError::io)?;
+				Ok(())
+			}
+		}
+	}
+
+	fn target_name(&self) -> &'static str {
+		match self.target {
+			StdTimeTarget::Duration => "Duration",
+			StdTimeTarget::Timestamp { .. } => "SystemTime",
+		}
+	}
+
+	fn fields_incorrect(&self) -> SerError {
+		let [secs_field, nanos_field] = self.target.field_names();
+		SerError::custom(format_args!(
+			"A struct named {:?} can be serialized as a std::time type, but only if its fields \
+				are {secs_field}/{nanos_field}",
+			self.target_name(),
+		))
+	}
+}
+
+struct ExtractU64ForStdTime;
+impl serde::Serializer for ExtractU64ForStdTime {
+	type Ok = u64;
+	type Error = SerError;
+
+	fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+		Ok(v.into())
+	}
+
+	fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+		Ok(v)
+	}
+
+	serde_serializer_quick_unsupported::serializer_unsupported! {
+		err = (SerError::new(
+			"Fields should be u64 seconds and u32 nanoseconds for serialization as std::time type"
+		));
+		bool i8 i16 i32 i64 u8 u16 f32 f64 char str bytes none some unit unit_struct
+		unit_variant newtype_struct newtype_variant seq tuple tuple_struct tuple_variant map struct
+		struct_variant i128 u128
+	}
+}
@@ -15,6 +15,18 @@ enum Kind<'r, 'c, 's, W> {
 		values: [u32; 3],
 		gotten_values: u8,
 	},
+	/// `std::time::Duration` or `std::time::SystemTime`
+	StdTime {
+		serializer_state: &'r mut SerializerState<'c, 's, W>,
+		std_time: std_time::StdTime,
+	},
+	/// The union variant is not known until the first field is serialized,
+	/// whose value should be the name of the record to pick (this is how
+	/// internally tagged enums are serialized)
+	UnionRecordFromTag {
+		serializer_state: Option<&'r mut SerializerState<'c, 's, W>>,
+		union: &'s Union<'s>,
+	},
 }
 
 struct KindRecord<'r, 'c, 's, W> {
@@ -106,6 +118,53 @@ impl<'r, 'c, 's, W: Write> SerializeStructAsRecordOrMapOrDuration<'r, 'c, 's, W>
 			},
 		})
 	}
+	pub(super) fn std_time(
+		state: &'r mut SerializerState<'c, 's, W>,
+		target: std_time::StdTimeTarget,
+	) -> Self {
+		Self {
+			kind: Kind::StdTime {
+				serializer_state: state,
+				std_time: std_time::StdTime::new(target),
+			},
+		}
+	}
+	pub(super) fn union_record_from_tag(
+		state: &'r mut SerializerState<'c, 's, W>,
+		union: &'s Union<'s>,
+	) -> Self {
+		Self {
+			kind: Kind::UnionRecordFromTag {
+				serializer_state: Some(state),
+				union,
+			},
+		}
+	}
+
+	/// If the union variant is not known yet, pick it from the value of the
+	/// first field (the tag), and switch to serializing that record
+	fn select_union_record_from_tag<T>(&mut self, value: &T) -> Result<(), SerError>
+	where
+		T: Serialize + ?Sized,
+	{
+		if let Kind::UnionRecordFromTag {
+			serializer_state,
+			union,
+		} = &mut self.kind
+		{
+			let (discriminant, record) =
+				value.serialize(FindUnionRecordByTagSerializer { union })?;
+			let serializer_state = serializer_state
+				.take()
+				.expect("UnionRecordFromTag should be replaced once the union variant is known");
+			serializer_state
+				.writer
+				.write_varint(discriminant)
+				.map_err(SerError::io)?;
+			*self = Self::record(serializer_state, record);
+		}
+		Ok(())
+	}
 
 	fn end(mut self) -> Result<(), SerError> {
 		match self.kind {
@@ -203,6 +262,18 @@ impl<'r, 'c, 's, W: Write> SerializeStructAsRecordOrMapOrDuration<'r, 'c, 's, W>
 						.map_err(SerError::io)?;
 				}
 			}
+			Kind::StdTime {
+				serializer_state,
+				std_time,
+			} => {
+				std_time.end(serializer_state)?;
+			}
+			Kind::UnionRecordFromTag { .. } => {
+				return Err(SerError::new(
+					"Could not serialize struct with no fields to union of several records: \
+						the union variant is picked from the value of the first field",
+				));
+			}
 		}
 		Ok(())
 	}
@@ -368,6 +439,7 @@ impl<'r, 'c, 's, W: Write> SerializeStruct
 	where
 		T: Serialize + ?Sized,
 	{
+		self.select_union_record_from_tag(value)?;
 		match &mut self.kind {
 			Kind::Record(KindRecord {
 				serializer_state,
@@ -404,6 +476,10 @@ impl<'r, 'c, 's, W: Write> SerializeStruct
 				let duration_field = extract_for_duration::DurationFieldName::from_str(key)?;
 				serialize_duration_field(values, gotten_values, duration_field, value)
 			}
+			Kind::StdTime { std_time, .. } => std_time.serialize_field(key, value),
+			Kind::UnionRecordFromTag { .. } => {
+				unreachable!("Union variant was selected above")
+			}
 		}
 	}
 
@@ -505,6 +581,11 @@ impl<'r, 'c, 's, W: Write> SerializeMap for SerializeMapAsRecordOrMapOrDuration<
 				);
 				Ok(())
 			}
+			Kind::StdTime { .. } | Kind::UnionRecordFromTag { .. } => {
+				unreachable!(
+					"Maps are never serialized as std::time types or with an unknown union variant"
+				)
+			}
 		}
 	}
 
@@ -546,6 +627,11 @@ impl<'r, 'c, 's, W: Write> SerializeMap for SerializeMapAsRecordOrMapOrDuration<
 				}
 				_ => panic!("serialize_key should have been called before serialize_value"),
 			},
+			Kind::StdTime { .. } | Kind::UnionRecordFromTag { .. } => {
+				unreachable!(
+					"Maps are never serialized as std::time types or with an unknown union variant"
+				)
+			}
 		}
 	}
 
@@ -592,6 +678,11 @@ impl<'r, 'c, 's, W: Write> SerializeMap for SerializeMapAsRecordOrMapOrDuration<
 					key.serialize(extract_for_duration::ExtractFieldNameForDuration)?;
 				serialize_duration_field(values, gotten_values, duration_field, value)
 			}
+			Kind::StdTime { .. } | Kind::UnionRecordFromTag { .. } => {
+				unreachable!(
+					"Maps are never serialized as std::time types or with an unknown union variant"
+				)
+			}
 		}
 	}
 
@@ -618,3 +709,32 @@ impl<'s> serde::Serializer for FindFieldIndexSerializer<'_, 's> {
 		struct_variant i128 u128
 	}
 }
+
+struct FindUnionRecordByTagSerializer<'s> {
+	union: &'s Union<'s>,
+}
+impl<'s> serde::Serializer for FindUnionRecordByTagSerializer<'s> {
+	type Ok = (i64, &'s Record<'s>);
+	type Error = SerError;
+
+	fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+		match self.union.per_type_lookup.named(v) {
+			Some((discriminant, SchemaNode::Record(record))) => Ok((discriminant, record)),
+			_ => Err(SerError::custom(format_args!(
+				"Could not find record named {v:?} in union to serialize struct to, \
+					while picking the union variant from the value of the first field"
+			))),
+		}
+	}
+
+	serde_serializer_quick_unsupported::serializer_unsupported! {
+		err = (SerError::new(
+			"Could not serialize struct to union of several records: \
+				could not pick the union variant from the value of the first field, \
+				which should be the name of the record"
+		));
+		bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char bytes none some unit unit_struct
+		unit_variant newtype_struct newtype_variant seq tuple tuple_struct tuple_variant map struct
+		struct_variant i128 u128
+	}
+}
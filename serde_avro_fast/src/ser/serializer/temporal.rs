@@ -0,0 +1,32 @@
+use super::*;
+
+use crate::temporal::{self, TimeUnit};
+
+/// Parse the string representation of a date, time of day or timestamp (as
+/// used by the serde implementations of `chrono`, `time` and `jiff`) into the
+/// integer that represents it for the given logical type
+///
+/// Sub-unit precision is an error unless `truncate` is set.
+pub(super) fn parse_str(
+	schema_node: &SchemaNode<'_>,
+	v: &str,
+	truncate: bool,
+) -> Result<i64, SerError> {
+	match *schema_node {
+		SchemaNode::Date => temporal::parse_date(v).map(i64::from),
+		SchemaNode::TimeMillis => temporal::parse_time(v, TimeUnit::Millis, truncate),
+		SchemaNode::TimeMicros => temporal::parse_time(v, TimeUnit::Micros, truncate),
+		SchemaNode::TimestampMillis => temporal::parse_timestamp(v, TimeUnit::Millis, truncate),
+		SchemaNode::TimestampMicros => temporal::parse_timestamp(v, TimeUnit::Micros, truncate),
+		_ => {
+			return Err(SerError::custom(format_args!(
+				"Could not serialize str to {schema_node:?}"
+			)))
+		}
+	}
+	.map_err(|e| {
+		SerError::custom(format_args!(
+			"Failed to parse {v:?} for serialization as {schema_node:?}: {e}"
+		))
+	})
+}
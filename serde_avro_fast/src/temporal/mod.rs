@@ -0,0 +1,106 @@
+//! Conversions between the avro temporal logical types (`date`, `time-*`,
+//! `timestamp-*`) and the string representations that the serde
+//! implementations of the `chrono`, `time` and `jiff` types use:
+//! - RFC3339 for timestamps (e.g. `2024-01-02T03:04:05.678Z`)
+//! - `YYYY-MM-DD` for dates
+//! - `HH:MM:SS[.fraction]` for times of day
+//!
+//! These representations are the same whichever of the crates is enabled, so
+//! only one of them is used to perform the conversions (the first enabled one
+//! of `jiff`, `chrono`, `time`).
+//!
+//! Backends provide the following functions, where timestamps are expressed in
+//! nanoseconds since the UNIX epoch, dates in days since the UNIX epoch, and
+//! times of day in nanoseconds since midnight:
+//! - `parse_timestamp_nanos(&str) -> Result<i128, String>`
+//! - `format_timestamp_nanos(i128) -> Result<String, String>`
+//! - `parse_date(&str) -> Result<i32, String>`
+//! - `format_date(i32) -> Result<String, String>`
+//! - `parse_time_nanos(&str) -> Result<i64, String>`
+//! - `format_time_nanos(i64) -> Result<String, String>`
+
+#[cfg(feature = "jiff")]
+mod with_jiff;
+#[cfg(feature = "jiff")]
+use with_jiff as backend;
+
+#[cfg(all(feature = "chrono", not(feature = "jiff")))]
+mod with_chrono;
+#[cfg(all(feature = "chrono", not(feature = "jiff")))]
+use with_chrono as backend;
+
+#[cfg(all(feature = "time", not(any(feature = "jiff", feature = "chrono"))))]
+mod with_time;
+#[cfg(all(feature = "time", not(any(feature = "jiff", feature = "chrono"))))]
+use with_time as backend;
+
+pub(crate) use backend::{format_date, parse_date};
+
+/// Unit of the integer representation of a timestamp or time of day
+#[derive(Clone, Copy)]
+pub(crate) enum TimeUnit {
+	Millis,
+	Micros,
+}
+
+impl TimeUnit {
+	const fn nanos(self) -> i64 {
+		match self {
+			Self::Millis => 1_000_000,
+			Self::Micros => 1_000,
+		}
+	}
+
+	fn precision_loss_error(self) -> String {
+		format!(
+			"Sub-{} precision would be lost (see \
+				`SerializerConfig::allow_sub_unit_precision_truncation`)",
+			match self {
+				Self::Millis => "millisecond",
+				Self::Micros => "microsecond",
+			}
+		)
+	}
+}
+
+const NANOS_PER_DAY: i64 = 86_400_000_000_000;
+
+/// Parse an RFC3339 timestamp into a number of `unit`s since the UNIX epoch
+///
+/// Errors if the timestamp has sub-unit precision, as it would be lost, unless
+/// `truncate` is set.
+pub(crate) fn parse_timestamp(s: &str, unit: TimeUnit, truncate: bool) -> Result<i64, String> {
+	let nanos = backend::parse_timestamp_nanos(s)?;
+	if !truncate && nanos % i128::from(unit.nanos()) != 0 {
+		return Err(unit.precision_loss_error());
+	}
+	nanos
+		.div_euclid(i128::from(unit.nanos()))
+		.try_into()
+		.map_err(|_| format!("Timestamp does not fit in an avro long: {s:?}"))
+}
+
+/// Format a number of `unit`s since the UNIX epoch as an RFC3339 timestamp
+pub(crate) fn format_timestamp(timestamp: i64, unit: TimeUnit) -> Result<String, String> {
+	backend::format_timestamp_nanos(i128::from(timestamp) * i128::from(unit.nanos()))
+}
+
+/// Parse a time of day into a number of `unit`s since midnight
+///
+/// Errors if the time of day has sub-unit precision, as it would be lost,
+/// unless `truncate` is set.
+pub(crate) fn parse_time(s: &str, unit: TimeUnit, truncate: bool) -> Result<i64, String> {
+	let nanos = backend::parse_time_nanos(s)?;
+	if !truncate && nanos % unit.nanos() != 0 {
+		return Err(unit.precision_loss_error());
+	}
+	Ok(nanos / unit.nanos())
+}
+
+/// Format a number of `unit`s since midnight as a time of day
+pub(crate) fn format_time(time_of_day: i64, unit: TimeUnit) -> Result<String, String> {
+	match time_of_day.checked_mul(unit.nanos()) {
+		Some(nanos) if (0..NANOS_PER_DAY).contains(&nanos) => backend::format_time_nanos(nanos),
+		_ => Err(format!("Time of day is out of range: {time_of_day}")),
+	}
+}
@@ -0,0 +1,50 @@
+use chrono::{DateTime, Datelike as _, NaiveDate, NaiveTime, SecondsFormat, Timelike as _};
+
+/// Number of days between `0001-01-01` (day 1 of the common era) and the UNIX
+/// epoch
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+pub(super) fn parse_timestamp_nanos(s: &str) -> Result<i128, String> {
+	let date_time = DateTime::parse_from_rfc3339(s).map_err(|e| e.to_string())?;
+	Ok(i128::from(date_time.timestamp()) * 1_000_000_000
+		+ i128::from(date_time.timestamp_subsec_nanos()))
+}
+
+pub(super) fn format_timestamp_nanos(nanos: i128) -> Result<String, String> {
+	let out_of_range = || format!("Timestamp is out of range: {nanos}ns");
+	let secs = i64::try_from(nanos.div_euclid(1_000_000_000)).map_err(|_| out_of_range())?;
+	#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+	let subsec_nanos = nanos.rem_euclid(1_000_000_000) as u32;
+	Ok(DateTime::from_timestamp(secs, subsec_nanos)
+		.ok_or_else(out_of_range)?
+		.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+}
+
+pub(crate) fn parse_date(s: &str) -> Result<i32, String> {
+	let date: NaiveDate = s.parse().map_err(|e: chrono::ParseError| e.to_string())?;
+	Ok(date.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE)
+}
+
+pub(crate) fn format_date(days_since_epoch: i32) -> Result<String, String> {
+	days_since_epoch
+		.checked_add(UNIX_EPOCH_DAYS_FROM_CE)
+		.and_then(NaiveDate::from_num_days_from_ce_opt)
+		.map(|date| date.to_string())
+		.ok_or_else(|| format!("Date is out of range: {days_since_epoch} days since epoch"))
+}
+
+pub(super) fn parse_time_nanos(s: &str) -> Result<i64, String> {
+	let time: NaiveTime = s.parse().map_err(|e: chrono::ParseError| e.to_string())?;
+	Ok(i64::from(time.num_seconds_from_midnight()) * 1_000_000_000 + i64::from(time.nanosecond()))
+}
+
+pub(super) fn format_time_nanos(nanos: i64) -> Result<String, String> {
+	let out_of_range = || format!("Time of day is out of range: {nanos}ns");
+	let secs = u32::try_from(nanos / 1_000_000_000).map_err(|_| out_of_range())?;
+	let subsec_nanos = u32::try_from(nanos % 1_000_000_000).map_err(|_| out_of_range())?;
+	Ok(
+		NaiveTime::from_num_seconds_from_midnight_opt(secs, subsec_nanos)
+			.ok_or_else(out_of_range)?
+			.to_string(),
+	)
+}
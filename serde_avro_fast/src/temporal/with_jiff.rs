@@ -0,0 +1,51 @@
+use jiff::{
+	civil::{Date, Time},
+	SignedDuration, Span, Timestamp,
+};
+
+const UNIX_EPOCH: Date = jiff::civil::date(1970, 1, 1);
+
+pub(super) fn parse_timestamp_nanos(s: &str) -> Result<i128, String> {
+	Ok(s.parse::<Timestamp>()
+		.map_err(|e| e.to_string())?
+		.as_nanosecond())
+}
+
+pub(super) fn format_timestamp_nanos(nanos: i128) -> Result<String, String> {
+	Ok(Timestamp::from_nanosecond(nanos)
+		.map_err(|e| e.to_string())?
+		.to_string())
+}
+
+pub(crate) fn parse_date(s: &str) -> Result<i32, String> {
+	let date: Date = s.parse().map_err(|e: jiff::Error| e.to_string())?;
+	Ok(UNIX_EPOCH
+		.until(date)
+		.map_err(|e| e.to_string())?
+		.get_days())
+}
+
+pub(crate) fn format_date(days_since_epoch: i32) -> Result<String, String> {
+	let span = Span::new()
+		.try_days(days_since_epoch)
+		.map_err(|e| e.to_string())?;
+	Ok(UNIX_EPOCH
+		.checked_add(span)
+		.map_err(|e| e.to_string())?
+		.to_string())
+}
+
+pub(super) fn parse_time_nanos(s: &str) -> Result<i64, String> {
+	let time: Time = s.parse().map_err(|e: jiff::Error| e.to_string())?;
+	time.duration_since(Time::midnight())
+		.as_nanos()
+		.try_into()
+		.map_err(|_| format!("Time of day is out of range: {s:?}"))
+}
+
+pub(super) fn format_time_nanos(nanos: i64) -> Result<String, String> {
+	Ok(Time::midnight()
+		.checked_add(SignedDuration::from_nanos(nanos))
+		.map_err(|e| e.to_string())?
+		.to_string())
+}
@@ -0,0 +1,66 @@
+use time::{
+	format_description::{well_known::Rfc3339, BorrowedFormatItem},
+	macros::format_description,
+	Date, OffsetDateTime, Time,
+};
+
+/// Julian day number of the UNIX epoch
+const UNIX_EPOCH_JULIAN_DAY: i32 = 2_440_588;
+
+const DATE_FORMAT: &[BorrowedFormatItem<'_>] = format_description!("[year]-[month]-[day]");
+const TIME_FORMAT_PARSE: &[BorrowedFormatItem<'_>] =
+	format_description!("[hour]:[minute]:[second][optional [.[subsecond]]]");
+const TIME_FORMAT: &[BorrowedFormatItem<'_>] =
+	format_description!("[hour]:[minute]:[second].[subsecond]");
+
+pub(super) fn parse_timestamp_nanos(s: &str) -> Result<i128, String> {
+	Ok(OffsetDateTime::parse(s, &Rfc3339)
+		.map_err(|e| e.to_string())?
+		.unix_timestamp_nanos())
+}
+
+pub(super) fn format_timestamp_nanos(nanos: i128) -> Result<String, String> {
+	OffsetDateTime::from_unix_timestamp_nanos(nanos)
+		.map_err(|e| e.to_string())?
+		.format(&Rfc3339)
+		.map_err(|e| e.to_string())
+}
+
+pub(crate) fn parse_date(s: &str) -> Result<i32, String> {
+	Ok(Date::parse(s, DATE_FORMAT)
+		.map_err(|e| e.to_string())?
+		.to_julian_day()
+		- UNIX_EPOCH_JULIAN_DAY)
+}
+
+pub(crate) fn format_date(days_since_epoch: i32) -> Result<String, String> {
+	days_since_epoch
+		.checked_add(UNIX_EPOCH_JULIAN_DAY)
+		.ok_or_else(|| format!("Date is out of range: {days_since_epoch} days since epoch"))
+		.and_then(|julian_day| Date::from_julian_day(julian_day).map_err(|e| e.to_string()))?
+		.format(DATE_FORMAT)
+		.map_err(|e| e.to_string())
+}
+
+pub(super) fn parse_time_nanos(s: &str) -> Result<i64, String> {
+	let time = Time::parse(s, TIME_FORMAT_PARSE).map_err(|e| e.to_string())?;
+	let (hour, minute, second, nanosecond) = time.as_hms_nano();
+	Ok(
+		((i64::from(hour) * 60 + i64::from(minute)) * 60 + i64::from(second)) * 1_000_000_000
+			+ i64::from(nanosecond),
+	)
+}
+
+pub(super) fn format_time_nanos(nanos: i64) -> Result<String, String> {
+	let out_of_range = |_| format!("Time of day is out of range: {nanos}ns");
+	let secs = nanos / 1_000_000_000;
+	Time::from_hms_nano(
+		u8::try_from(secs / 3600).map_err(out_of_range)?,
+		u8::try_from(secs / 60 % 60).map_err(out_of_range)?,
+		u8::try_from(secs % 60).map_err(out_of_range)?,
+		u32::try_from(nanos % 1_000_000_000).map_err(out_of_range)?,
+	)
+	.map_err(|e| e.to_string())?
+	.format(TIME_FORMAT)
+	.map_err(|e| e.to_string())
+}
@@ -0,0 +1,93 @@
+#![allow(missing_docs)]
+
+use serde_avro_fast::{de::DatumView, Schema};
+
+const SCHEMA: &str = r#"
+{
+	"type": "record",
+	"name": "Order",
+	"fields": [
+		{ "name": "items", "type": { "type": "array", "items": "int" } },
+		{ "name": "comment", "type": "string" },
+		{
+			"name": "customer",
+			"type": [
+				"null",
+				{
+					"type": "record",
+					"name": "Customer",
+					"fields": [
+						{ "name": "age", "type": "int" },
+						{ "name": "id", "type": "string" }
+					]
+				}
+			]
+		},
+		{ "name": "amount", "type": "long" }
+	]
+}
+"#;
+
+#[derive(serde_derive::Serialize)]
+struct Order<'a> {
+	items: Vec<i32>,
+	comment: &'a str,
+	customer: Option<Customer<'a>>,
+	amount: i64,
+}
+
+#[derive(serde_derive::Serialize)]
+struct Customer<'a> {
+	age: i32,
+	id: &'a str,
+}
+
+#[test]
+fn datum_view() {
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let serialized = serde_avro_fast::to_datum_vec(
+		&Order {
+			items: vec![1, 2, 3],
+			comment: "foo",
+			customer: Some(Customer { age: 42, id: "bar" }),
+			amount: -12,
+		},
+		&mut serde_avro_fast::ser::SerializerConfig::new(&schema),
+	)
+	.unwrap();
+
+	let view = DatumView::new(&serialized, &schema).unwrap();
+	// Out of order accesses use the cached offsets
+	assert_eq!(view.field("amount").unwrap().as_i64().unwrap(), -12);
+	assert_eq!(view.field("comment").unwrap().as_str().unwrap(), "foo");
+	let customer = view.field("customer").unwrap();
+	assert!(!customer.is_null());
+	assert_eq!(customer.field("id").unwrap().as_str().unwrap(), "bar");
+	assert_eq!(customer.field("age").unwrap().as_i32().unwrap(), 42);
+	assert_eq!(
+		view.field_by_position(0)
+			.unwrap()
+			.deserialize::<Vec<i32>>()
+			.unwrap(),
+		[1, 2, 3]
+	);
+
+	assert!(view.field("unknown").is_err());
+	assert!(view.field_by_position(4).is_err());
+	assert!(view.field("comment").unwrap().field("id").is_err());
+	assert!(view.field("comment").unwrap().as_i64().is_err());
+
+	let serialized = serde_avro_fast::to_datum_vec(
+		&Order {
+			items: vec![],
+			comment: "",
+			customer: None,
+			amount: 3,
+		},
+		&mut serde_avro_fast::ser::SerializerConfig::new(&schema),
+	)
+	.unwrap();
+	let view = DatumView::new(&serialized, &schema).unwrap();
+	assert!(view.field("customer").unwrap().is_null());
+	assert_eq!(view.field("amount").unwrap().as_i64().unwrap(), 3);
+}
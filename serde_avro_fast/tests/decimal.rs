@@ -0,0 +1,241 @@
+#![allow(missing_docs)]
+
+use serde_avro_fast::{ser::SerializerConfig, Schema};
+
+const PRECISION_38: &str = "1234567890123456789012345678.9012345678";
+const PRECISION_38_UNSCALED: i128 = 12345678901234567890123456789012345678;
+
+#[test]
+fn precision_38() {
+	let schema: Schema = r#"{
+		"type": "fixed",
+		"name": "decimal_38_10",
+		"size": 16,
+		"logicalType": "decimal",
+		"precision": 38,
+		"scale": 10
+	}"#
+	.parse()
+	.unwrap();
+	let serializer_config = &mut SerializerConfig::new(&schema);
+
+	let serialized = serde_avro_fast::to_datum_vec(&PRECISION_38, serializer_config).unwrap();
+	assert_eq!(serialized, PRECISION_38_UNSCALED.to_be_bytes());
+	let negative = format!("-{PRECISION_38}");
+	let serialized_negative = serde_avro_fast::to_datum_vec(&negative, serializer_config).unwrap();
+	assert_eq!(serialized_negative, (-PRECISION_38_UNSCALED).to_be_bytes());
+
+	// Make sure that the above is consistent with apache-avro's impl.
+	let apache_schema = apache_avro::Schema::parse_str(
+		r#"{"type": "fixed", "name": "decimal_38_10", "size": 16, "logicalType": "decimal", "precision": 38, "scale": 10}"#,
+	)
+	.unwrap();
+	assert_eq!(
+		apache_avro::from_avro_datum(&apache_schema, &mut serialized.as_slice(), None).unwrap(),
+		apache_avro::types::Value::Decimal(apache_avro::Decimal::from(
+			PRECISION_38_UNSCALED.to_be_bytes()
+		)),
+	);
+
+	let deserialized: String = serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	assert_eq!(deserialized, PRECISION_38);
+	let deserialized: String =
+		serde_avro_fast::from_datum_slice(&serialized_negative, &schema).unwrap();
+	assert_eq!(deserialized, negative);
+
+	let deserialized: bigdecimal::BigDecimal =
+		serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	assert_eq!(
+		deserialized,
+		PRECISION_38.parse::<bigdecimal::BigDecimal>().unwrap()
+	);
+	assert_eq!(
+		serde_avro_fast::to_datum_vec(&deserialized, serializer_config).unwrap(),
+		serialized
+	);
+
+	// Never rounded
+	assert!(serde_avro_fast::to_datum_vec(
+		&"1234567890123456789012345678.90123456789",
+		serializer_config
+	)
+	.is_err());
+	// But extra zeroes are fine
+	assert_eq!(
+		serde_avro_fast::to_datum_vec(
+			&"1234567890123456789012345678.901234567800000",
+			serializer_config
+		)
+		.unwrap(),
+		serialized
+	);
+	assert_eq!(
+		serde_avro_fast::to_datum_vec(
+			&"1.2345678901234567890123456789012345678E+27",
+			serializer_config
+		)
+		.unwrap(),
+		serialized
+	);
+}
+
+#[test]
+fn integers_128_in_fixed_17() {
+	let schema: Schema = r#"{
+		"type": "fixed",
+		"name": "decimal_39",
+		"size": 17,
+		"logicalType": "decimal",
+		"precision": 39,
+		"scale": 0
+	}"#
+	.parse()
+	.unwrap();
+	let serializer_config = &mut SerializerConfig::new(&schema);
+
+	for n in [i128::MIN, -1, 0, i128::MAX] {
+		let serialized = serde_avro_fast::to_datum_vec(&n, serializer_config).unwrap();
+		let sign_byte = if n < 0 { 0xFF } else { 0 };
+		assert_eq!(
+			serialized,
+			[[sign_byte].as_slice(), &n.to_be_bytes()].concat()
+		);
+		let deserialized: i128 = serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+		assert_eq!(deserialized, n);
+	}
+	for n in [0, i128::MAX as u128 + 1, u128::MAX] {
+		let serialized = serde_avro_fast::to_datum_vec(&n, serializer_config).unwrap();
+		assert_eq!(serialized, [[0].as_slice(), &n.to_be_bytes()].concat());
+		let deserialized: u128 = serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+		assert_eq!(deserialized, n);
+		let (unscaled, scale): (u128, u32) =
+			serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+		assert_eq!((unscaled, scale), (n, 0));
+	}
+}
+
+#[test]
+fn raw_representation() {
+	let schema: Schema =
+		r#"{"type": "bytes", "logicalType": "decimal", "precision": 38, "scale": 10}"#
+			.parse()
+			.unwrap();
+	let serializer_config = &mut SerializerConfig::new(&schema);
+
+	let serialized =
+		serde_avro_fast::to_datum_vec(&(PRECISION_38_UNSCALED, 10), serializer_config).unwrap();
+	assert_eq!(
+		serialized,
+		serde_avro_fast::to_datum_vec(&PRECISION_38, serializer_config).unwrap()
+	);
+
+	let (unscaled, scale): (i128, u32) =
+		serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	assert_eq!((unscaled, scale), (PRECISION_38_UNSCALED, 10));
+
+	let (unscaled, scale): (serde_bytes::ByteBuf, u32) =
+		serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	assert_eq!(
+		(unscaled.as_slice(), scale),
+		(PRECISION_38_UNSCALED.to_be_bytes().as_slice(), 10)
+	);
+	assert_eq!(
+		serde_avro_fast::to_datum_vec(&(unscaled, scale), serializer_config).unwrap(),
+		serialized
+	);
+
+	// Scale has to match for regular decimals
+	assert!(serde_avro_fast::to_datum_vec(&(PRECISION_38_UNSCALED, 9), serializer_config).is_err());
+}
+
+#[test]
+fn raw_representation_u128() {
+	let schema: Schema =
+		r#"{"type": "bytes", "logicalType": "decimal", "precision": 39, "scale": 0}"#
+			.parse()
+			.unwrap();
+
+	// Unscaled values that don't fit in an i128 can still be read as u128
+	let serialized = [[34, 0].as_slice(), &u128::MAX.to_be_bytes()].concat();
+	let (unscaled, scale): (u128, u32) =
+		serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	assert_eq!((unscaled, scale), (u128::MAX, 0));
+}
+
+#[cfg(any(feature = "num-bigint", feature = "bigdecimal"))]
+#[test]
+fn arbitrary_precision() {
+	let schema: Schema = r#"{"type": "bytes", "logicalType": "big-decimal"}"#
+		.parse()
+		.unwrap();
+	let serializer_config = &mut SerializerConfig::new(&schema);
+
+	let value = "-1234567890123456789012345678901234567890.12345678901234567890123456789";
+	let serialized = serde_avro_fast::to_datum_vec(&value, serializer_config).unwrap();
+
+	// Make sure that the above is consistent with apache-avro's impl.
+	assert_eq!(
+		apache_avro::to_avro_datum(
+			&apache_avro::Schema::BigDecimal,
+			apache_avro::types::Value::BigDecimal(value.parse().unwrap())
+		)
+		.unwrap(),
+		serialized
+	);
+
+	let deserialized: String = serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	assert_eq!(deserialized, value);
+	let deserialized: bigdecimal::BigDecimal =
+		serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	assert_eq!(
+		deserialized,
+		value.parse::<bigdecimal::BigDecimal>().unwrap()
+	);
+	assert_eq!(
+		serde_avro_fast::to_datum_vec(&deserialized, serializer_config).unwrap(),
+		serialized
+	);
+
+	let (unscaled, scale): (serde_bytes::ByteBuf, u32) =
+		serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	assert_eq!(scale, 29);
+	assert_eq!(
+		serde_avro_fast::to_datum_vec(&(unscaled, scale), serializer_config).unwrap(),
+		serialized
+	);
+}
+
+#[test]
+fn no_rounding() {
+	let schema: Schema =
+		r#"{"type": "bytes", "logicalType": "decimal", "precision": 10, "scale": 2}"#
+			.parse()
+			.unwrap();
+	let serializer_config = &mut SerializerConfig::new(&schema);
+
+	// Would be rounded to 1.23 if rescaled
+	assert!(serde_avro_fast::to_datum_vec(&"1.2345", serializer_config).is_err());
+	assert!(serde_avro_fast::to_datum_vec(
+		&"1.2345".parse::<rust_decimal::Decimal>().unwrap(),
+		serializer_config
+	)
+	.is_err());
+
+	// Floats are approximations, so they are rounded
+	assert_eq!(
+		serde_avro_fast::to_datum_vec(&1.2345, serializer_config).unwrap(),
+		[2, 123]
+	);
+
+	// But extra zeroes are fine
+	let serialized = serde_avro_fast::to_datum_vec(&"1.2300", serializer_config).unwrap();
+	assert_eq!(serialized, [2, 123]);
+	assert_eq!(
+		serde_avro_fast::to_datum_vec(&"1.23", serializer_config).unwrap(),
+		serialized
+	);
+	assert_eq!(
+		serde_avro_fast::to_datum_vec(&"1.2", serializer_config).unwrap(),
+		[2, 120]
+	);
+}
@@ -7,7 +7,9 @@
 use {
 	serde_avro_fast::{
 		from_datum_reader, from_datum_slice,
-		object_container_file_encoding::{Compression, CompressionLevel, Reader, WriterBuilder},
+		object_container_file_encoding::{
+			self, CodecRegistry, Compression, CompressionLevel, ConcatError, Reader, WriterBuilder,
+		},
 		ser::SerializerConfig,
 		Schema,
 	},
@@ -57,6 +59,21 @@ struct SchemaRecord<'a> {
 	b: Cow<'a, str>,
 }
 
+/// For APIs that require `DeserializeOwned`
+#[derive(Deserialize)]
+struct OwnedSchemaRecord {
+	a: i64,
+	b: String,
+}
+impl OwnedSchemaRecord {
+	fn into_schema_record(self) -> SchemaRecord<'static> {
+		SchemaRecord {
+			a: self.a,
+			b: self.b.into(),
+		}
+	}
+}
+
 #[test]
 fn test_from_avro_datum() {
 	let schema: Schema = SCHEMA.parse().unwrap();
@@ -185,7 +202,7 @@ fn round_trip_writer(compression_codec: Compression, approx_block_size: u32) {
 
 	let mut serializer_config = SerializerConfig::new(&schema);
 	let mut writer = WriterBuilder::new(&mut serializer_config)
-		.compression(compression_codec)
+		.compression(compression_codec.clone())
 		.approx_block_size(approx_block_size)
 		.build(Vec::new())
 		.unwrap();
@@ -203,6 +220,32 @@ fn round_trip_writer(compression_codec: Compression, approx_block_size: u32) {
 		Compression::Null => assert!(res.iter().all(|r| matches!(r.b, Cow::Borrowed(_)))),
 		_ => assert!(res.iter().all(|r| matches!(r.b, Cow::Owned(_)))),
 	}
+
+	#[cfg(feature = "tokio")]
+	assert_eq!(
+		input.as_slice(),
+		async_read_all(&serialized).unwrap().as_slice()
+	);
+}
+
+#[cfg(feature = "tokio")]
+fn async_read_all(
+	serialized: &[u8],
+) -> Result<Vec<SchemaRecord<'static>>, Box<dyn std::error::Error>> {
+	use futures_util::TryStreamExt;
+	tokio::runtime::Builder::new_current_thread()
+		.build()
+		.unwrap()
+		.block_on(async {
+			let mut reader =
+				serde_avro_fast::object_container_file_encoding::AsyncReader::new(serialized)
+					.await?;
+			Ok(reader
+				.deserialize()
+				.map_ok(OwnedSchemaRecord::into_schema_record)
+				.try_collect()
+				.await?)
+		})
 }
 
 #[test]
@@ -279,17 +322,1067 @@ fn test_writer_zstandard() {
 	round_trip_writer(
 		Compression::Zstandard {
 			level: CompressionLevel::default(),
+			dictionary: None,
 		},
 		64 * 1024,
 	);
 	round_trip_writer(
 		Compression::Zstandard {
 			level: CompressionLevel::default(),
+			dictionary: None,
 		},
 		1,
 	);
 }
 
+#[cfg(feature = "zstandard")]
+#[test]
+fn test_writer_zstandard_dictionary() {
+	use object_container_file_encoding::{ZstandardDictionary, ZSTANDARD_DICTIONARY_METADATA_KEY};
+
+	let dictionary = ZstandardDictionary::new(b"\x36foo\x54bar".repeat(32));
+	let compression = Compression::Zstandard {
+		level: CompressionLevel::default(),
+		dictionary: Some(dictionary.clone()),
+	};
+	round_trip_writer(compression.clone(), 64 * 1024);
+	round_trip_writer(compression.clone(), 1);
+
+	// The dictionary is stored in the header, and is used when appending even if
+	// it's not specified on the builder
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let record = |a: i64| SchemaRecord { a, b: "foo".into() };
+	let mut file = std::io::Cursor::new(
+		object_container_file_encoding::write_all(
+			&schema,
+			compression.clone(),
+			Vec::new(),
+			[record(1)],
+		)
+		.unwrap(),
+	);
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.compression(Compression::Zstandard {
+			level: CompressionLevel::new(3),
+			dictionary: None,
+		})
+		.build_append(&mut file)
+		.unwrap();
+	writer.serialize(record(2)).unwrap();
+	writer.into_inner().unwrap();
+
+	let mut reader = Reader::from_slice(file.get_ref()).unwrap();
+	assert_eq!(
+		reader.metadata().get(ZSTANDARD_DICTIONARY_METADATA_KEY),
+		Some(dictionary.as_bytes())
+	);
+	assert_eq!(reader.compression(), &compression);
+	let res: Vec<SchemaRecord<'_>> = reader
+		.deserialize_borrowed()
+		.collect::<Result<_, _>>()
+		.unwrap();
+	assert_eq!(res, [record(1), record(2)]);
+
+	// Blocks can't be written to a file that doesn't use the same dictionary
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.compression(Compression::Zstandard {
+			level: CompressionLevel::default(),
+			dictionary: None,
+		})
+		.build(Vec::new())
+		.unwrap();
+	let raw_block = Reader::from_slice(file.get_ref())
+		.unwrap()
+		.next_raw_block()
+		.unwrap()
+		.unwrap();
+	assert!(writer.push_raw_block(&raw_block).is_err());
+}
+
+#[cfg(feature = "lz4")]
+#[test]
+fn test_writer_lz4() {
+	round_trip_writer(Compression::Lz4, 64 * 1024);
+	round_trip_writer(Compression::Lz4, 1);
+}
+
+#[test]
+fn test_custom_codec() {
+	use object_container_file_encoding::{Codec, CustomCodec};
+
+	/// XORs every byte, and prefixes the block with a marker
+	struct Xor;
+	impl Codec for Xor {
+		fn name(&self) -> &'static str {
+			"xor"
+		}
+		fn compress(&self, uncompressed: &[u8], compressed: &mut Vec<u8>) -> std::io::Result<()> {
+			compressed.push(b'X');
+			compressed.extend(uncompressed.iter().map(|b| b ^ 0x5A));
+			Ok(())
+		}
+		fn decompress(&self, compressed: &[u8], decompressed: &mut Vec<u8>) -> std::io::Result<()> {
+			match compressed.split_first() {
+				Some((b'X', rest)) => {
+					decompressed.extend(rest.iter().map(|b| b ^ 0x5A));
+					Ok(())
+				}
+				_ => Err(std::io::Error::other("missing xor marker")),
+			}
+		}
+	}
+
+	let compression = Compression::Custom(CustomCodec::new(Xor));
+	let codecs = CodecRegistry::new().register(Xor);
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let record = |a: i64| SchemaRecord { a, b: "foo".into() };
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.compression(compression.clone())
+		.approx_block_size(1)
+		.build(Vec::new())
+		.unwrap();
+	writer.serialize_all([record(1), record(2)]).unwrap();
+	let mut file = std::io::Cursor::new(writer.into_inner().unwrap());
+
+	// Unknown codecs still fail if they are not registered
+	assert!(Reader::from_slice(file.get_ref())
+		.err()
+		.unwrap()
+		.to_string()
+		.contains("unknown variant `xor`"));
+
+	#[cfg(feature = "tokio")]
+	{
+		use futures_util::TryStreamExt;
+		let res: Vec<SchemaRecord<'_>> = tokio::runtime::Builder::new_current_thread()
+			.build()
+			.unwrap()
+			.block_on(async {
+				object_container_file_encoding::AsyncReader::new_with_codecs(
+					file.get_ref().as_slice(),
+					&codecs,
+				)
+				.await
+				.unwrap()
+				.deserialize()
+				.map_ok(OwnedSchemaRecord::into_schema_record)
+				.try_collect()
+				.await
+			})
+			.unwrap();
+		assert_eq!(res, [record(1), record(2)]);
+	}
+
+	// Appending resolves the codec of the builder
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.compression(compression.clone())
+		.build_append(&mut file)
+		.unwrap();
+	writer.serialize(record(3)).unwrap();
+	writer.into_inner().unwrap();
+
+	let mut reader = Reader::new_with_codecs(
+		serde_avro_fast::de::read::SliceRead::new(file.get_ref()),
+		&codecs,
+	)
+	.unwrap();
+	assert_eq!(reader.compression_codec(), compression.codec());
+	assert_eq!(reader.compression_codec().name(), "xor");
+	assert_eq!(reader.compression(), &compression);
+	let res: Vec<SchemaRecord<'_>> = reader
+		.deserialize_borrowed()
+		.collect::<Result<_, _>>()
+		.unwrap();
+	assert_eq!(res, [record(1), record(2), record(3)]);
+}
+
+#[test]
+fn test_custom_codec_reserved_names() {
+	use object_container_file_encoding::{Codec, CustomCodec};
+
+	/// Does not compress, under any name
+	struct Named(&'static str);
+	impl Codec for Named {
+		fn name(&self) -> &'static str {
+			self.0
+		}
+		fn compress(&self, uncompressed: &[u8], compressed: &mut Vec<u8>) -> std::io::Result<()> {
+			compressed.extend_from_slice(uncompressed);
+			Ok(())
+		}
+		fn decompress(&self, compressed: &[u8], decompressed: &mut Vec<u8>) -> std::io::Result<()> {
+			decompressed.extend_from_slice(compressed);
+			Ok(())
+		}
+	}
+
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let build = |name: &'static str| {
+		let mut serializer_config = SerializerConfig::new(&schema);
+		WriterBuilder::new(&mut serializer_config)
+			.compression(Compression::Custom(CustomCodec::new(Named(name))))
+			.build(Vec::new())
+			.map(|_| ())
+	};
+	// Reserved whether or not the corresponding feature is enabled
+	for name in [
+		"null",
+		"deflate",
+		"snappy",
+		"zstandard",
+		"lz4",
+		"avro.custom",
+	] {
+		assert!(build(name).unwrap_err().to_string().contains("is reserved"));
+	}
+	build("custom").unwrap();
+}
+
+#[test]
+fn test_raw_blocks() {
+	let input = &[
+		SchemaRecord {
+			a: 27,
+			b: "foo".into(),
+		},
+		SchemaRecord {
+			a: 42,
+			b: "bar".into(),
+		},
+	];
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.approx_block_size(1)
+		.build(Vec::new())
+		.unwrap();
+	writer.serialize_all(input.iter()).unwrap();
+	let serialized = writer.into_inner().unwrap();
+
+	let mut reader = Reader::from_slice(&serialized).unwrap();
+	let mut raw_blocks = Vec::new();
+	while let Some(raw_block) = reader.next_raw_block().unwrap() {
+		assert_eq!(raw_block.n_objects, 1);
+		assert!(matches!(raw_block.compressed_bytes, Cow::Borrowed(_)));
+		raw_blocks.push(raw_block);
+	}
+	assert_eq!(raw_blocks.len(), 2);
+	// The reader keeps returning `None` once it reached the end of the file
+	assert!(reader.next_raw_block().unwrap().is_none());
+	assert!(reader
+		.deserialize_next_borrowed::<SchemaRecord<'_>>()
+		.unwrap()
+		.is_none());
+
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.build(Vec::new())
+		.unwrap();
+	writer.serialize(&input[0]).unwrap();
+	for raw_block in &raw_blocks {
+		writer.push_raw_block(raw_block).unwrap();
+	}
+	// Raw blocks are accounted separately as their uncompressed size is unknown
+	let stats = writer.stats();
+	assert_eq!(stats.n_records, 3);
+	assert_eq!(stats.n_blocks, 3);
+	assert_eq!(stats.uncompressed_bytes, 5);
+	assert_eq!(stats.compressed_bytes, 5);
+	assert_eq!(
+		stats.raw_bytes,
+		raw_blocks
+			.iter()
+			.map(|raw_block| raw_block.compressed_bytes.len() as u64)
+			.sum::<u64>()
+	);
+	let concatenated = writer.into_inner().unwrap();
+	let res: Vec<SchemaRecord<'_>> = Reader::from_slice(&concatenated)
+		.unwrap()
+		.deserialize_borrowed()
+		.collect::<Result<_, _>>()
+		.unwrap();
+	assert_eq!(res[..1], input[..1]);
+	assert_eq!(res[1..], input[..]);
+
+	#[cfg(feature = "deflate")]
+	{
+		let mut writer = WriterBuilder::new(&mut serializer_config)
+			.compression(Compression::Deflate {
+				level: CompressionLevel::default(),
+			})
+			.build(Vec::new())
+			.unwrap();
+		assert!(writer.push_raw_block(&raw_blocks[0]).is_err());
+	}
+
+	// Can't read raw blocks in the middle of a block
+	let mut reader = Reader::from_slice(ENCODED).unwrap();
+	let _: SchemaRecord<'_> = reader.deserialize_next_borrowed().unwrap().unwrap();
+	assert!(reader.next_raw_block().is_err());
+}
+
+#[test]
+fn test_concat() {
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let record = |a: i64| SchemaRecord { a, b: "foo".into() };
+	let null_compressed = object_container_file_encoding::write_all(
+		&schema,
+		Compression::Null,
+		Vec::new(),
+		[record(1), record(2)],
+	)
+	.unwrap();
+	let concatenated = object_container_file_encoding::concat(
+		[
+			Reader::from_slice(&null_compressed).unwrap(),
+			Reader::from_slice(ENCODED).unwrap(),
+		],
+		Vec::new(),
+	)
+	.unwrap();
+	let res: Vec<SchemaRecord<'_>> = Reader::from_slice(&concatenated)
+		.unwrap()
+		.deserialize_borrowed()
+		.collect::<Result<_, _>>()
+		.unwrap();
+	assert_eq!(
+		res,
+		[
+			record(1),
+			record(2),
+			record(27),
+			SchemaRecord {
+				a: 42,
+				b: "bar".into(),
+			},
+		]
+	);
+
+	// Different codecs require recompressing
+	#[cfg(feature = "deflate")]
+	{
+		let deflate_compressed = object_container_file_encoding::write_all(
+			&schema,
+			Compression::Deflate {
+				level: CompressionLevel::default(),
+			},
+			Vec::new(),
+			[record(3)],
+		)
+		.unwrap();
+		let concatenated = object_container_file_encoding::concat(
+			[
+				Reader::from_slice(&deflate_compressed).unwrap(),
+				Reader::from_slice(&null_compressed).unwrap(),
+			],
+			Vec::new(),
+		)
+		.unwrap();
+		let mut reader = Reader::from_slice(&concatenated).unwrap();
+		assert_eq!(
+			reader.compression_codec(),
+			Compression::Deflate {
+				level: CompressionLevel::default(),
+			}
+			.codec()
+		);
+		let res: Vec<SchemaRecord<'_>> = reader
+			.deserialize_borrowed()
+			.collect::<Result<_, _>>()
+			.unwrap();
+		assert_eq!(res, [record(3), record(1), record(2)]);
+	}
+
+	let other_schema: Schema = UNION_SCHEMA.parse().unwrap();
+	let other_schema_file = object_container_file_encoding::write_all(
+		&other_schema,
+		Compression::Null,
+		Vec::new(),
+		[Some(1i64)],
+	)
+	.unwrap();
+	assert!(matches!(
+		object_container_file_encoding::concat(
+			[
+				Reader::from_slice(&null_compressed).unwrap(),
+				Reader::from_slice(&other_schema_file).unwrap(),
+			],
+			Vec::new(),
+		),
+		Err(ConcatError::IncompatibleSchema { input_index: 1 })
+	));
+	assert!(matches!(
+		object_container_file_encoding::concat(
+			Vec::<Reader<serde_avro_fast::de::read::SliceRead<'_>>>::new(),
+			Vec::new(),
+		),
+		Err(ConcatError::NoInputs)
+	));
+
+	// User metadata of the first input is carried over
+	let with_user_metadata = |created_by: &str| {
+		let mut serializer_config = SerializerConfig::new(&schema);
+		let mut writer = WriterBuilder::new(&mut serializer_config)
+			.build_with_user_metadata(
+				Vec::new(),
+				std::collections::HashMap::from([("created_by", created_by)]),
+			)
+			.unwrap();
+		writer.serialize(record(4)).unwrap();
+		writer.into_inner().unwrap()
+	};
+	let (first, second) = (with_user_metadata("first"), with_user_metadata("second"));
+	let concatenated = object_container_file_encoding::concat(
+		[
+			Reader::from_slice(&first).unwrap(),
+			Reader::from_slice(&second).unwrap(),
+		],
+		Vec::new(),
+	)
+	.unwrap();
+	let reader = Reader::from_slice(&concatenated).unwrap();
+	assert_eq!(
+		reader.metadata().user_entries().collect::<Vec<_>>(),
+		[("created_by", b"first".as_slice())]
+	);
+}
+
+#[cfg(feature = "deflate")]
+#[test]
+fn test_max_decompressed_block_size() {
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let long_string = "a".repeat(100_000);
+	let record = SchemaRecord {
+		a: 1,
+		b: long_string.as_str().into(),
+	};
+	let deflate_compressed = object_container_file_encoding::write_all(
+		&schema,
+		Compression::Deflate {
+			level: CompressionLevel::default(),
+		},
+		Vec::new(),
+		[&record],
+	)
+	.unwrap();
+	let null_compressed = object_container_file_encoding::write_all(
+		&schema,
+		Compression::Null,
+		Vec::new(),
+		[&record],
+	)
+	.unwrap();
+	assert!(deflate_compressed.len() < 10_000);
+
+	// Blocks that are decompressed whole are checked against the limit
+	let concat = |max_decompressed_block_size| {
+		let mut deflate_reader = Reader::from_slice(&deflate_compressed).unwrap();
+		deflate_reader.set_max_decompressed_block_size(max_decompressed_block_size);
+		object_container_file_encoding::concat(
+			[
+				Reader::from_slice(&null_compressed).unwrap(),
+				deflate_reader,
+			],
+			Vec::new(),
+		)
+	};
+	concat(200_000).unwrap();
+	match concat(50_000) {
+		Err(ConcatError::Read {
+			input_index: 1,
+			de_error,
+		}) => assert!(de_error.to_string().contains("larger than allowed")),
+		other => panic!("Unexpected result: {other:?}"),
+	}
+
+	#[cfg(feature = "tokio")]
+	{
+		let res: Result<Option<OwnedSchemaRecord>, _> =
+			tokio::runtime::Builder::new_current_thread()
+				.build()
+				.unwrap()
+				.block_on(async {
+					let mut reader = object_container_file_encoding::AsyncReader::new(
+						deflate_compressed.as_slice(),
+					)
+					.await
+					.unwrap();
+					reader.set_max_decompressed_block_size(50_000);
+					reader.deserialize_next().await
+				});
+		assert!(res
+			.err()
+			.unwrap()
+			.to_string()
+			.contains("larger than allowed"));
+	}
+}
+
+#[test]
+fn test_recompress() {
+	#[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
+	struct UserMetadata {
+		#[serde(with = "serde_bytes")]
+		custom: Vec<u8>,
+	}
+	let user_metadata = UserMetadata {
+		custom: b"value".to_vec(),
+	};
+
+	// Attributes that don't affect the parsed schema are preserved
+	let schema_json = r#"{"type":"record","name":"test","doc":"Some doc","fields":[{"name":"a","type":"long","default":42},{"name":"b","type":"string"}]}"#;
+	let schema: Schema = schema_json.parse().unwrap();
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.approx_block_size(1)
+		.build_with_user_metadata(Vec::new(), &user_metadata)
+		.unwrap();
+	writer
+		.serialize_all((0..3).map(|a| SchemaRecord { a, b: "foo".into() }))
+		.unwrap();
+	let original = writer.into_inner().unwrap();
+
+	let recompressed = object_container_file_encoding::recompress(
+		original.as_slice(),
+		Compression::Null,
+		Vec::new(),
+	)
+	.unwrap();
+	let (mut reader, recompressed_user_metadata) = Reader::new_and_metadata::<UserMetadata>(
+		serde_avro_fast::de::read::SliceRead::new(&recompressed),
+	)
+	.unwrap();
+	assert_eq!(recompressed_user_metadata, user_metadata);
+	assert_eq!(reader.schema().json(), schema_json);
+	assert_eq!(reader.compression_codec(), Compression::Null.codec());
+	let res: Vec<SchemaRecord<'_>> = reader
+		.deserialize_borrowed()
+		.collect::<Result<_, _>>()
+		.unwrap();
+	assert_eq!(
+		res,
+		(0..3)
+			.map(|a| SchemaRecord { a, b: "foo".into() })
+			.collect::<Vec<_>>()
+	);
+}
+
+#[test]
+fn test_append() {
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let record = |a: i64| SchemaRecord { a, b: "foo".into() };
+	let append =
+		|serializer_config: &mut SerializerConfig<'_>, file, records: &[SchemaRecord<'_>]| {
+			let mut writer = WriterBuilder::new(serializer_config)
+				.compression(Compression::Null)
+				.build_append(file)
+				.unwrap();
+			writer.serialize_all(records).unwrap();
+			writer.into_inner().unwrap()
+		};
+
+	// Empty file gets a header
+	let file = append(
+		&mut serializer_config,
+		std::io::Cursor::new(Vec::new()),
+		&[record(1), record(2)],
+	);
+	let file = append(&mut serializer_config, file, &[record(3)]);
+	let res: Vec<SchemaRecord<'_>> = Reader::from_slice(file.get_ref())
+		.unwrap()
+		.deserialize_borrowed()
+		.collect::<Result<_, _>>()
+		.unwrap();
+	assert_eq!(res, [record(1), record(2), record(3)]);
+
+	// Codec of the file is kept
+	#[cfg(feature = "deflate")]
+	{
+		let mut writer = WriterBuilder::new(&mut serializer_config)
+			.compression(Compression::Deflate {
+				level: CompressionLevel::default(),
+			})
+			.build(Vec::new())
+			.unwrap();
+		writer.serialize(record(1)).unwrap();
+		let file = std::io::Cursor::new(writer.into_inner().unwrap());
+		let file = append(&mut serializer_config, file, &[record(2)]);
+		let mut reader = Reader::from_slice(file.get_ref()).unwrap();
+		assert_eq!(
+			reader.compression_codec(),
+			Compression::Deflate {
+				level: CompressionLevel::default(),
+			}
+			.codec()
+		);
+		let res: Vec<SchemaRecord<'_>> = reader
+			.deserialize_borrowed()
+			.collect::<Result<_, _>>()
+			.unwrap();
+		assert_eq!(res, [record(1), record(2)]);
+	}
+
+	// Schema mismatch
+	let other_schema: Schema = UNION_SCHEMA.parse().unwrap();
+	let mut other_serializer_config = SerializerConfig::new(&other_schema);
+	assert!(WriterBuilder::new(&mut other_serializer_config)
+		.build_append(file)
+		.is_err());
+}
+
+#[test]
+fn test_resync_on_corrupt_blocks() {
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let record = |a: i64| SchemaRecord { a, b: "foo".into() };
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.approx_block_size(1)
+		.build(Vec::new())
+		.unwrap();
+	writer.serialize_all((1..=4).map(record)).unwrap();
+	let file = writer.into_inner().unwrap();
+	let sync_marker = &file[file.len() - 16..];
+	// Each block starts right after a sync marker
+	let block_starts: Vec<usize> = file
+		.windows(16)
+		.enumerate()
+		.filter(|&(_, window)| window == sync_marker)
+		.map(|(pos, _)| pos + 16)
+		.collect();
+	assert_eq!(block_starts.len(), 5);
+
+	fn read(
+		file: &[u8],
+	) -> (
+		Vec<Result<SchemaRecord<'static>, serde_avro_fast::de::DeError>>,
+		Vec<object_container_file_encoding::CorruptBlock>,
+	) {
+		// The callback may borrow local state
+		let mut corrupt_blocks = Vec::new();
+		let mut reader = Reader::from_slice(file)
+			.unwrap()
+			.resync_on_corrupt_blocks(|corrupt_block| corrupt_blocks.push(corrupt_block));
+		let res: Vec<Result<SchemaRecord<'_>, _>> = reader
+			.deserialize()
+			.map(|res| res.map(OwnedSchemaRecord::into_schema_record))
+			.collect();
+		// The reader stays `Send` and `Sync` when resynchronizing
+		fn assert_send_sync<T: Send + Sync>(_: &T) {}
+		assert_send_sync(&reader);
+		drop(reader);
+		(res, corrupt_blocks)
+	}
+
+	// Invalid block header: only that block is skipped
+	let mut corrupted = file.clone();
+	corrupted[block_starts[1]] = 1; // Negative object count
+	let (res, corrupt_blocks) = read(&corrupted);
+	let res: Vec<SchemaRecord<'_>> = res.into_iter().collect::<Result<_, _>>().unwrap();
+	assert_eq!(res, [record(1), record(3), record(4)]);
+	assert_eq!(corrupt_blocks.len(), 1);
+	assert_eq!(
+		corrupt_blocks[0].skipped_range,
+		block_starts[1]..block_starts[2]
+	);
+	assert_eq!(corrupt_blocks[0].estimated_lost_records, Some(1));
+
+	// Record that fails to deserialize: the error is yielded, then we carry on
+	// with the next block
+	let mut corrupted = file.clone();
+	// Block header (2 bytes), then `a` (1 byte), then the length of `b`
+	corrupted[block_starts[2] + 3] = 0x7e;
+	let (res, corrupt_blocks) = read(&corrupted);
+	assert_eq!(res.len(), 4);
+	assert!(res[2].is_err());
+	assert_eq!(
+		res.into_iter().filter_map(Result::ok).collect::<Vec<_>>(),
+		[record(1), record(2), record(4)]
+	);
+	assert_eq!(corrupt_blocks.len(), 1);
+	assert_eq!(
+		corrupt_blocks[0].skipped_range,
+		block_starts[2]..block_starts[3]
+	);
+	assert_eq!(corrupt_blocks[0].estimated_lost_records, Some(1));
+
+	// Empty blocks are valid
+	let mut with_empty_block = file.clone();
+	with_empty_block.splice(
+		block_starts[1]..block_starts[1],
+		[0, 0].iter().chain(sync_marker).copied(),
+	);
+	let (res, corrupt_blocks) = read(&with_empty_block);
+	let res: Vec<SchemaRecord<'_>> = res.into_iter().collect::<Result<_, _>>().unwrap();
+	assert_eq!(res, (1..=4).map(record).collect::<Vec<_>>());
+	assert!(corrupt_blocks.is_empty());
+
+	// Truncated file (e.g. crashed writer)
+	let (res, corrupt_blocks) = read(&file[..file.len() - 5]);
+	let res: Vec<SchemaRecord<'_>> = res.into_iter().collect::<Result<_, _>>().unwrap();
+	assert_eq!(res, [record(1), record(2), record(3)]);
+	assert_eq!(corrupt_blocks.len(), 1);
+	assert_eq!(
+		corrupt_blocks[0].skipped_range,
+		block_starts[3]..file.len() - 5
+	);
+}
+
+#[test]
+fn test_from_range() {
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let record = |a: i64| SchemaRecord { a, b: "foo".into() };
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.approx_block_size(20)
+		.build(Vec::new())
+		.unwrap();
+	writer.serialize_all((0..100).map(record)).unwrap();
+	let file = writer.into_inner().unwrap();
+	let header_source = Reader::from_slice(&file).unwrap();
+
+	let file_len = file.len() as u64;
+	for split_size in [1, 7, 16, 50, 100, file_len] {
+		let mut res = Vec::new();
+		let mut start = 0;
+		while start < file_len {
+			let end = (start + split_size).min(file_len);
+			// Small buffer to make sure we find sync markers that span across
+			// buffer refills
+			let data_source =
+				std::io::BufReader::with_capacity(5, std::io::Cursor::new(file.as_slice()));
+			let mut reader = Reader::from_range(&header_source, data_source, start, end).unwrap();
+			for record in reader.deserialize::<OwnedSchemaRecord>() {
+				res.push(record.unwrap().into_schema_record());
+			}
+			start = end;
+		}
+		assert_eq!(res, (0..100).map(record).collect::<Vec<_>>());
+	}
+
+	// Range that contains no sync marker
+	let mut reader = Reader::from_range(
+		&header_source,
+		std::io::Cursor::new(file.as_slice()),
+		file_len - 10,
+		file_len,
+	)
+	.unwrap();
+	assert!(reader.next_raw_block().unwrap().is_none());
+}
+
+#[test]
+#[cfg(feature = "memmap2")]
+fn test_open_mmap() {
+	#[derive(Deserialize, Debug, PartialEq, Eq)]
+	struct BorrowedSchemaRecord<'a> {
+		a: i64,
+		b: &'a str,
+	}
+
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let record = |a: i64| SchemaRecord { a, b: "foo".into() };
+	let path = std::env::temp_dir().join(format!(
+		"serde_avro_fast_test_open_mmap_{}.avro",
+		std::process::id()
+	));
+	for compression in [
+		Compression::Null,
+		Compression::Deflate {
+			level: CompressionLevel::default(),
+		},
+	] {
+		let mut writer = WriterBuilder::new(&mut serializer_config)
+			.compression(compression.clone())
+			.approx_block_size(20)
+			.build(Vec::new())
+			.unwrap();
+		writer.serialize_all((0..10).map(record)).unwrap();
+		std::fs::write(&path, writer.into_inner().unwrap()).unwrap();
+
+		// Safety: the file is not modified while it is mapped
+		let mut reader = unsafe { Reader::open_mmap(&path) }.unwrap();
+		assert_eq!(reader.compression_codec(), compression.codec());
+		let first = reader
+			.deserialize::<OwnedSchemaRecord>()
+			.next()
+			.unwrap()
+			.unwrap();
+		assert_eq!(first.into_schema_record(), record(0));
+		// Finish the first block (4 records of 5 bytes)
+		let mut res = Vec::new();
+		for record in reader.deserialize::<OwnedSchemaRecord>().take(3) {
+			res.push(record.unwrap().into_schema_record());
+		}
+		let mut n_blocks = 0;
+		reader
+			.for_each_in_block(|block| {
+				n_blocks += 1;
+				while let Some(record) = block.deserialize_next::<BorrowedSchemaRecord<'_>>()? {
+					res.push(SchemaRecord {
+						a: record.a,
+						b: record.b.to_owned().into(),
+					});
+				}
+				Ok::<_, serde_avro_fast::de::DeError>(())
+			})
+			.unwrap();
+		assert!(n_blocks > 1);
+		assert_eq!(res, (1..10).map(record).collect::<Vec<_>>());
+	}
+	std::fs::remove_file(&path).unwrap();
+
+	assert!(matches!(
+		// Safety: the file does not exist
+		unsafe { Reader::open_mmap(&path) },
+		Err(object_container_file_encoding::OpenMmapError::Io(_))
+	));
+}
+
+#[test]
+fn test_metadata() {
+	#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+	struct UserMetadata<'a> {
+		created_by: &'a str,
+		#[serde(rename = "avro.custom")]
+		custom: &'a str,
+	}
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let user_metadata = UserMetadata {
+		created_by: "me",
+		custom: "value",
+	};
+	let writer = WriterBuilder::new(&mut serializer_config)
+		.build_with_user_metadata(Vec::new(), &user_metadata)
+		.unwrap();
+	let file = writer.into_inner().unwrap();
+
+	// Borrowing from the input is still possible when building the reader
+	let (reader, borrowed_user_metadata) = Reader::new_and_metadata::<UserMetadata<'_>>(
+		serde_avro_fast::de::read::SliceRead::new(&file),
+	)
+	.unwrap();
+	assert_eq!(borrowed_user_metadata, user_metadata);
+
+	let metadata = reader.metadata();
+	assert_eq!(
+		metadata.iter().map(|(key, _)| key).collect::<Vec<_>>(),
+		["avro.schema", "avro.codec", "created_by", "avro.custom"]
+	);
+	assert_eq!(metadata.schema_json(), Some(schema.json()));
+	assert_eq!(metadata.codec(), Some("null"));
+	assert_eq!(metadata.get("avro.custom"), Some(b"value".as_slice()));
+	assert_eq!(metadata.get_str("created_by"), Some("me"));
+	assert_eq!(metadata.get("missing"), None);
+	assert_eq!(
+		metadata.user_entries().collect::<Vec<_>>(),
+		[("created_by", b"me".as_slice())]
+	);
+	assert_eq!(
+		metadata.user_metadata::<UserMetadata<'_>>().unwrap(),
+		user_metadata
+	);
+
+	// Same when reading from an `impl BufRead`
+	let reader = Reader::from_reader(file.as_slice()).unwrap();
+	assert_eq!(reader.metadata(), metadata);
+}
+
+#[test]
+fn test_writer_flush_policies_and_stats() {
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let record = |a: i64| SchemaRecord { a, b: "foo".into() };
+	let n_blocks = |file: &[u8]| {
+		let mut reader = Reader::from_slice(file).unwrap();
+		let mut n_blocks = 0;
+		while reader.next_raw_block().unwrap().is_some() {
+			n_blocks += 1;
+		}
+		n_blocks
+	};
+
+	// Max records per block
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.max_records_per_block(2)
+		.build(Vec::new())
+		.unwrap();
+	writer.serialize_all((0..5).map(record)).unwrap();
+	assert_eq!(writer.stats().n_records, 4);
+	assert_eq!(writer.stats().n_blocks, 2);
+	writer.finish_block().unwrap();
+	let stats = writer.stats();
+	assert_eq!(stats.n_records, 5);
+	assert_eq!(stats.n_blocks, 3);
+	// Null codec: 5 bytes per record
+	assert_eq!(stats.uncompressed_bytes, 25);
+	assert_eq!(stats.compressed_bytes, 25);
+	assert_eq!(stats.compression_ratio(), Some(1.));
+	assert_eq!(n_blocks(&writer.into_inner().unwrap()), 3);
+
+	// Max block duration
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.max_block_duration(std::time::Duration::ZERO)
+		.build(Vec::new())
+		.unwrap();
+	writer.serialize_all((0..3).map(record)).unwrap();
+	assert_eq!(n_blocks(&writer.into_inner().unwrap()), 3);
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.max_block_duration(std::time::Duration::from_secs(3600))
+		.build(Vec::new())
+		.unwrap();
+	writer.serialize_all((0..3).map(record)).unwrap();
+	assert_eq!(n_blocks(&writer.into_inner().unwrap()), 1);
+
+	// Explicit flush also flushes the underlying writer
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.build(std::io::BufWriter::new(Vec::new()))
+		.unwrap();
+	assert_eq!(writer.stats(), Default::default());
+	assert_eq!(writer.stats().compression_ratio(), None);
+	writer.serialize(record(0)).unwrap();
+	writer.flush().unwrap();
+	let res: Vec<SchemaRecord<'_>> = Reader::from_slice(writer.inner().get_ref())
+		.unwrap()
+		.deserialize_borrowed()
+		.collect::<Result<_, _>>()
+		.unwrap();
+	assert_eq!(res, [record(0)]);
+	assert_eq!(writer.stats().n_blocks, 1);
+	drop(writer);
+
+	#[cfg(feature = "deflate")]
+	{
+		let mut writer = WriterBuilder::new(&mut serializer_config)
+			.compression(Compression::Deflate {
+				level: CompressionLevel::default(),
+			})
+			.build(Vec::new())
+			.unwrap();
+		writer.serialize_all((0..100).map(|_| record(0))).unwrap();
+		writer.finish_block().unwrap();
+		let stats = writer.stats();
+		assert_eq!(stats.uncompressed_bytes, 500);
+		assert!(stats.compression_ratio().unwrap() > 10.);
+	}
+}
+
+#[test]
+fn test_writer_parallel_compression() {
+	use {
+		object_container_file_encoding::{Codec, CustomCodec},
+		std::{
+			collections::HashSet,
+			sync::{Arc, Mutex},
+		},
+	};
+
+	/// XORs every byte, taking longer for some blocks than for others so that
+	/// blocks finish compressing out of order, and failing on blocks that
+	/// contain `fail`
+	struct SlowXor {
+		threads: Arc<Mutex<HashSet<String>>>,
+	}
+	impl Codec for SlowXor {
+		fn name(&self) -> &'static str {
+			"slow-xor"
+		}
+		fn compress(&self, uncompressed: &[u8], compressed: &mut Vec<u8>) -> std::io::Result<()> {
+			self.threads
+				.lock()
+				.unwrap()
+				.insert(std::thread::current().name().unwrap_or_default().to_owned());
+			if uncompressed.windows(4).any(|w| w == b"fail") {
+				return Err(std::io::Error::other("refusing to compress this block"));
+			}
+			std::thread::sleep(std::time::Duration::from_millis(
+				u64::from(uncompressed[0] % 4) * 2,
+			));
+			compressed.extend(uncompressed.iter().map(|b| b ^ 0x5A));
+			Ok(())
+		}
+		fn decompress(&self, compressed: &[u8], decompressed: &mut Vec<u8>) -> std::io::Result<()> {
+			decompressed.extend(compressed.iter().map(|b| b ^ 0x5A));
+			Ok(())
+		}
+	}
+
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let record = |a: i64| SchemaRecord {
+		a,
+		b: format!("record {a}").into(),
+	};
+	let threads = Arc::new(Mutex::new(HashSet::new()));
+	let compression = Compression::Custom(CustomCodec::new(SlowXor {
+		threads: threads.clone(),
+	}));
+	let write = |serializer_config: &mut SerializerConfig<'_>,
+	             n_threads: usize,
+	             records: &mut dyn Iterator<Item = SchemaRecord<'static>>| {
+		let mut writer = WriterBuilder::new(serializer_config)
+			.compression(compression.clone())
+			.max_records_per_block(3)
+			.parallel_compression(n_threads)
+			.sync_marker([7; 16])
+			.build(Vec::new())?;
+		writer.serialize_all(records)?;
+		writer.into_inner()
+	};
+
+	// Blocks are compressed on several threads, but written in order, and the
+	// result is the same as when compressing inline
+	let serialized = write(&mut serializer_config, 4, &mut (0..300).map(record)).unwrap();
+	let threads = std::mem::take(&mut *threads.lock().unwrap());
+	assert!(threads.len() > 1);
+	assert!(threads
+		.iter()
+		.all(|name| name.starts_with("avro-compression-")));
+	let expected = write(&mut serializer_config, 0, &mut (0..300).map(record)).unwrap();
+	assert_eq!(serialized, expected);
+	let res: Vec<SchemaRecord<'_>> = Reader::new_with_codecs(
+		serde_avro_fast::de::read::SliceRead::new(&serialized),
+		&CodecRegistry::new().register(SlowXor {
+			threads: Default::default(),
+		}),
+	)
+	.unwrap()
+	.deserialize_borrowed()
+	.collect::<Result<_, _>>()
+	.unwrap();
+	assert_eq!(res, (0..300).map(record).collect::<Vec<_>>());
+
+	// An error when compressing a block is surfaced
+	let err = write(
+		&mut serializer_config,
+		4,
+		&mut (0..300).map(record).chain([SchemaRecord {
+			a: 300,
+			b: "fail".into(),
+		}]),
+	)
+	.unwrap_err();
+	assert!(err.to_string().contains("refusing to compress this block"));
+	let err = write(
+		&mut serializer_config,
+		4,
+		&mut (0..300).map(|a| match a {
+			150 => SchemaRecord {
+				a,
+				b: "fail".into(),
+			},
+			_ => record(a),
+		}),
+	)
+	.unwrap_err();
+	assert!(err.to_string().contains("refusing to compress this block"));
+}
+
 #[test]
 fn test_reader_invalid_header() {
 	//let schema: Schema = SCHEMA.parse().unwrap();
@@ -310,6 +1403,189 @@ fn test_reader_invalid_block() {
 	assert!(res.is_err());
 }
 
+#[cfg(feature = "tokio")]
+#[test]
+fn test_async_reader() {
+	assert_eq!(
+		async_read_all(ENCODED).unwrap(),
+		[
+			SchemaRecord {
+				a: 27,
+				b: "foo".into(),
+			},
+			SchemaRecord {
+				a: 42,
+				b: "bar".into(),
+			},
+		]
+	);
+	assert!(async_read_all(&ENCODED[1..]).is_err());
+	assert!(async_read_all(&ENCODED[..(ENCODED.len() - 19)]).is_err());
+	assert!(async_read_all(&ENCODED[..165]).is_err());
+	assert!(async_read_all(&[]).is_err());
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn test_async_writer() {
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let record = |a: i64| SchemaRecord { a, b: "foo".into() };
+	fn read_all(file: &[u8]) -> Vec<SchemaRecord<'_>> {
+		Reader::from_slice(file)
+			.unwrap()
+			.deserialize_borrowed()
+			.collect::<Result<_, _>>()
+			.unwrap()
+	}
+	let sync_marker = [7; 16];
+	let runtime = tokio::runtime::Builder::new_current_thread()
+		.build()
+		.unwrap();
+
+	// Multiple blocks, same output as the synchronous writer
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.approx_block_size(1)
+		.sync_marker(sync_marker)
+		.build(Vec::new())
+		.unwrap();
+	writer.serialize_all((0..5).map(record)).unwrap();
+	let expected = writer.into_inner().unwrap();
+	let serialized = runtime.block_on(async {
+		let mut writer = WriterBuilder::new(&mut serializer_config)
+			.approx_block_size(1)
+			.sync_marker(sync_marker)
+			.build_async(Vec::new())
+			.await
+			.unwrap();
+		writer.serialize_all((0..5).map(record)).await.unwrap();
+		// Each block is written as soon as it is full
+		assert_eq!(writer.stats().n_blocks, 5);
+		writer.into_inner().await.unwrap()
+	});
+	assert_eq!(serialized, expected);
+	assert_eq!(
+		read_all(&serialized),
+		(0..5).map(record).collect::<Vec<_>>()
+	);
+
+	// Finishing a block makes what was written so far a valid file
+	runtime.block_on(async {
+		let mut writer = WriterBuilder::new(&mut serializer_config)
+			.build_async(Vec::new())
+			.await
+			.unwrap();
+		writer.serialize(record(0)).await.unwrap();
+		writer.serialize(record(1)).await.unwrap();
+		assert_eq!(read_all(writer.inner()), []);
+		writer.finish_block().await.unwrap();
+		assert_eq!(read_all(writer.inner()), [record(0), record(1)]);
+		writer.serialize(record(2)).await.unwrap();
+		let stats = writer.stats();
+		assert_eq!((stats.n_records, stats.n_blocks), (2, 1));
+		let serialized = writer.into_inner().await.unwrap();
+		assert_eq!(read_all(&serialized), [record(0), record(1), record(2)]);
+	});
+
+	// Explicit flush also flushes the underlying writer
+	runtime.block_on(async {
+		let mut writer = WriterBuilder::new(&mut serializer_config)
+			.build_async(tokio::io::BufWriter::new(Vec::new()))
+			.await
+			.unwrap();
+		writer.serialize(record(0)).await.unwrap();
+		writer.flush().await.unwrap();
+		assert_eq!(read_all(writer.inner().get_ref()), [record(0)]);
+		writer.serialize(record(1)).await.unwrap();
+		let serialized = writer.into_inner().await.unwrap().into_inner();
+		assert_eq!(read_all(&serialized), [record(0), record(1)]);
+	});
+
+	// Compressed
+	#[cfg(feature = "deflate")]
+	{
+		let serialized = runtime.block_on(async {
+			let mut writer = WriterBuilder::new(&mut serializer_config)
+				.compression(Compression::Deflate {
+					level: CompressionLevel::default(),
+				})
+				.approx_block_size(1)
+				.build_async(Vec::new())
+				.await
+				.unwrap();
+			writer.serialize_all((0..5).map(record)).await.unwrap();
+			writer.into_inner().await.unwrap()
+		});
+		assert_eq!(
+			async_read_all(&serialized).unwrap(),
+			(0..5).map(record).collect::<Vec<_>>()
+		);
+	}
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_reader() {
+	use rayon::iter::ParallelIterator;
+
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let mut serializer_config = SerializerConfig::new(&schema);
+	let record = |a: i64| SchemaRecord {
+		a,
+		b: format!("record {a}").into(),
+	};
+	let mut writer = WriterBuilder::new(&mut serializer_config)
+		.max_records_per_block(7)
+		.build(Vec::new())
+		.unwrap();
+	writer.serialize_all((0..1000).map(record)).unwrap();
+	let serialized = writer.into_inner().unwrap();
+
+	let blocks: Vec<Vec<OwnedSchemaRecord>> = Reader::from_slice(&serialized)
+		.unwrap()
+		.par_deserialize_blocks()
+		.collect::<Result<_, _>>()
+		.unwrap();
+	assert_eq!(blocks.len(), 143);
+	assert!(blocks[..142].iter().all(|block| block.len() == 7));
+	assert_eq!(
+		blocks
+			.into_iter()
+			.flatten()
+			.map(OwnedSchemaRecord::into_schema_record)
+			.collect::<Vec<_>>(),
+		(0..1000).map(record).collect::<Vec<_>>()
+	);
+
+	// Starting in the middle of a block
+	let mut reader = Reader::from_slice(&serialized).unwrap();
+	let mut res: Vec<SchemaRecord<'_>> = (0..3)
+		.map(|_| reader.deserialize_next_borrowed().unwrap().unwrap())
+		.collect();
+	res.extend(
+		reader
+			.par_deserialize::<OwnedSchemaRecord>()
+			.map(|record| record.map(OwnedSchemaRecord::into_schema_record))
+			.collect::<Result<Vec<_>, _>>()
+			.unwrap(),
+	);
+	assert_eq!(res, (0..1000).map(record).collect::<Vec<_>>());
+	assert!(reader
+		.deserialize_next::<OwnedSchemaRecord>()
+		.unwrap()
+		.is_none());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_reader_invalid_block() {
+	use rayon::iter::ParallelIterator;
+	let invalid = &ENCODED[0..(ENCODED.len() - 19)];
+	let mut reader = Reader::from_slice(invalid).unwrap();
+	let res: Result<Vec<OwnedSchemaRecord>, _> = reader.par_deserialize().collect();
+	assert!(res.is_err());
+}
+
 #[test]
 fn test_reader_empty_buffer() {
 	let empty: &[u8] = &[];
@@ -328,6 +1604,7 @@ fn test_reader_only_header() {
 	assert!(res.is_err());
 }
 
+#[cfg(any(feature = "snappy", not(feature = "lz4")))]
 const SNAPPY_COMPRESSED_AVRO: &[u8] = &[
 	79, 98, 106, 1, 4, 22, 97, 118, 114, 111, 46, 115, 99, 104, 101, 109, 97, 210, 1, 123, 34, 102,
 	105, 101, 108, 100, 115, 34, 58, 91, 123, 34, 110, 97, 109, 101, 34, 58, 34, 110, 117, 109, 34,
@@ -339,7 +1616,8 @@ const SNAPPY_COMPRESSED_AVRO: &[u8] = &[
 	208, 200, 110, 164, 47, 203, 25, 90, 235, 161, 167, 195, 177, 2, 20, 4, 12, 6, 49, 50, 51, 115,
 	38, 58, 0, 213, 209, 241, 208, 200, 110, 164, 47, 203, 25, 90, 235, 161, 167, 195, 177,
 ];
-#[cfg(not(feature = "snappy"))]
+// lz4 would be listed in the expected codecs
+#[cfg(not(any(feature = "snappy", feature = "lz4")))]
 #[test]
 fn test_avro_3549_read_not_enabled_codec() {
 	if let Err(err) = Reader::from_slice(SNAPPY_COMPRESSED_AVRO) {
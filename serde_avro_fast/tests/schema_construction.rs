@@ -56,6 +56,125 @@ fn prettify_json(s: &str) -> String {
 	.unwrap()
 }
 
+#[test]
+fn docs_and_defaults_are_retained() {
+	let schema_str = prettify_json(
+		r#"
+			{
+				"type": "record",
+				"name": "a.b",
+				"doc": "A record",
+				"fields": [
+					{
+						"name": "c",
+						"doc": "A field",
+						"type": ["null", "long"],
+						"default": null
+					},
+					{
+						"name": "d",
+						"type": {
+							"type": "enum",
+							"name": "e",
+							"doc": "An enum",
+							"symbols": ["F", "G"]
+						},
+						"default": "G"
+					}
+				]
+			}
+		"#,
+	);
+	let mut parsed_schema: SchemaMut = schema_str.parse().unwrap();
+	match &parsed_schema.root().type_ {
+		RegularType::Record(record) => {
+			assert_eq!(record.doc(), Some("A record"));
+			assert_eq!(record.fields[0].doc(), Some("A field"));
+			assert_eq!(record.fields[0].default(), Some(&serde_json::Value::Null));
+			assert_eq!(record.fields[1].doc(), None);
+			assert_eq!(record.fields[1].default(), Some(&"G".into()));
+		}
+		_ => panic!("Root should be a record"),
+	}
+
+	// They are written back when the schema JSON is re-generated
+	parsed_schema.nodes_mut();
+	assert_eq!(
+		serde_json::to_string_pretty(&parsed_schema).unwrap(),
+		schema_str
+	);
+}
+
+#[test]
+fn check_defaults() {
+	let schema = |field_type: &str, default: &str| -> SchemaMut {
+		format!(
+			r#"{{
+				"type": "record",
+				"name": "a",
+				"fields": [{{ "name": "b", "type": {field_type}, "default": {default} }}]
+			}}"#
+		)
+		.parse()
+		.unwrap()
+	};
+	for (field_type, default) in [
+		(r#""int""#, "1"),
+		(r#""long""#, "-1"),
+		(r#""double""#, "1.5"),
+		(r#""string""#, r#""a""#),
+		(r#""bytes""#, r#""\u00ff""#),
+		(r#"{"type": "fixed", "name": "f", "size": 2}"#, r#""ab""#),
+		(
+			r#"{"type": "enum", "name": "e", "symbols": ["C"]}"#,
+			r#""C""#,
+		),
+		(r#"["null", "int"]"#, "null"),
+		(r#"{"type": "array", "items": "int"}"#, "[1, 2]"),
+		(r#"{"type": "map", "values": "int"}"#, r#"{"c": 1}"#),
+		(
+			r#"{"type": "record", "name": "r", "fields": [
+				{"name": "c", "type": "int"},
+				{"name": "d", "type": "int", "default": 0}
+			]}"#,
+			r#"{"c": 1}"#,
+		),
+	] {
+		schema(field_type, default).check_defaults().unwrap();
+	}
+	for (field_type, default) in [
+		(r#""int""#, "2147483648"),
+		(r#""long""#, "1.5"),
+		(r#""string""#, "1"),
+		(r#""bytes""#, r#""\u0100""#),
+		(r#"{"type": "fixed", "name": "f", "size": 2}"#, r#""a""#),
+		(
+			r#"{"type": "enum", "name": "e", "symbols": ["C"]}"#,
+			r#""D""#,
+		),
+		// The default of a union should match its first variant
+		(r#"["null", "int"]"#, "1"),
+		(r#"{"type": "array", "items": "int"}"#, r#"["c"]"#),
+		(r#"{"type": "map", "values": "int"}"#, r#"{"c": "d"}"#),
+		(
+			r#"{"type": "record", "name": "r", "fields": [{"name": "c", "type": "int"}]}"#,
+			"{}",
+		),
+	] {
+		assert_eq!(
+			schema(field_type, default)
+				.check_defaults()
+				.unwrap_err()
+				.to_string(),
+			format!(
+				"The default of field `b` of record `a` does not match the schema of the field: \
+					{}",
+				serde_json::from_str::<serde_json::Value>(default).unwrap()
+			)
+		);
+	}
+}
+
 #[test]
 fn impossible_schema_construction() {
 	// Contains a cycle that would lead to infinite recursion when serializing
@@ -0,0 +1,112 @@
+#![allow(missing_docs)]
+
+use {
+	serde_avro_fast::{from_datum_slice, ser::SerializerConfig, to_datum_vec, Schema},
+	std::time::{Duration, SystemTime},
+};
+
+fn round_trip<T>(value: T, expected_value: T, schema: &str, apache_value: apache_avro::types::Value)
+where
+	T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+{
+	let apache_schema = apache_avro::Schema::parse_str(schema).unwrap();
+	let schema: Schema = schema.parse().unwrap();
+	let mut config = SerializerConfig::new(&schema);
+	config.allow_sub_unit_precision_truncation();
+	let datum = to_datum_vec(&value, &mut config).unwrap();
+	assert_eq!(
+		apache_avro::from_avro_datum(&apache_schema, &mut &*datum, None).unwrap(),
+		apache_value
+	);
+	assert_eq!(
+		from_datum_slice::<T>(&datum, &schema).unwrap(),
+		expected_value
+	);
+}
+
+#[test]
+fn std_duration() {
+	let schema = r#"{"type":"fixed","name":"duration","size":12,"logicalType":"duration"}"#;
+	round_trip(
+		Duration::new(2 * 86_400 + 3, 4_000_000),
+		Duration::new(2 * 86_400 + 3, 4_000_000),
+		schema,
+		apache_avro::types::Value::Duration(apache_avro::Duration::new(
+			apache_avro::Months::new(0),
+			apache_avro::Days::new(2),
+			apache_avro::Millis::new(3_004),
+		)),
+	);
+	round_trip(
+		Some(Duration::from_millis(1)),
+		Some(Duration::from_millis(1)),
+		&format!(r#"["null",{schema}]"#),
+		apache_avro::types::Value::Union(
+			1,
+			Box::new(apache_avro::types::Value::Duration(
+				apache_avro::Duration::new(
+					apache_avro::Months::new(0),
+					apache_avro::Days::new(0),
+					apache_avro::Millis::new(1),
+				),
+			)),
+		),
+	);
+
+	// Sub-millisecond precision would be lost
+	round_trip(
+		Duration::new(3, 4_567_891),
+		Duration::new(3, 4_000_000),
+		schema,
+		apache_avro::types::Value::Duration(apache_avro::Duration::new(
+			apache_avro::Months::new(0),
+			apache_avro::Days::new(0),
+			apache_avro::Millis::new(3_004),
+		)),
+	);
+	let schema: Schema = schema.parse().unwrap();
+	assert!(to_datum_vec(
+		&Duration::new(3, 4_567_891),
+		&mut SerializerConfig::new(&schema)
+	)
+	.is_err());
+
+	// Months don't have a fixed length
+	assert!(from_datum_slice::<Duration>(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], &schema).is_err());
+}
+
+#[test]
+fn std_system_time() {
+	let time = SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789);
+	round_trip(
+		time,
+		SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_000),
+		r#"{"type":"long","logicalType":"timestamp-micros"}"#,
+		apache_avro::types::Value::TimestampMicros(1_700_000_000_123_456),
+	);
+	round_trip(
+		Some(time),
+		Some(SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123_000_000)),
+		r#"["null",{"type":"long","logicalType":"timestamp-millis"}]"#,
+		apache_avro::types::Value::Union(
+			1,
+			Box::new(apache_avro::types::Value::TimestampMillis(
+				1_700_000_000_123,
+			)),
+		),
+	);
+
+	// Sub-unit precision is only truncated when allowed
+	let schema: Schema = r#"{"type":"long","logicalType":"timestamp-micros"}"#
+		.parse()
+		.unwrap();
+	assert!(to_datum_vec(&time, &mut SerializerConfig::new(&schema)).is_err());
+	assert!(to_datum_vec(
+		&(SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_000)),
+		&mut SerializerConfig::new(&schema)
+	)
+	.is_ok());
+
+	// SystemTime can't represent timestamps before the epoch via serde
+	assert!(from_datum_slice::<SystemTime>(&[1], &schema).is_err());
+}
@@ -0,0 +1,228 @@
+#![allow(missing_docs)]
+#![cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+
+use serde_avro_fast::Schema;
+
+const SCHEMA: &str = r#"
+{
+	"type": "record",
+	"name": "Temporal",
+	"fields": [
+		{ "name": "timestamp_millis", "type": { "type": "long", "logicalType": "timestamp-millis" } },
+		{ "name": "timestamp_micros", "type": ["null", { "type": "long", "logicalType": "timestamp-micros" }] },
+		{ "name": "date", "type": { "type": "int", "logicalType": "date" } },
+		{ "name": "time_millis", "type": { "type": "int", "logicalType": "time-millis" } },
+		{ "name": "time_micros", "type": { "type": "long", "logicalType": "time-micros" } }
+	]
+}
+"#;
+
+#[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq)]
+struct Raw {
+	timestamp_millis: i64,
+	timestamp_micros: Option<i64>,
+	date: i32,
+	time_millis: i32,
+	time_micros: i64,
+}
+
+const RAW: Raw = Raw {
+	timestamp_millis: 1_700_000_000_123,
+	timestamp_micros: Some(-1_234_567),
+	date: 19_675,
+	time_millis: 45_296_789,
+	time_micros: 45_296_789_012,
+};
+
+fn round_trip<T>(value: &T)
+where
+	T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+{
+	let schema: Schema = SCHEMA.parse().unwrap();
+	let serialized = serde_avro_fast::to_datum_vec(
+		value,
+		&mut serde_avro_fast::ser::SerializerConfig::new(&schema),
+	)
+	.unwrap();
+	// Integers are what is specified
+	let raw: Raw = serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	assert_eq!(raw, RAW);
+	let deserialized: T = serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	assert_eq!(&deserialized, value);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono() {
+	use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+
+	#[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq)]
+	struct Temporal {
+		timestamp_millis: DateTime<Utc>,
+		timestamp_micros: Option<DateTime<Utc>>,
+		date: NaiveDate,
+		time_millis: NaiveTime,
+		time_micros: NaiveTime,
+	}
+
+	round_trip(&Temporal {
+		timestamp_millis: DateTime::from_timestamp_millis(1_700_000_000_123).unwrap(),
+		timestamp_micros: Some(DateTime::from_timestamp_micros(-1_234_567).unwrap()),
+		date: NaiveDate::from_ymd_opt(2023, 11, 14).unwrap(),
+		time_millis: NaiveTime::from_hms_milli_opt(12, 34, 56, 789).unwrap(),
+		time_micros: NaiveTime::from_hms_micro_opt(12, 34, 56, 789_012).unwrap(),
+	});
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time() {
+	use time::{macros::*, Date, OffsetDateTime, Time};
+
+	#[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq)]
+	struct Temporal {
+		#[serde(with = "time::serde::rfc3339")]
+		timestamp_millis: OffsetDateTime,
+		timestamp_micros: Option<Rfc3339>,
+		#[serde(with = "date_format")]
+		date: Date,
+		#[serde(with = "time_format")]
+		time_millis: Time,
+		#[serde(with = "time_format")]
+		time_micros: Time,
+	}
+	// `time::serde::rfc3339::option` deserializes through `deserialize_any`, which
+	// provides the integer representation, so we wrap instead
+	#[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq)]
+	#[serde(transparent)]
+	struct Rfc3339(#[serde(with = "time::serde::rfc3339")] OffsetDateTime);
+	time::serde::format_description!(date_format, Date, "[year]-[month]-[day]");
+	time::serde::format_description!(
+		time_format,
+		Time,
+		"[hour]:[minute]:[second][optional [.[subsecond]]]"
+	);
+
+	round_trip(&Temporal {
+		timestamp_millis: datetime!(2023-11-14 22:13:20.123 UTC),
+		timestamp_micros: Some(Rfc3339(datetime!(1969-12-31 23:59:58.765433 UTC))),
+		date: date!(2023 - 11 - 14),
+		time_millis: time!(12:34:56.789),
+		time_micros: time!(12:34:56.789012),
+	});
+}
+
+#[cfg(feature = "jiff")]
+#[test]
+fn jiff() {
+	use jiff::{
+		civil::{date, time, Date, Time},
+		Timestamp,
+	};
+
+	#[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq)]
+	struct Temporal {
+		timestamp_millis: Timestamp,
+		timestamp_micros: Option<Timestamp>,
+		date: Date,
+		time_millis: Time,
+		time_micros: Time,
+	}
+
+	round_trip(&Temporal {
+		timestamp_millis: Timestamp::from_millisecond(1_700_000_000_123).unwrap(),
+		timestamp_micros: Some(Timestamp::from_microsecond(-1_234_567).unwrap()),
+		date: date(2023, 11, 14),
+		time_millis: time(12, 34, 56, 789_000_000),
+		time_micros: time(12, 34, 56, 789_012_000),
+	});
+}
+
+#[test]
+fn sub_unit_precision_is_an_error() {
+	let schema: Schema = SCHEMA.parse().unwrap();
+	#[derive(serde_derive::Serialize)]
+	struct Strings<'a> {
+		timestamp_millis: &'a str,
+		timestamp_micros: Option<&'a str>,
+		date: &'a str,
+		time_millis: &'a str,
+		time_micros: &'a str,
+	}
+	let serialize = |value: &Strings<'_>| {
+		serde_avro_fast::to_datum_vec(
+			value,
+			&mut serde_avro_fast::ser::SerializerConfig::new(&schema),
+		)
+	};
+	let valid = Strings {
+		timestamp_millis: "2023-11-14T22:13:20.123Z",
+		timestamp_micros: Some("1969-12-31T23:59:58.765433Z"),
+		date: "2023-11-14",
+		time_millis: "12:34:56.789",
+		time_micros: "12:34:56.789012",
+	};
+	assert!(serialize(&valid).is_ok());
+	for (value, expected_error) in [
+		(
+			Strings {
+				timestamp_millis: "2023-11-14T22:13:20.1234Z",
+				..valid
+			},
+			"Sub-millisecond precision would be lost",
+		),
+		(
+			Strings {
+				timestamp_micros: Some("1969-12-31T23:59:58.7654321Z"),
+				..valid
+			},
+			"Sub-microsecond precision would be lost",
+		),
+		(
+			Strings {
+				time_millis: "12:34:56.7891",
+				..valid
+			},
+			"Sub-millisecond precision would be lost",
+		),
+		(
+			Strings {
+				time_micros: "12:34:56.7890123",
+				..valid
+			},
+			"Sub-microsecond precision would be lost",
+		),
+	] {
+		let error = serialize(&value).unwrap_err().to_string();
+		assert!(error.contains(expected_error), "{error}");
+	}
+}
+
+#[test]
+fn sub_unit_precision_truncation() {
+	let schema: Schema = SCHEMA.parse().unwrap();
+	#[derive(serde_derive::Serialize)]
+	struct Strings<'a> {
+		timestamp_millis: &'a str,
+		timestamp_micros: Option<&'a str>,
+		date: &'a str,
+		time_millis: &'a str,
+		time_micros: &'a str,
+	}
+	let mut config = serde_avro_fast::ser::SerializerConfig::new(&schema);
+	config.allow_sub_unit_precision_truncation();
+	let serialized = serde_avro_fast::to_datum_vec(
+		&Strings {
+			timestamp_millis: "2023-11-14T22:13:20.1239Z",
+			// Truncated towards the past
+			timestamp_micros: Some("1969-12-31T23:59:58.7654339Z"),
+			date: "2023-11-14",
+			time_millis: "12:34:56.7899",
+			time_micros: "12:34:56.7890129",
+		},
+		&mut config,
+	)
+	.unwrap();
+	let raw: Raw = serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	assert_eq!(raw, RAW);
+}
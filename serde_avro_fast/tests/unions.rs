@@ -147,3 +147,111 @@ fn option_of_enum_union_multi() {
 		&schema,
 	);
 }
+
+#[test]
+fn struct_and_tuple_variants_as_namespaced_records() {
+	let schema: Schema = r#"[
+		"null",
+		{
+			"type": "record",
+			"name": "events.Event.Created",
+			"fields": [{ "name": "id", "type": "long" }, { "name": "name", "type": "string" }]
+		},
+		{
+			"type": "record",
+			"name": "events.Event.Moved",
+			"fields": [{ "name": "_0", "type": "long" }, { "name": "_1", "type": "long" }]
+		}
+	]"#
+	.parse()
+	.unwrap();
+	#[derive(serde_derive::Deserialize, serde_derive::Serialize, PartialEq, Debug)]
+	enum Event {
+		Null,
+		// Matched by short name, as the fully qualified name is not a variant
+		Created { id: i64, name: String },
+		Moved(i64, i64),
+	}
+	test::<Event>(&[0], Event::Null, &schema);
+	test::<Event>(
+		&[2, 2, 2, b'a'],
+		Event::Created {
+			id: 1,
+			name: "a".to_owned(),
+		},
+		&schema,
+	);
+	test::<Event>(&[4, 2, 3], Event::Moved(1, -2), &schema);
+
+	// Records may also be serialized from/deserialized into tuples directly
+	let record_schema: Schema = r#"{
+		"type": "record",
+		"name": "Point",
+		"fields": [{ "name": "x", "type": "long" }, { "name": "y", "type": "string" }]
+	}"#
+	.parse()
+	.unwrap();
+	test::<(i64, &str)>(&[2, 2, b'a'], (1, "a"), &record_schema);
+	assert!(to_datum_vec(&(1, "a", 2), &mut SerializerConfig::new(&record_schema)).is_err());
+}
+
+#[test]
+fn tuple_struct_as_record_in_union() {
+	let schema: Schema = r#"[
+		"null",
+		{
+			"type": "record",
+			"name": "Point",
+			"fields": [{ "name": "x", "type": "long" }, { "name": "y", "type": "long" }]
+		},
+		{
+			"type": "record",
+			"name": "OtherPoint",
+			"fields": [{ "name": "x", "type": "long" }, { "name": "y", "type": "long" }]
+		}
+	]"#
+	.parse()
+	.unwrap();
+	#[derive(serde_derive::Deserialize, serde_derive::Serialize, PartialEq, Debug)]
+	struct OtherPoint(i64, i64);
+	// The union variant is looked up by the name of the tuple struct
+	test::<OtherPoint>(&[4, 2, 4], OtherPoint(1, 2), &schema);
+	test::<Option<OtherPoint>>(&[4, 2, 4], Some(OtherPoint(1, 2)), &schema);
+	test::<Option<OtherPoint>>(&[0], None, &schema);
+}
+
+#[test]
+fn internally_tagged_enum_picks_record_from_tag() {
+	let schema: Schema = r#"[
+		{
+			"type": "record",
+			"name": "ns.Shape.Circle",
+			"fields": [{ "name": "type", "type": "string" }, { "name": "radius", "type": "long" }]
+		},
+		{
+			"type": "record",
+			"name": "ns.Shape.Empty",
+			"fields": [{ "name": "type", "type": "string" }]
+		}
+	]"#
+	.parse()
+	.unwrap();
+	#[derive(serde_derive::Deserialize, serde_derive::Serialize, PartialEq, Debug)]
+	#[serde(tag = "type")]
+	enum Shape {
+		Circle { radius: i64 },
+		Empty,
+	}
+	// Neither the name of the enum nor the fields allow picking the union
+	// variant, so it is picked from the value of the tag
+	test::<Shape>(
+		&[0, 12, b'C', b'i', b'r', b'c', b'l', b'e', 4],
+		Shape::Circle { radius: 2 },
+		&schema,
+	);
+	test::<Shape>(
+		&[2, 10, b'E', b'm', b'p', b't', b'y'],
+		Shape::Empty,
+		&schema,
+	);
+}
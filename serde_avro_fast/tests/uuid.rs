@@ -0,0 +1,178 @@
+#![allow(missing_docs)]
+
+use serde_avro_fast::Schema;
+
+const SCHEMA: &str = r#"
+{
+	"type": "record",
+	"name": "Uuids",
+	"fields": [
+		{ "name": "string", "type": { "type": "string", "logicalType": "uuid" } },
+		{ "name": "fixed", "type": { "type": "fixed", "name": "Uuid", "size": 16, "logicalType": "uuid" } },
+		{ "name": "optional_fixed", "type": ["null", "Uuid"] }
+	]
+}
+"#;
+
+#[cfg(feature = "uuid")]
+const UUID: uuid::Uuid = uuid::uuid!("67e55044-10b1-426f-9247-bb680e5fe0c8");
+
+#[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq)]
+struct Uuids<T> {
+	string: T,
+	fixed: T,
+	optional_fixed: Option<T>,
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn uuid() {
+	let schema: Schema = SCHEMA.parse().unwrap();
+
+	let value = Uuids {
+		string: UUID,
+		fixed: UUID,
+		optional_fixed: Some(UUID),
+	};
+	let serialized = serde_avro_fast::to_datum_vec(
+		&value,
+		&mut serde_avro_fast::ser::SerializerConfig::new(&schema),
+	)
+	.unwrap();
+
+	let mut expected = vec![72];
+	expected.extend_from_slice(UUID.hyphenated().to_string().as_bytes());
+	expected.extend_from_slice(UUID.as_bytes());
+	expected.push(2);
+	expected.extend_from_slice(UUID.as_bytes());
+	assert_eq!(serialized, expected);
+
+	let deserialized: Uuids<uuid::Uuid> =
+		serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	assert_eq!(deserialized, value);
+
+	// Fixed uuids are provided as strings when hinted so
+	let as_strings: Uuids<String> =
+		serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	let uuid_string = UUID.hyphenated().to_string();
+	assert_eq!(
+		as_strings,
+		Uuids {
+			string: uuid_string.clone(),
+			fixed: uuid_string.clone(),
+			optional_fixed: Some(uuid_string.clone()),
+		}
+	);
+	assert_eq!(
+		serde_avro_fast::to_datum_vec(
+			&as_strings,
+			&mut serde_avro_fast::ser::SerializerConfig::new(&schema)
+		)
+		.unwrap(),
+		serialized
+	);
+
+	// Raw bytes can be serialized to both representations
+	let as_bytes = Uuids {
+		string: serde_bytes::ByteBuf::from(UUID.as_bytes().to_vec()),
+		fixed: serde_bytes::ByteBuf::from(UUID.as_bytes().to_vec()),
+		optional_fixed: Some(serde_bytes::ByteBuf::from(UUID.as_bytes().to_vec())),
+	};
+	assert_eq!(
+		serde_avro_fast::to_datum_vec(
+			&as_bytes,
+			&mut serde_avro_fast::ser::SerializerConfig::new(&schema)
+		)
+		.unwrap(),
+		serialized
+	);
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn fixed_of_wrong_size_is_not_uuid() {
+	let schema: Schema =
+		r#"{ "type": "fixed", "name": "NotUuid", "size": 15, "logicalType": "uuid" }"#
+			.parse()
+			.unwrap();
+	assert!(serde_avro_fast::to_datum_vec(
+		&UUID,
+		&mut serde_avro_fast::ser::SerializerConfig::new(&schema)
+	)
+	.is_err());
+}
+
+#[test]
+fn byte_arrays_to_fixed() {
+	// Fixed uuids can still be serialized to as any other fixed of size 16
+	let schema: Schema = SCHEMA.parse().unwrap();
+
+	#[derive(serde_derive::Serialize)]
+	struct ByteArrays {
+		string: &'static str,
+		fixed: [u8; 16],
+		optional_fixed: Option<[u8; 16]>,
+	}
+	let bytes = *b"0123456789abcdef";
+	let value = ByteArrays {
+		string: "67e55044-10b1-426f-9247-bb680e5fe0c8",
+		fixed: bytes,
+		optional_fixed: Some(bytes),
+	};
+
+	let mut config = serde_avro_fast::ser::SerializerConfig::new(&schema);
+	config.allow_slow_sequence_to_bytes();
+	let serialized = serde_avro_fast::to_datum_vec(&value, &mut config).unwrap();
+
+	let mut expected = vec![72];
+	expected.extend_from_slice(b"67e55044-10b1-426f-9247-bb680e5fe0c8");
+	expected.extend_from_slice(&bytes);
+	expected.push(2);
+	expected.extend_from_slice(&bytes);
+	assert_eq!(serialized, expected);
+
+	// As well as from raw strings of the fixed's size
+	let as_strings = Uuids {
+		string: "0123456789abcdef",
+		fixed: "0123456789abcdef",
+		optional_fixed: Some("0123456789abcdef"),
+	};
+	let mut expected = vec![32];
+	expected.extend_from_slice(&bytes);
+	expected.extend_from_slice(&bytes);
+	expected.push(2);
+	expected.extend_from_slice(&bytes);
+	assert_eq!(
+		serde_avro_fast::to_datum_vec(
+			&as_strings,
+			&mut serde_avro_fast::ser::SerializerConfig::new(&schema)
+		)
+		.unwrap(),
+		expected
+	);
+}
+
+#[cfg(not(feature = "uuid"))]
+#[test]
+fn fixed_as_raw_string() {
+	let schema: Schema = SCHEMA.parse().unwrap();
+
+	// Without the `uuid` feature, fixed uuids are read as utf-8 strings, as for
+	// any other fixed
+	let mut serialized = vec![64];
+	serialized.extend_from_slice(b"0123456789abcdeffedcba9876543210");
+	serialized.extend_from_slice(b"0123456789abcdef");
+	serialized.push(2);
+	serialized.extend_from_slice(b"0123456789abcdef");
+
+	let deserialized: Uuids<String> =
+		serde_avro_fast::from_datum_slice(&serialized, &schema).unwrap();
+	assert_eq!(
+		deserialized,
+		Uuids {
+			string: "0123456789abcdeffedcba9876543210".to_owned(),
+			fixed: "0123456789abcdef".to_owned(),
+			optional_fixed: Some("0123456789abcdef".to_owned()),
+		}
+	);
+}